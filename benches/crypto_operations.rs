@@ -25,12 +25,13 @@
 //! compared to classical algorithms.
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use libp2p_identity::{KeyType, Keypair, PublicKey};
+use libp2p_identity::{Keypair, PublicKey};
 
 const MESSAGE_SIZES: &[usize] = &[32, 64, 128, 256, 512, 1024, 2048, 4096];
 const SMALL_MESSAGE: &[u8] = b"Hello, libp2p world!";
 const MEDIUM_MESSAGE: &[u8] = &[0u8; 256];
 const LARGE_MESSAGE: &[u8] = &[0u8; 4096];
+const BATCH_SIZES: &[usize] = &[8, 32, 128];
 
 // Key generation benchmarks
 fn bench_key_generation(c: &mut Criterion) {
@@ -68,6 +69,34 @@ fn bench_key_generation(c: &mut Criterion) {
     group.finish();
 }
 
+// Generating many Dilithium keys one by one compared to `generate_dilithium_batch`,
+// which only runs in parallel with the `parallel` feature
+fn bench_batch_key_generation(c: &mut Criterion) {
+    const KEYS: usize = 256;
+
+    let mut group = c.benchmark_group("batch_key_generation");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(KEYS as u64));
+
+    group.bench_function(BenchmarkId::new("🛡️ serial", KEYS), |b| {
+        b.iter(|| {
+            black_box(
+                (0..KEYS)
+                    .map(|_| Keypair::generate_dilithium())
+                    .collect::<Vec<_>>(),
+            );
+        })
+    });
+
+    group.bench_function(BenchmarkId::new("🛡️ batch", KEYS), |b| {
+        b.iter(|| {
+            black_box(Keypair::generate_dilithium_batch(KEYS));
+        })
+    });
+
+    group.finish();
+}
+
 // Signing benchmarks
 fn bench_signing(c: &mut Criterion) {
     let mut group = c.benchmark_group("signing");
@@ -201,6 +230,56 @@ fn bench_verification(c: &mut Criterion) {
     group.finish();
 }
 
+// Batch verification compared to verifying each signature in a loop
+fn bench_batch_verification(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_verification");
+
+    let keypair = Keypair::generate_dilithium();
+    let pubkey = keypair.public();
+
+    for &size in BATCH_SIZES {
+        let messages: Vec<Vec<u8>> = (0..size).map(|i| vec![i as u8; 64]).collect();
+        let signatures: Vec<Vec<u8>> = messages
+            .iter()
+            .map(|msg| keypair.sign(msg).unwrap())
+            .collect();
+        let items: Vec<(&[u8], &[u8])> = messages
+            .iter()
+            .zip(&signatures)
+            .map(|(msg, sig)| (msg.as_slice(), sig.as_slice()))
+            .collect();
+
+        group.throughput(Throughput::Elements(size as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("🛡️ loop", size),
+            &items,
+            |b, items| {
+                b.iter(|| {
+                    black_box(
+                        items
+                            .iter()
+                            .map(|(msg, sig)| pubkey.verify(msg, sig))
+                            .collect::<Vec<_>>(),
+                    );
+                })
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("🛡️ batch", size),
+            &items,
+            |b, items| {
+                b.iter(|| {
+                    black_box(pubkey.verify_batch(items));
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
 // Throughput benchmarks for different message sizes
 fn bench_dilithium_throughput(c: &mut Criterion) {
     let mut group = c.benchmark_group("dilithium_throughput");
@@ -238,6 +317,122 @@ fn bench_dilithium_throughput(c: &mut Criterion) {
     group.finish();
 }
 
+// Verification without the checked entry point's overhead
+fn bench_verify_unchecked(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify_unchecked");
+
+    let keypair = Keypair::generate_dilithium();
+    let pubkey = keypair.public();
+    let signature = keypair.sign(MEDIUM_MESSAGE).unwrap();
+
+    group.bench_function("🛡️ verify", |b| {
+        b.iter(|| {
+            black_box(pubkey.verify(MEDIUM_MESSAGE, &signature));
+        })
+    });
+
+    group.bench_function("🛡️ verify_unchecked", |b| {
+        b.iter(|| {
+            black_box(pubkey.verify_unchecked(MEDIUM_MESSAGE, &signature));
+        })
+    });
+
+    group.finish();
+}
+
+// One key verifying many signatures, with and without precomputed state
+fn bench_prepared_public_key(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prepared_public_key");
+
+    let keypair = Keypair::generate_dilithium();
+    let pubkey = keypair.public();
+    let items: Vec<(Vec<u8>, Vec<u8>)> = (0..1000u32)
+        .map(|i| {
+            let msg = i.to_be_bytes().repeat(8);
+            let sig = keypair.sign(&msg).unwrap();
+            (msg, sig)
+        })
+        .collect();
+
+    group.throughput(Throughput::Elements(items.len() as u64));
+
+    group.bench_function("🛡️ verify", |b| {
+        b.iter(|| {
+            for (msg, sig) in &items {
+                black_box(pubkey.verify(msg, sig));
+            }
+        })
+    });
+
+    group.bench_function("🛡️ prepared", |b| {
+        let prepared = pubkey.clone().into_prepared();
+        b.iter(|| {
+            for (msg, sig) in &items {
+                black_box(prepared.verify(msg, sig));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+// Decoding a peer store worth of public keys
+fn bench_decode_many(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_many");
+
+    let encoded: Vec<Vec<u8>> = (0..1000)
+        .map(|_| Keypair::generate_dilithium().public().encode_protobuf())
+        .collect();
+    let blobs: Vec<&[u8]> = encoded.iter().map(Vec::as_slice).collect();
+
+    group.throughput(Throughput::Elements(blobs.len() as u64));
+
+    group.bench_function("🛡️ try_decode_protobuf loop", |b| {
+        b.iter(|| {
+            let keys: Vec<_> = blobs
+                .iter()
+                .map(|blob| PublicKey::try_decode_protobuf(blob))
+                .collect();
+            black_box(keys);
+        })
+    });
+
+    group.bench_function("🛡️ decode_many", |b| {
+        b.iter(|| {
+            black_box(PublicKey::decode_many(&blobs));
+        })
+    });
+
+    group.finish();
+}
+
+// Signing a batch of messages with one key
+fn bench_sign_many(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sign_many");
+
+    let keypair = Keypair::generate_dilithium();
+    let messages: Vec<Vec<u8>> = (0..100u32).map(|i| i.to_be_bytes().repeat(64)).collect();
+    let messages: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+
+    group.throughput(Throughput::Elements(messages.len() as u64));
+
+    group.bench_function("🛡️ sign_many/100", |b| {
+        b.iter(|| {
+            black_box(keypair.sign_many(&messages).unwrap());
+        })
+    });
+
+    group.bench_function("🛡️ sign loop/100", |b| {
+        b.iter(|| {
+            for msg in &messages {
+                black_box(keypair.sign(msg).unwrap());
+            }
+        })
+    });
+
+    group.finish();
+}
+
 // Protobuf serialization benchmarks
 fn bench_protobuf_operations(c: &mut Criterion) {
     let mut group = c.benchmark_group("protobuf_operations");
@@ -394,9 +589,15 @@ fn bench_memory_usage(c: &mut Criterion) {
 criterion_group!(
     crypto_benches,
     bench_key_generation,
+    bench_batch_key_generation,
     bench_signing,
     bench_verification,
+    bench_batch_verification,
     bench_dilithium_throughput,
+    bench_sign_many,
+    bench_verify_unchecked,
+    bench_prepared_public_key,
+    bench_decode_many,
     bench_protobuf_operations,
     bench_memory_usage
 );
@@ -0,0 +1,206 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Configurable keypair generation.
+
+#[cfg(feature = "dilithium")]
+use crate::DilithiumLevel;
+use crate::{KeyType, Keypair, SigningError};
+use rand::{CryptoRng, RngCore};
+
+/// Generates a [`Keypair`] from a set of options, as an alternative to the
+/// `Keypair::generate_*` constructors.
+///
+/// ```
+/// # use libp2p_identity::{DilithiumLevel, KeyType, KeypairBuilder};
+/// let keypair = KeypairBuilder::new(KeyType::Dilithium)
+///     .level(DilithiumLevel::Level3)
+///     .rng(&mut rand::thread_rng())
+///     .build()
+///     .unwrap();
+/// assert_eq!(keypair.key_type(), KeyType::Dilithium);
+/// ```
+///
+/// Options that do not apply to the key type are rejected by [`KeypairBuilder::build`]
+/// rather than ignored.
+#[derive(Debug, Clone)]
+pub struct KeypairBuilder<R = rand::rngs::ThreadRng> {
+    key_type: KeyType,
+    #[cfg(feature = "dilithium")]
+    level: Option<DilithiumLevel>,
+    rng: Option<R>,
+}
+
+impl KeypairBuilder {
+    /// Start building a keypair of the given type, seeded from the thread-local RNG
+    /// and, for Dilithium, at the default [`DilithiumLevel`].
+    pub fn new(key_type: KeyType) -> Self {
+        KeypairBuilder {
+            key_type,
+            #[cfg(feature = "dilithium")]
+            level: None,
+            rng: None,
+        }
+    }
+}
+
+impl<R: RngCore + CryptoRng> KeypairBuilder<R> {
+    /// Set the security level of a Dilithium keypair.
+    ///
+    /// Only valid for [`KeyType::Dilithium`].
+    #[cfg(feature = "dilithium")]
+    pub fn level(mut self, level: DilithiumLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Seed the keypair from `rng` instead of the thread-local RNG, e.g. a seeded
+    /// `ChaCha20Rng` for reproducible tests.
+    pub fn rng<R2: RngCore + CryptoRng>(self, rng: R2) -> KeypairBuilder<R2> {
+        KeypairBuilder {
+            key_type: self.key_type,
+            #[cfg(feature = "dilithium")]
+            level: self.level,
+            rng: Some(rng),
+        }
+    }
+
+    /// Generate the keypair.
+    ///
+    /// Returns [`SigningError::UnsupportedKeyType`] for key types not enabled in this
+    /// build and for RSA, which has no key generation, and
+    /// [`SigningError::UnsupportedOption`] for options that do not apply to the key
+    /// type.
+    pub fn build(mut self) -> Result<Keypair, SigningError> {
+        #[cfg(feature = "dilithium")]
+        if self.level.is_some() && self.key_type != KeyType::Dilithium {
+            return Err(SigningError::UnsupportedOption {
+                key_type: self.key_type,
+                option: "level",
+            });
+        }
+
+        match self.rng.take() {
+            Some(mut rng) => self.generate(&mut rng),
+            None => self.generate(&mut rand::thread_rng()),
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn generate<R2: RngCore + CryptoRng>(&self, rng: &mut R2) -> Result<Keypair, SigningError> {
+        match self.key_type {
+            #[cfg(feature = "ed25519")]
+            KeyType::Ed25519 => Ok(Keypair::generate_ed25519_with_rng(rng)),
+            #[cfg(feature = "secp256k1")]
+            KeyType::Secp256k1 => Ok(Keypair::generate_secp256k1_with_rng(rng)),
+            #[cfg(feature = "ecdsa")]
+            KeyType::Ecdsa => Ok(Keypair::generate_ecdsa_with_rng(rng)),
+            #[cfg(feature = "dilithium")]
+            KeyType::Dilithium => Ok(Keypair::generate_dilithium_level_with_rng(
+                self.level.unwrap_or_default(),
+                rng,
+            )),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyType::HybridDilithiumEd25519 => {
+                Ok(Keypair::generate_hybrid_dilithium_ed25519_with_rng(rng))
+            }
+            #[cfg(feature = "sphincs-plus")]
+            KeyType::SphincsPlus => Ok(Keypair::generate_sphincs_plus_with_rng(rng)),
+            #[cfg(feature = "falcon")]
+            KeyType::Falcon => Ok(Keypair::generate_falcon_with_rng(rng)),
+            #[allow(unreachable_patterns)]
+            key_type => Err(SigningError::UnsupportedKeyType(key_type)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn dilithium_with_level_and_rng() {
+        use rand::SeedableRng as _;
+
+        let build = || {
+            KeypairBuilder::new(KeyType::Dilithium)
+                .level(DilithiumLevel::Level3)
+                .rng(rand_chacha::ChaCha20Rng::seed_from_u64(7))
+                .build()
+                .unwrap()
+        };
+
+        let keypair = build();
+        assert_eq!(keypair.key_type(), KeyType::Dilithium);
+        assert_eq!(
+            keypair.public().encode_protobuf().len(),
+            Keypair::generate_dilithium_level(DilithiumLevel::Level3)
+                .public()
+                .encode_protobuf()
+                .len()
+        );
+        assert_eq!(build().public(), keypair.public());
+
+        let default = KeypairBuilder::new(KeyType::Dilithium).build().unwrap();
+        assert_eq!(
+            default.public().encode_protobuf().len(),
+            Keypair::generate_dilithium()
+                .public()
+                .encode_protobuf()
+                .len()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn level_on_ed25519_is_rejected() {
+        let err = KeypairBuilder::new(KeyType::Ed25519)
+            .level(DilithiumLevel::Level2)
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            SigningError::UnsupportedOption {
+                key_type: KeyType::Ed25519,
+                option: "level"
+            }
+        ));
+        assert!(KeypairBuilder::new(KeyType::Ed25519).build().is_ok());
+    }
+
+    #[test]
+    #[cfg(not(feature = "falcon"))]
+    fn disabled_key_type_is_rejected() {
+        assert!(matches!(
+            KeypairBuilder::new(KeyType::Falcon).build(),
+            Err(SigningError::UnsupportedKeyType(KeyType::Falcon))
+        ));
+    }
+
+    #[test]
+    fn rsa_is_rejected() {
+        assert!(matches!(
+            KeypairBuilder::new(KeyType::RSA).build(),
+            Err(SigningError::UnsupportedKeyType(KeyType::RSA))
+        ));
+    }
+}
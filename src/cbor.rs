@@ -0,0 +1,144 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Deterministic CBOR encoding of public keys, for IPLD and other content addressed
+//! systems.
+//!
+//! A key is a map of its protobuf key type tag and its raw key bytes, in the core
+//! deterministic encoding of RFC 8949: shortest integer and length forms, definite
+//! lengths, and map keys in bytewise order.
+//!
+//! ```text
+//! { "key": h'<raw public key bytes>', "type": 4 }
+//! ```
+
+use crate::{error::DecodingError, keypair::PublicKey, KeyType};
+use ciborium::Value;
+
+const KEY: &str = "key";
+const TYPE: &str = "type";
+
+/// Encode a public key as a deterministic CBOR map.
+pub(crate) fn encode(key: &PublicKey) -> Vec<u8> {
+    // "key" sorts before "type" as its encoding is one byte shorter.
+    let map = Value::Map(vec![
+        (Value::Text(KEY.to_string()), Value::Bytes(key.raw_bytes())),
+        (
+            Value::Text(TYPE.to_string()),
+            Value::Integer(key.key_type().to_protobuf_tag().into()),
+        ),
+    ]);
+
+    let mut buf = Vec::new();
+    ciborium::into_writer(&map, &mut buf).expect("writing to a Vec cannot fail");
+    buf
+}
+
+/// Decode a CBOR map produced by [`encode`].
+///
+/// Only the deterministic encoding is accepted, so a key has exactly one valid CBOR
+/// form and its content address cannot be varied.
+pub(crate) fn decode(bytes: &[u8]) -> Result<PublicKey, DecodingError> {
+    let value: Value = ciborium::from_reader(bytes)
+        .map_err(|e| DecodingError::new(format!("failed to parse CBOR public key: {e}")))?;
+
+    let Value::Map(entries) = value else {
+        return Err(DecodingError::new(
+            "CBOR public key must be a map".to_string(),
+        ));
+    };
+
+    let mut key = None;
+    let mut tag = None;
+    for (name, value) in entries {
+        match (name.as_text(), value) {
+            (Some(KEY), Value::Bytes(bytes)) if key.is_none() => key = Some(bytes),
+            (Some(TYPE), Value::Integer(int)) if tag.is_none() => tag = Some(int),
+            (name, _) => {
+                return Err(DecodingError::new(format!(
+                    "unexpected or duplicate CBOR public key field {name:?}"
+                )))
+            }
+        }
+    }
+
+    let key =
+        key.ok_or_else(|| DecodingError::new(format!("CBOR public key is missing `{KEY}`")))?;
+    let tag = tag.ok_or(DecodingError::MissingKeyType)?;
+    let tag = i32::try_from(tag)
+        .map_err(|_| DecodingError::new("CBOR public key type is out of range".to_string()))?;
+    let key_type = KeyType::from_protobuf_tag(tag).ok_or(DecodingError::UnknownKeyType(tag))?;
+
+    let public = PublicKey::from_raw_bytes(key_type, &key)?;
+    if encode(&public) != bytes {
+        return Err(DecodingError::new(
+            "CBOR public key is not in deterministic encoding".to_string(),
+        ));
+    }
+
+    Ok(public)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn layout_is_deterministic() {
+        let key = crate::Keypair::generate_dilithium().public();
+        let cbor = encode(&key);
+
+        // map(2), "key", bytes(2592) with a two-byte length, ..., "type", 4
+        assert_eq!(
+            &cbor[..8],
+            &[0xa2, 0x63, b'k', b'e', b'y', 0x59, 0x0a, 0x20]
+        );
+        assert!(cbor.ends_with(&[0x64, b't', b'y', b'p', b'e', 0x04]));
+        assert_eq!(encode(&key), cbor);
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn non_deterministic_encodings_are_rejected() {
+        let key = crate::Keypair::generate_dilithium().public();
+        let raw = key.raw_bytes();
+
+        let mut reordered = Vec::new();
+        let map = Value::Map(vec![
+            (Value::Text(TYPE.to_string()), Value::Integer(4.into())),
+            (Value::Text(KEY.to_string()), Value::Bytes(raw)),
+        ]);
+        ciborium::into_writer(&map, &mut reordered).unwrap();
+        assert!(decode(&reordered).is_err());
+
+        let mut trailing = encode(&key);
+        trailing.push(0);
+        assert!(decode(&trailing).is_err());
+    }
+
+    #[test]
+    fn malformed_cbor_is_rejected() {
+        assert!(decode(&[]).is_err());
+        assert!(decode(&[0x80]).is_err());
+        assert!(decode(&[0xa1, 0x64, b't', b'y', b'p', b'e', 0x04]).is_err());
+        assert!(decode(&[0xa1, 0x63, b'k', b'e', b'y', 0x40]).is_err());
+    }
+}
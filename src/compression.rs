@@ -0,0 +1,119 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! zstd compression of signatures for storage.
+//!
+//! A compressed signature is the varint-encoded length of the signature followed by
+//! its zstd frame. Signatures are high-entropy, so this rarely saves much, but it
+//! lets callers apply compression uniformly to everything they store.
+
+use crate::error::DecodingError;
+
+/// The longest signature [`decompress_signature`] accepts, above the 49,856 bytes of
+/// the largest signatures, SPHINCS+ ones.
+pub const MAX_DECOMPRESSED_SIGNATURE_LEN: usize = 64 * 1024;
+
+const LEVEL: i32 = 3;
+
+/// Compress a signature with zstd, prefixed with its length.
+///
+/// Signatures longer than [`MAX_DECOMPRESSED_SIGNATURE_LEN`] are compressed too, but
+/// [`decompress_signature`] rejects them.
+pub fn compress_signature(sig: &[u8]) -> Vec<u8> {
+    let mut len_buf = unsigned_varint::encode::usize_buffer();
+    let mut out = unsigned_varint::encode::usize(sig.len(), &mut len_buf).to_vec();
+    out.extend_from_slice(
+        &zstd::bulk::compress(sig, LEVEL).expect("compressing into memory does not fail"),
+    );
+    out
+}
+
+/// Decompress a signature produced by [`compress_signature`].
+///
+/// The length header is checked against [`MAX_DECOMPRESSED_SIGNATURE_LEN`] before
+/// anything is allocated, and the decompressed signature must have exactly that
+/// length.
+pub fn decompress_signature(data: &[u8]) -> Result<Vec<u8>, DecodingError> {
+    let (len, frame) = unsigned_varint::decode::usize(data)
+        .map_err(|e| DecodingError::failed_to_parse("compressed signature length", e))?;
+    if len > MAX_DECOMPRESSED_SIGNATURE_LEN {
+        return Err(DecodingError::new(format!(
+            "failed to parse compressed signature: length {len} exceeds the maximum of {MAX_DECOMPRESSED_SIGNATURE_LEN}"
+        )));
+    }
+
+    let sig = zstd::bulk::decompress(frame, len)
+        .map_err(|e| DecodingError::failed_to_parse("compressed signature", e))?;
+    if sig.len() != len {
+        return Err(DecodingError::new(format!(
+            "failed to parse compressed signature: expected {len} bytes, got {}",
+            sig.len()
+        )));
+    }
+    Ok(sig)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn dilithium_signature_roundtrip() {
+        let sig = crate::Keypair::generate_dilithium().sign(b"hello").unwrap();
+
+        let compressed = compress_signature(&sig);
+        assert_eq!(decompress_signature(&compressed).unwrap(), sig);
+    }
+
+    #[test]
+    fn empty_and_redundant_signatures_roundtrip() {
+        for sig in [vec![], vec![0u8; 4096]] {
+            let compressed = compress_signature(&sig);
+            assert_eq!(decompress_signature(&compressed).unwrap(), sig);
+        }
+        assert!(compress_signature(&[0u8; 4096]).len() < 100);
+    }
+
+    #[test]
+    fn corrupted_header_is_rejected() {
+        let sig = (0..=255u8).cycle().take(4627).collect::<Vec<_>>();
+        let compressed = compress_signature(&sig);
+        let (_, frame) = unsigned_varint::decode::usize(&compressed).unwrap();
+
+        let with_len = |len: usize| {
+            let mut buf = unsigned_varint::encode::usize_buffer();
+            [unsigned_varint::encode::usize(len, &mut buf), frame].concat()
+        };
+
+        // Missing or truncated length.
+        assert!(decompress_signature(&[]).is_err());
+        assert!(decompress_signature(&[0x80]).is_err());
+        // Length above the limit is rejected before decompressing.
+        assert!(decompress_signature(&with_len(MAX_DECOMPRESSED_SIGNATURE_LEN + 1)).is_err());
+        // Length that does not match the frame.
+        assert!(decompress_signature(&with_len(sig.len() - 1)).is_err());
+        assert!(decompress_signature(&with_len(sig.len() + 1)).is_err());
+        // Corrupted frame.
+        let mut corrupted = compressed.clone();
+        corrupted[3] ^= 0xff;
+        assert!(decompress_signature(&corrupted).is_err());
+    }
+}
@@ -0,0 +1,1359 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Dilithium (ML-DSA) keys.
+//!
+//! With only the `dilithium-verify` feature, just [`PublicKey`] and its verifiers are
+//! available, and the module builds without the standard library.
+//!
+//! Everything here calls the backend's public signing and verification API, except with
+//! the opt-in `dilithium-internals` feature. That feature moves streaming, batch and
+//! prepared-key operations onto an in-crate copy of the ML-DSA steps, built on the
+//! backend's internal modules, so that they can stop at the message representative and
+//! reuse the expanded key. Without it, [`Signer`] and [`Verifier`] buffer the message and
+//! the batch and prepared operations verify or sign one message at a time.
+
+use super::error::DecodingError;
+#[cfg(feature = "dilithium")]
+use super::error::{SigningError, ValidationError};
+#[cfg(feature = "dilithium-internals")]
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(all(feature = "dilithium", feature = "dilithium-internals"))]
+use qp_rusty_crystals_dilithium::params::CRHBYTES;
+#[cfg(feature = "dilithium")]
+use qp_rusty_crystals_dilithium::params::SEEDBYTES;
+#[cfg(any(feature = "dilithium", feature = "dilithium-internals"))]
+use qp_rusty_crystals_dilithium::params::TR_BYTES;
+use qp_rusty_crystals_dilithium::{ml_dsa_44, ml_dsa_65, ml_dsa_87};
+#[cfg(feature = "dilithium")]
+use zeroize::Zeroize;
+
+#[cfg(feature = "dilithium-internals")]
+#[macro_use]
+mod ml_dsa;
+
+#[cfg(feature = "dilithium-internals")]
+use ml_dsa::MessageHasher;
+
+/// Hash a packed public key into the `tr` value that prefixes every message representative.
+#[cfg(any(feature = "dilithium", feature = "dilithium-internals"))]
+fn public_key_hash(pk: &[u8]) -> [u8; TR_BYTES] {
+    let mut tr = [0u8; TR_BYTES];
+    qp_rusty_crystals_dilithium::fips202::shake256(&mut tr, TR_BYTES, pk, pk.len());
+    tr
+}
+
+/// The parameter set of a Dilithium key, named after its NIST security level.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DilithiumLevel {
+    /// ML-DSA-44.
+    Level2,
+    /// ML-DSA-65.
+    Level3,
+    /// ML-DSA-87.
+    #[default]
+    Level5,
+}
+
+impl DilithiumLevel {
+    pub(crate) const ALL: [DilithiumLevel; 3] = [
+        DilithiumLevel::Level2,
+        DilithiumLevel::Level3,
+        DilithiumLevel::Level5,
+    ];
+
+    /// Length in bytes of a public key at this level.
+    pub const fn public_key_len(self) -> usize {
+        match self {
+            DilithiumLevel::Level2 => ml_dsa_44::PUBLICKEYBYTES,
+            DilithiumLevel::Level3 => ml_dsa_65::PUBLICKEYBYTES,
+            DilithiumLevel::Level5 => ml_dsa_87::PUBLICKEYBYTES,
+        }
+    }
+
+    /// Length in bytes of a secret key at this level.
+    pub const fn secret_key_len(self) -> usize {
+        match self {
+            DilithiumLevel::Level2 => ml_dsa_44::SECRETKEYBYTES,
+            DilithiumLevel::Level3 => ml_dsa_65::SECRETKEYBYTES,
+            DilithiumLevel::Level5 => ml_dsa_87::SECRETKEYBYTES,
+        }
+    }
+
+    /// Length in bytes of a signature at this level.
+    pub const fn signature_len(self) -> usize {
+        match self {
+            DilithiumLevel::Level2 => ml_dsa_44::SIGNBYTES,
+            DilithiumLevel::Level3 => ml_dsa_65::SIGNBYTES,
+            DilithiumLevel::Level5 => ml_dsa_87::SIGNBYTES,
+        }
+    }
+
+    /// The number of polynomials in `s1` and `s2`, and the bound `eta` on their
+    /// coefficients.
+    #[cfg(feature = "dilithium")]
+    const fn secret_vector_params(self) -> (usize, usize, u32) {
+        use qp_rusty_crystals_dilithium::params;
+
+        match self {
+            DilithiumLevel::Level2 => (
+                params::ml_dsa_44::L,
+                params::ml_dsa_44::K,
+                params::ml_dsa_44::ETA as u32,
+            ),
+            DilithiumLevel::Level3 => (
+                params::ml_dsa_65::L,
+                params::ml_dsa_65::K,
+                params::ml_dsa_65::ETA as u32,
+            ),
+            DilithiumLevel::Level5 => (
+                params::ml_dsa_87::L,
+                params::ml_dsa_87::K,
+                params::ml_dsa_87::ETA as u32,
+            ),
+        }
+    }
+
+    fn from_public_key_len(len: usize) -> Option<DilithiumLevel> {
+        Self::ALL.into_iter().find(|l| l.public_key_len() == len)
+    }
+
+    #[cfg(feature = "dilithium")]
+    fn from_secret_key_len(len: usize) -> Option<DilithiumLevel> {
+        Self::ALL.into_iter().find(|l| l.secret_key_len() == len)
+    }
+
+    #[cfg(feature = "dilithium")]
+    fn from_keypair_len(len: usize) -> Option<DilithiumLevel> {
+        Self::ALL
+            .into_iter()
+            .find(|l| l.secret_key_len() + l.public_key_len() == len)
+    }
+}
+
+impl fmt::Display for DilithiumLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DilithiumLevel::Level2 => f.write_str("ML-DSA-44"),
+            DilithiumLevel::Level3 => f.write_str("ML-DSA-65"),
+            DilithiumLevel::Level5 => f.write_str("ML-DSA-87"),
+        }
+    }
+}
+
+#[cfg(feature = "dilithium")]
+/// A Dilithium keypair.
+#[derive(Clone)]
+pub struct Keypair {
+    secret: SecretKey,
+    public: PublicKey,
+}
+
+#[cfg(feature = "dilithium")]
+impl Keypair {
+    /// Generate a new random Dilithium keypair at the given level.
+    #[cfg(feature = "rand")]
+    pub fn generate(level: DilithiumLevel) -> Keypair {
+        Keypair::generate_with_rng(level, &mut rand::thread_rng())
+    }
+
+    /// Generate a new Dilithium keypair at the given level, seeded from `rng`.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: rand::RngCore + rand::CryptoRng>(
+        level: DilithiumLevel,
+        rng: &mut R,
+    ) -> Keypair {
+        let mut seed = zeroize::Zeroizing::new([0u8; 32]);
+        rng.fill_bytes(seed.as_mut());
+        Keypair::from_seed(level, &seed)
+    }
+
+    /// Like [`Keypair::generate_with_rng`], but returns the error of the RNG instead of
+    /// panicking if it cannot provide the seed.
+    #[cfg(feature = "rand")]
+    pub fn try_generate_with_rng<R: rand::RngCore + rand::CryptoRng>(
+        level: DilithiumLevel,
+        rng: &mut R,
+    ) -> Result<Keypair, rand::Error> {
+        let mut seed = zeroize::Zeroizing::new([0u8; 32]);
+        rng.try_fill_bytes(seed.as_mut())?;
+        Ok(Keypair::from_seed(level, &seed))
+    }
+
+    /// Deterministically expand a 32-byte seed into a keypair at the given level,
+    /// using the ML-DSA key generation seed expansion.
+    ///
+    /// The same seed and level always yield the same keypair.
+    pub fn from_seed(level: DilithiumLevel, seed: &[u8; 32]) -> Keypair {
+        // The backend keypairs are plain arrays, so wipe them once copied out.
+        let (secret, public) = match level {
+            DilithiumLevel::Level2 => {
+                let mut kp = ml_dsa_44::Keypair::generate(Some(seed));
+                let bytes = (kp.secret.bytes.to_vec(), kp.public.bytes.to_vec());
+                kp.secret.bytes.zeroize();
+                bytes
+            }
+            DilithiumLevel::Level3 => {
+                let mut kp = ml_dsa_65::Keypair::generate(Some(seed));
+                let bytes = (kp.secret.bytes.to_vec(), kp.public.bytes.to_vec());
+                kp.secret.bytes.zeroize();
+                bytes
+            }
+            DilithiumLevel::Level5 => {
+                let mut kp = ml_dsa_87::Keypair::generate(Some(seed));
+                let bytes = (kp.secret.bytes.to_vec(), kp.public.bytes.to_vec());
+                kp.secret.bytes.zeroize();
+                bytes
+            }
+        };
+
+        Keypair {
+            secret: SecretKey {
+                level,
+                bytes: secret,
+            },
+            public: PublicKey {
+                level,
+                bytes: public,
+            },
+        }
+    }
+
+    /// Encode the keypair as the concatenation of the secret key and public key bytes.
+    ///
+    /// The packed FIPS 204 encodings are byte strings with a fixed bit layout, so the
+    /// output is the same on little- and big-endian hosts.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.encoded_len());
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    /// The length of the encoding produced by [`Keypair::to_bytes`].
+    pub(crate) fn encoded_len(&self) -> usize {
+        self.secret.bytes.len() + self.public.bytes.len()
+    }
+
+    /// Append the encoding produced by [`Keypair::to_bytes`] to `out`.
+    ///
+    /// The secret key is copied with a plain memory copy that does not depend on its
+    /// contents. `out` does not reallocate if it has [`Keypair::encoded_len`] spare
+    /// capacity.
+    pub(crate) fn write_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.secret.bytes);
+        out.extend_from_slice(&self.public.bytes);
+    }
+
+    /// Try to parse a keypair from the format produced by [`Keypair::to_bytes`].
+    ///
+    /// The level is inferred from the length of the input. The public key must be the
+    /// one the secret key commits to: both share the seed `rho`, and the secret key
+    /// holds the hash `tr` of the public key.
+    ///
+    /// Only the length of the input, `rho`, `tr` and the public key affect the timing,
+    /// and all of them are public: `rho` and `tr` are compared with ordinary early-exit
+    /// comparisons, while the secret parts `key`, `s1`, `s2` and `t0` are only copied.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Keypair, DecodingError> {
+        let level = DilithiumLevel::from_keypair_len(bytes.len()).ok_or_else(|| {
+            DecodingError::failed_to_parse("Dilithium keypair", UnexpectedLength(bytes.len()))
+        })?;
+        let (secret, public) = bytes.split_at(level.secret_key_len());
+
+        let rho_matches = secret[..SEEDBYTES] == public[..SEEDBYTES];
+        let tr_matches = secret[2 * SEEDBYTES..2 * SEEDBYTES + TR_BYTES] == public_key_hash(public);
+        if !(rho_matches && tr_matches) {
+            return Err(DecodingError::failed_to_parse(
+                "Dilithium keypair",
+                MismatchedPublicKey,
+            ));
+        }
+
+        Ok(Keypair {
+            secret: SecretKey {
+                level,
+                bytes: secret.to_vec(),
+            },
+            public: PublicKey {
+                level,
+                bytes: public.to_vec(),
+            },
+        })
+    }
+
+    /// Sign a message using the private key of this keypair.
+    ///
+    /// Messages of any length can be signed, including empty ones; they are hashed in
+    /// place, never copied. Use [`Keypair::signer`] to sign large messages without
+    /// holding them in memory.
+    ///
+    /// Signing is hedged: fresh randomness from the operating system is mixed into every
+    /// signature, so signing the same message twice yields different signatures. This
+    /// hardens signing against fault and side-channel attacks that exploit repeated
+    /// computations. The randomness is drawn by the backend's own RNG; use
+    /// [`Keypair::sign_deterministic`] for reproducible signatures.
+    pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
+        self.secret.sign(&[], msg, true)
+    }
+
+    /// Sign a message with deterministic ML-DSA: the same key and message always give
+    /// the same signature, e.g. for reproducible tests.
+    ///
+    /// Both this and [`Keypair::sign`] verify under the same public key.
+    pub fn sign_deterministic(&self, msg: &[u8]) -> Vec<u8> {
+        self.secret.sign(&[], msg, false)
+    }
+
+    /// Sign every message in `msgs` like [`Keypair::sign`], returning the signatures in
+    /// the same order.
+    ///
+    /// With the `dilithium-internals` feature, the secret key is unpacked and the public
+    /// matrix expanded once for the whole batch rather than once per message, and the
+    /// randomness of every signature is drawn from the operating system. Otherwise each
+    /// message is signed with [`Keypair::sign`].
+    pub fn sign_many(&self, msgs: &[&[u8]]) -> Vec<Vec<u8>> {
+        #[cfg(feature = "dilithium-internals")]
+        {
+            let mut rnds = vec![[0u8; SEEDBYTES]; msgs.len()];
+            for rnd in &mut rnds {
+                rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, rnd);
+            }
+            self.secret.sign_many(&[], msgs, &rnds)
+        }
+        #[cfg(not(feature = "dilithium-internals"))]
+        msgs.iter().map(|msg| self.sign(msg)).collect()
+    }
+
+    /// Sign a message bound to the context string `ctx`, using the ML-DSA context field.
+    ///
+    /// The signature only verifies with [`PublicKey::verify_with_context`] under the
+    /// same context. An empty context is the same as [`Keypair::sign`].
+    ///
+    /// Contexts longer than 255 bytes are rejected with [`SigningError::ContextTooLong`].
+    pub fn sign_with_context(&self, ctx: &[u8], msg: &[u8]) -> Result<Vec<u8>, SigningError> {
+        if ctx.len() > 255 {
+            return Err(SigningError::ContextTooLong(ctx.len()));
+        }
+        Ok(self.secret.sign(ctx, msg, true))
+    }
+
+    /// Start signing a message that is fed in chunks.
+    ///
+    /// The message is signed like [`Keypair::sign`] signs the concatenation of all
+    /// chunks. Only with the `dilithium-internals` feature is it signed without holding
+    /// the whole message in memory; otherwise the chunks are buffered.
+    pub fn signer(&self) -> Signer {
+        Signer {
+            #[cfg(feature = "dilithium-internals")]
+            hasher: MessageHasher::new(&self.secret.tr()),
+            #[cfg(not(feature = "dilithium-internals"))]
+            msg: Vec::new(),
+            secret: self.secret.clone(),
+        }
+    }
+
+    /// Get the public key of this keypair.
+    pub fn public(&self) -> &PublicKey {
+        &self.public
+    }
+
+    /// Get the secret key of this keypair.
+    pub fn secret(&self) -> &SecretKey {
+        &self.secret
+    }
+
+    /// Get the level of this keypair.
+    pub fn level(&self) -> DilithiumLevel {
+        self.secret.level
+    }
+
+    /// Check the keypair beyond the length and hash checks done when it is decoded.
+    ///
+    /// Every coefficient of the secret vectors `s1` and `s2` must lie in `[-eta, eta]`,
+    /// and a deterministic signature made with the secret key must verify under this
+    /// keypair's public key, a pairwise consistency test. Keys failing either check
+    /// produce signatures that never verify.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        const MESSAGE: &[u8] = b"libp2p-pqc-pairwise-consistency";
+
+        if !self.secret.has_valid_coefficients() {
+            return Err(ValidationError::SecretCoefficientOutOfRange);
+        }
+        if !self
+            .public
+            .verify(MESSAGE, &self.secret.sign(&[], MESSAGE, false))
+        {
+            return Err(ValidationError::MismatchedPublicKey);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "dilithium")]
+impl fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keypair")
+            .field("public", &self.public)
+            .finish()
+    }
+}
+
+#[cfg(feature = "dilithium")]
+/// Demote a Dilithium keypair to a secret key.
+impl From<Keypair> for SecretKey {
+    fn from(kp: Keypair) -> SecretKey {
+        kp.secret
+    }
+}
+
+#[cfg(feature = "dilithium")]
+/// Promote a Dilithium secret key into a keypair, recomputing the public key.
+///
+/// The backend cannot recompute a public key, so this fails unless the
+/// `dilithium-internals` feature is enabled.
+impl TryFrom<SecretKey> for Keypair {
+    type Error = DecodingError;
+
+    fn try_from(secret: SecretKey) -> Result<Keypair, DecodingError> {
+        let public = secret.derive_public()?;
+        Ok(Keypair { secret, public })
+    }
+}
+
+/// A Dilithium public key.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PublicKey {
+    level: DilithiumLevel,
+    bytes: Vec<u8>,
+}
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PublicKey({}): ", self.level)?;
+        for byte in &self.bytes {
+            write!(f, "{byte:x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PublicKey {
+    /// Verify the Dilithium signature on a message using the public key.
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
+        self.verify_with_context(&[], msg, sig)
+    }
+
+    /// Verify a signature like [`PublicKey::verify`], reading the public key in place
+    /// instead of copying it into the backend's key type first.
+    ///
+    /// Signatures of the wrong length are rejected by the backend before they are
+    /// unpacked, so any input is safe to pass.
+    pub fn verify_unchecked(&self, msg: &[u8], sig: &[u8]) -> bool {
+        use qp_rusty_crystals_dilithium::sign;
+
+        // An empty context: the domain separator byte and the context length.
+        let mut m = Vec::with_capacity(msg.len() + 2);
+        m.extend_from_slice(&[0, 0]);
+        m.extend_from_slice(msg);
+
+        match self.level {
+            DilithiumLevel::Level2 => sign::ml_dsa_44::verify(sig, &m, &self.bytes),
+            DilithiumLevel::Level3 => sign::ml_dsa_65::verify(sig, &m, &self.bytes),
+            DilithiumLevel::Level5 => sign::ml_dsa_87::verify(sig, &m, &self.bytes),
+        }
+    }
+
+    /// Verify a signature made with [`Keypair::sign_with_context`] under the context `ctx`.
+    ///
+    /// Contexts longer than 255 bytes never verify.
+    pub fn verify_with_context(&self, ctx: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+        match self.level {
+            DilithiumLevel::Level2 => ml_dsa_44::PublicKey {
+                bytes: self.to_array(),
+            }
+            .verify(msg, sig, Some(ctx)),
+            DilithiumLevel::Level3 => ml_dsa_65::PublicKey {
+                bytes: self.to_array(),
+            }
+            .verify(msg, sig, Some(ctx)),
+            DilithiumLevel::Level5 => ml_dsa_87::PublicKey {
+                bytes: self.to_array(),
+            }
+            .verify(msg, sig, Some(ctx)),
+        }
+    }
+
+    /// Start verifying a signature over a message that is fed in chunks.
+    ///
+    /// The result is identical to [`PublicKey::verify`] over the concatenation of all
+    /// chunks. Without the `dilithium-internals` feature, the chunks are buffered.
+    pub fn verifier(&self) -> Verifier {
+        Verifier {
+            #[cfg(feature = "dilithium-internals")]
+            hasher: MessageHasher::new(&public_key_hash(&self.bytes)),
+            #[cfg(not(feature = "dilithium-internals"))]
+            msg: Vec::new(),
+            public: self.clone(),
+        }
+    }
+
+    /// Verify a batch of `(message, signature)` pairs, returning one result per item.
+    ///
+    /// With the `dilithium-internals` feature, the work that only depends on the public
+    /// key (unpacking it, expanding the matrix `A` and hashing the key) is done once for
+    /// the whole batch. Otherwise each item is checked with [`PublicKey::verify`].
+    pub fn verify_batch(&self, items: &[(&[u8], &[u8])]) -> Vec<bool> {
+        #[cfg(feature = "dilithium-internals")]
+        match self.level {
+            DilithiumLevel::Level2 => verify_batch!(&self.bytes, items, ml_dsa_44, lvl2),
+            DilithiumLevel::Level3 => verify_batch!(&self.bytes, items, ml_dsa_65, lvl3),
+            DilithiumLevel::Level5 => verify_batch!(&self.bytes, items, ml_dsa_87, lvl5),
+        }
+        #[cfg(not(feature = "dilithium-internals"))]
+        items
+            .iter()
+            .map(|(msg, sig)| self.verify(msg, sig))
+            .collect()
+    }
+
+    /// Precompute the verification state of this key, for a key that verifies many
+    /// signatures.
+    ///
+    /// Without the `dilithium-internals` feature there is nothing to precompute, and the
+    /// prepared key verifies like [`PublicKey::verify`].
+    pub fn into_prepared(self) -> PreparedPublicKey {
+        #[cfg(feature = "dilithium-internals")]
+        let expanded = {
+            let pk = &self.bytes[..];
+            let expanded = match self.level {
+                DilithiumLevel::Level2 => {
+                    Expanded::Level2(Box::new(expand_public_key!(pk, ml_dsa_44, lvl2)))
+                }
+                DilithiumLevel::Level3 => {
+                    Expanded::Level3(Box::new(expand_public_key!(pk, ml_dsa_65, lvl3)))
+                }
+                DilithiumLevel::Level5 => {
+                    Expanded::Level5(Box::new(expand_public_key!(pk, ml_dsa_87, lvl5)))
+                }
+            };
+            (public_key_hash(pk), expanded)
+        };
+
+        PreparedPublicKey {
+            #[cfg(feature = "dilithium-internals")]
+            tr: expanded.0,
+            #[cfg(feature = "dilithium-internals")]
+            expanded: expanded.1,
+            public: self,
+        }
+    }
+
+    /// Convert the public key to its raw byte representation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.bytes.clone()
+    }
+
+    /// The raw bytes of the public key, without copying them.
+    #[cfg(feature = "dilithium")]
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Try to parse a public key from the raw bytes produced by [`PublicKey::to_bytes`].
+    ///
+    /// The level is inferred from the length of the input.
+    pub fn try_from_bytes(k: &[u8]) -> Result<PublicKey, DecodingError> {
+        let level = DilithiumLevel::from_public_key_len(k.len()).ok_or_else(|| {
+            DecodingError::failed_to_parse("Dilithium public key", UnexpectedLength(k.len()))
+        })?;
+
+        Ok(PublicKey {
+            level,
+            bytes: k.to_vec(),
+        })
+    }
+
+    /// Get the level of this public key.
+    pub fn level(&self) -> DilithiumLevel {
+        self.level
+    }
+
+    fn to_array<const N: usize>(&self) -> [u8; N] {
+        self.bytes[..]
+            .try_into()
+            .expect("length is validated on construction")
+    }
+}
+
+#[cfg(feature = "dilithium")]
+/// Incremental Dilithium signer, created by [`Keypair::signer`].
+#[derive(Clone)]
+pub struct Signer {
+    secret: SecretKey,
+    #[cfg(feature = "dilithium-internals")]
+    hasher: MessageHasher,
+    #[cfg(not(feature = "dilithium-internals"))]
+    msg: Vec<u8>,
+}
+
+#[cfg(feature = "dilithium")]
+impl Signer {
+    /// Feed the next chunk of the message.
+    pub fn update(&mut self, chunk: &[u8]) {
+        #[cfg(feature = "dilithium-internals")]
+        self.hasher.update(chunk);
+        #[cfg(not(feature = "dilithium-internals"))]
+        self.msg.extend_from_slice(chunk);
+    }
+
+    /// Sign the message fed so far, like [`Keypair::sign`].
+    pub fn finalize(self) -> Vec<u8> {
+        #[cfg(feature = "dilithium-internals")]
+        {
+            let mut rnd = [0u8; SEEDBYTES];
+            rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut rnd);
+
+            self.secret
+                .sign_mus(&[(self.hasher.finalize(), rnd)])
+                .pop()
+                .expect("one signature per message")
+        }
+        #[cfg(not(feature = "dilithium-internals"))]
+        self.secret.sign(&[], &self.msg, true)
+    }
+}
+
+#[cfg(feature = "dilithium")]
+impl fmt::Debug for Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signer")
+            .field("level", &self.secret.level)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Incremental Dilithium verifier, created by [`PublicKey::verifier`].
+#[derive(Clone)]
+pub struct Verifier {
+    public: PublicKey,
+    #[cfg(feature = "dilithium-internals")]
+    hasher: MessageHasher,
+    #[cfg(not(feature = "dilithium-internals"))]
+    msg: Vec<u8>,
+}
+
+impl Verifier {
+    /// Feed the next chunk of the message.
+    pub fn update(&mut self, chunk: &[u8]) {
+        #[cfg(feature = "dilithium-internals")]
+        self.hasher.update(chunk);
+        #[cfg(not(feature = "dilithium-internals"))]
+        self.msg.extend_from_slice(chunk);
+    }
+
+    /// Verify `sig` over the message fed so far.
+    #[cfg(not(feature = "dilithium-internals"))]
+    pub fn finalize(self, sig: &[u8]) -> bool {
+        self.public.verify(&self.msg, sig)
+    }
+
+    /// Verify `sig` over the message fed so far.
+    #[cfg(feature = "dilithium-internals")]
+    pub fn finalize(self, sig: &[u8]) -> bool {
+        let mu = self.hasher.finalize();
+        let pk = &self.public.bytes[..];
+
+        match self.public.level {
+            DilithiumLevel::Level2 => {
+                let (mat, t1) = expand_public_key!(pk, ml_dsa_44, lvl2);
+                verify_mu!(&mat, &t1, &mu, sig, ml_dsa_44, lvl2)
+            }
+            DilithiumLevel::Level3 => {
+                let (mat, t1) = expand_public_key!(pk, ml_dsa_65, lvl3);
+                verify_mu!(&mat, &t1, &mu, sig, ml_dsa_65, lvl3)
+            }
+            DilithiumLevel::Level5 => {
+                let (mat, t1) = expand_public_key!(pk, ml_dsa_87, lvl5);
+                verify_mu!(&mat, &t1, &mu, sig, ml_dsa_87, lvl5)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Verifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Verifier")
+            .field("public", &self.public)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "dilithium-internals")]
+#[derive(Clone)]
+enum Expanded {
+    Level2(expanded_type!(ml_dsa_44, lvl2)),
+    Level3(expanded_type!(ml_dsa_65, lvl3)),
+    Level5(expanded_type!(ml_dsa_87, lvl5)),
+}
+
+/// A Dilithium public key with its verification state precomputed, created by
+/// [`PublicKey::into_prepared`].
+///
+/// With the `dilithium-internals` feature, unpacking the key, expanding the matrix `A`
+/// and hashing the key are done once, so each [`PreparedPublicKey::verify`] only pays
+/// for the message and the signature.
+#[derive(Clone)]
+pub struct PreparedPublicKey {
+    public: PublicKey,
+    #[cfg(feature = "dilithium-internals")]
+    tr: [u8; TR_BYTES],
+    #[cfg(feature = "dilithium-internals")]
+    expanded: Expanded,
+}
+
+impl PreparedPublicKey {
+    /// Verify the Dilithium signature on a message, with the same result as
+    /// [`PublicKey::verify`].
+    #[cfg(not(feature = "dilithium-internals"))]
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
+        self.public.verify(msg, sig)
+    }
+
+    /// Verify the Dilithium signature on a message, with the same result as
+    /// [`PublicKey::verify`].
+    #[cfg(feature = "dilithium-internals")]
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
+        let mut hasher = MessageHasher::new(&self.tr);
+        hasher.update(msg);
+        let mu = hasher.finalize();
+
+        match &self.expanded {
+            Expanded::Level2(e) => verify_mu!(&e.0, &e.1, &mu, sig, ml_dsa_44, lvl2),
+            Expanded::Level3(e) => verify_mu!(&e.0, &e.1, &mu, sig, ml_dsa_65, lvl3),
+            Expanded::Level5(e) => verify_mu!(&e.0, &e.1, &mu, sig, ml_dsa_87, lvl5),
+        }
+    }
+
+    /// The public key this state was prepared from.
+    pub fn public(&self) -> &PublicKey {
+        &self.public
+    }
+}
+
+impl fmt::Debug for PreparedPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreparedPublicKey")
+            .field("public", &self.public)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Constant-time comparison of the key bytes; the level is public and compared directly.
+impl subtle::ConstantTimeEq for PublicKey {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        subtle::Choice::from((self.level == other.level) as u8) & self.bytes.ct_eq(&other.bytes)
+    }
+}
+
+impl From<ml_dsa_87::PublicKey> for PublicKey {
+    fn from(key: ml_dsa_87::PublicKey) -> Self {
+        PublicKey {
+            level: DilithiumLevel::Level5,
+            bytes: key.bytes.to_vec(),
+        }
+    }
+}
+
+#[cfg(feature = "dilithium")]
+/// A Dilithium secret key.
+#[derive(Clone)]
+pub struct SecretKey {
+    level: DilithiumLevel,
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "dilithium")]
+/// View the bytes of the secret key.
+impl AsRef<[u8]> for SecretKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(feature = "dilithium")]
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretKey")
+    }
+}
+
+#[cfg(feature = "dilithium")]
+/// Wipe the secret key bytes before the buffer is freed.
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+#[cfg(feature = "dilithium")]
+impl zeroize::ZeroizeOnDrop for SecretKey {}
+
+#[cfg(feature = "dilithium")]
+impl SecretKey {
+    /// Sign `msg` under the context `ctx`, at most 255 bytes, with the backend signer.
+    fn sign(&self, ctx: &[u8], msg: &[u8], hedged: bool) -> Vec<u8> {
+        // The backend key is a plain array, so wipe it once the signature is made. The
+        // ML-DSA-44 and ML-DSA-65 signers return `None` for contexts over 255 bytes.
+        macro_rules! backend_sign {
+            ($module:ident $(, $expect:literal)?) => {{
+                let mut sk = $module::SecretKey {
+                    bytes: self.to_array(),
+                };
+                let sig = sk.sign(msg, Some(ctx), hedged)$(.expect($expect))?.to_vec();
+                sk.bytes.zeroize();
+                sig
+            }};
+        }
+
+        match self.level {
+            DilithiumLevel::Level2 => backend_sign!(ml_dsa_44, "context is at most 255 bytes"),
+            DilithiumLevel::Level3 => backend_sign!(ml_dsa_65, "context is at most 255 bytes"),
+            DilithiumLevel::Level5 => backend_sign!(ml_dsa_87),
+        }
+    }
+
+    /// Sign each message with the matching `rnd`, unpacking the secret key and expanding
+    /// the matrix `A` only once for the whole batch.
+    #[cfg(feature = "dilithium-internals")]
+    fn sign_many(&self, ctx: &[u8], msgs: &[&[u8]], rnds: &[[u8; SEEDBYTES]]) -> Vec<Vec<u8>> {
+        let tr = self.tr();
+        let inputs: Vec<_> = msgs
+            .iter()
+            .zip(rnds)
+            .map(|(msg, rnd)| {
+                let mut hasher = MessageHasher::with_context(&tr, ctx);
+                hasher.update(msg);
+                (hasher.finalize(), *rnd)
+            })
+            .collect();
+
+        self.sign_mus(&inputs)
+    }
+
+    /// Sign message representatives computed by a [`MessageHasher`] for this key, each
+    /// with its `rnd`.
+    #[cfg(feature = "dilithium-internals")]
+    fn sign_mus(&self, inputs: &[([u8; CRHBYTES], [u8; SEEDBYTES])]) -> Vec<Vec<u8>> {
+        let sk = &self.bytes[..];
+
+        match self.level {
+            DilithiumLevel::Level2 => sign_mu!(sk, inputs, ml_dsa_44, lvl2),
+            DilithiumLevel::Level3 => sign_mu!(sk, inputs, ml_dsa_65, lvl3),
+            DilithiumLevel::Level5 => sign_mu!(sk, inputs, ml_dsa_87, lvl5),
+        }
+    }
+
+    fn to_array<const N: usize>(&self) -> [u8; N] {
+        self.bytes[..]
+            .try_into()
+            .expect("length is validated on construction")
+    }
+
+    /// The hash of the public key, stored in the secret key after `rho` and `key`.
+    #[cfg(feature = "dilithium-internals")]
+    fn tr(&self) -> [u8; TR_BYTES] {
+        self.bytes[2 * SEEDBYTES..2 * SEEDBYTES + TR_BYTES]
+            .try_into()
+            .expect("secret key length is validated on construction")
+    }
+
+    /// Try to parse a Dilithium secret key from a byte slice containing the actual key.
+    ///
+    /// The level is inferred from the length of the input.
+    pub fn try_from_bytes(sk: &[u8]) -> Result<SecretKey, DecodingError> {
+        let level = DilithiumLevel::from_secret_key_len(sk.len()).ok_or_else(|| {
+            DecodingError::failed_to_parse("Dilithium secret key", UnexpectedLength(sk.len()))
+        })?;
+
+        Ok(SecretKey {
+            level,
+            bytes: sk.to_vec(),
+        })
+    }
+
+    /// Get the level of this secret key.
+    pub fn level(&self) -> DilithiumLevel {
+        self.level
+    }
+
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        self.bytes.clone()
+    }
+
+    /// Recompute the public key from the secret key and check it against the
+    /// public key hash embedded in the secret key.
+    #[cfg(not(feature = "dilithium-internals"))]
+    fn derive_public(&self) -> Result<PublicKey, DecodingError> {
+        Err(DecodingError::new(
+            "recomputing a Dilithium public key requires the `dilithium-internals` feature"
+                .to_string(),
+        ))
+    }
+
+    /// Recompute the public key from the secret key and check it against the
+    /// public key hash embedded in the secret key.
+    #[cfg(feature = "dilithium-internals")]
+    fn derive_public(&self) -> Result<PublicKey, DecodingError> {
+        let bytes = match self.level {
+            DilithiumLevel::Level2 => derive_public_key!(&self.bytes, ml_dsa_44, lvl2),
+            DilithiumLevel::Level3 => derive_public_key!(&self.bytes, ml_dsa_65, lvl3),
+            DilithiumLevel::Level5 => derive_public_key!(&self.bytes, ml_dsa_87, lvl5),
+        }
+        .ok_or_else(|| {
+            DecodingError::new(
+                "failed to parse Dilithium secret key: public key hash mismatch".to_string(),
+            )
+        })?;
+
+        Ok(PublicKey {
+            level: self.level,
+            bytes,
+        })
+    }
+
+    /// Whether every packed coefficient of `s1` and `s2` lies in `[-eta, eta]`.
+    ///
+    /// Coefficients are packed as `eta - c` in 3 bits for `eta = 2` and in 4 bits for
+    /// `eta = 4`, so every group of `bits` bytes holds 8 coefficients. All of them are
+    /// checked regardless of earlier failures.
+    fn has_valid_coefficients(&self) -> bool {
+        let (l, k, eta) = self.level.secret_vector_params();
+        let bits = if eta == 2 { 3 } else { 4 };
+        let start = 2 * SEEDBYTES + TR_BYTES;
+        let packed = &self.bytes[start..start + (l + k) * 256 * bits / 8];
+
+        let mut out_of_range = false;
+        for group in packed.chunks_exact(bits) {
+            let mut word = [0u8; 4];
+            word[..bits].copy_from_slice(group);
+            let word = u32::from_le_bytes(word);
+            for i in 0..8 {
+                out_of_range |= (word >> (bits * i)) & ((1 << bits) - 1) > 2 * eta;
+            }
+        }
+        !out_of_range
+    }
+}
+
+/// The length of an encoded key does not match any [`DilithiumLevel`].
+#[derive(Debug)]
+struct UnexpectedLength(usize);
+
+impl fmt::Display for UnexpectedLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no Dilithium level has an encoding of {} bytes", self.0)
+    }
+}
+
+impl core::error::Error for UnexpectedLength {}
+
+#[cfg(feature = "dilithium")]
+/// The public half of an encoded keypair does not belong to its secret half.
+#[derive(Debug)]
+struct MismatchedPublicKey;
+
+#[cfg(feature = "dilithium")]
+impl fmt::Display for MismatchedPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the public key does not match the secret key")
+    }
+}
+
+#[cfg(feature = "dilithium")]
+impl core::error::Error for MismatchedPublicKey {}
+
+#[cfg(all(test, feature = "dilithium"))]
+mod tests {
+    use super::*;
+
+    fn eq_keypairs(kp1: &Keypair, kp2: &Keypair) -> bool {
+        kp1.public() == kp2.public() && kp1.secret.bytes == kp2.secret.bytes
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn verify_unchecked_matches_verify() {
+        for level in DilithiumLevel::ALL {
+            let kp = Keypair::generate(level);
+            let sig = kp.sign(b"hot path");
+
+            assert!(kp.public().verify_unchecked(b"hot path", &sig));
+            assert!(!kp.public().verify_unchecked(b"cold path", &sig));
+            assert!(!kp
+                .public()
+                .verify_unchecked(b"hot path", &sig[..sig.len() - 1]));
+            assert!(!kp.public().verify_unchecked(b"hot path", &[]));
+            assert!(!kp
+                .public()
+                .verify_unchecked(b"hot path", &[sig, vec![0]].concat()));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn validate_rejects_corrupted_keys() {
+        for level in DilithiumLevel::ALL {
+            let kp = Keypair::generate(level);
+            assert_eq!(kp.validate(), Ok(()));
+
+            // Every byte of the packed `s1` holds at least one coefficient; all ones
+            // puts them out of range, yet the rho and tr checks on decoding still pass.
+            let mut bytes = kp.to_bytes();
+            bytes[2 * SEEDBYTES + TR_BYTES] = 0xff;
+            let corrupted = Keypair::try_from_bytes(&bytes).unwrap();
+            assert_eq!(
+                corrupted.validate(),
+                Err(ValidationError::SecretCoefficientOutOfRange)
+            );
+
+            let mut public = kp.public.clone();
+            public.bytes[SEEDBYTES] ^= 1;
+            let corrupted = Keypair {
+                secret: kp.secret.clone(),
+                public,
+            };
+            assert_eq!(
+                corrupted.validate(),
+                Err(ValidationError::MismatchedPublicKey)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn dilithium_keypair_encode_decode() {
+        for level in DilithiumLevel::ALL {
+            let kp1 = Keypair::generate(level);
+            let kp2 = Keypair::try_from_bytes(&kp1.to_bytes()).unwrap();
+
+            assert!(eq_keypairs(&kp1, &kp2));
+            assert_eq!(kp2.level(), level);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn dilithium_key_sizes() {
+        for level in DilithiumLevel::ALL {
+            let kp = Keypair::generate(level);
+            let sig = kp.sign(b"hello world");
+
+            assert_eq!(kp.public().to_bytes().len(), level.public_key_len());
+            assert_eq!(kp.secret().as_ref().len(), level.secret_key_len());
+            assert_eq!(sig.len(), level.signature_len());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn dilithium_signature() {
+        for level in DilithiumLevel::ALL {
+            let kp = Keypair::generate(level);
+            let pk = kp.public();
+
+            let msg = "hello world".as_bytes();
+            let sig = kp.sign(msg);
+            assert!(pk.verify(msg, &sig));
+
+            let mut invalid_sig = sig.clone();
+            invalid_sig[3..6].copy_from_slice(&[10, 23, 42]);
+            assert!(!pk.verify(msg, &invalid_sig));
+
+            let invalid_msg = "h3ll0 w0rld".as_bytes();
+            assert!(!pk.verify(invalid_msg, &sig));
+        }
+    }
+
+    #[test]
+    fn dilithium_signing_modes() {
+        let msgs: [&[u8]; 3] = [b"", b"first", b"second"];
+
+        for level in DilithiumLevel::ALL {
+            let kp = Keypair::from_seed(level, &[3; 32]);
+            let pk = kp.public();
+
+            let deterministic = kp.sign_deterministic(msgs[1]);
+            assert_eq!(kp.sign_deterministic(msgs[1]), deterministic);
+            assert_ne!(kp.sign(msgs[1]), deterministic);
+            assert!(pk.verify(msgs[1], &deterministic));
+            assert!(pk.verify(msgs[1], &kp.sign(msgs[1])));
+
+            let sigs = kp.sign_many(&msgs);
+            assert_eq!(sigs.len(), msgs.len());
+            for (msg, sig) in msgs.iter().zip(&sigs) {
+                assert!(pk.verify(msg, sig));
+            }
+            assert_ne!(sigs, kp.sign_many(&msgs));
+        }
+    }
+
+    #[test]
+    fn dilithium_verify_batch_matches_verify() {
+        for level in DilithiumLevel::ALL {
+            let kp = Keypair::from_seed(level, &[4u8; 32]);
+            let other = Keypair::from_seed(level, &[8u8; 32]);
+
+            let msgs: Vec<Vec<u8>> = (0..8u8).map(|i| vec![i; i as usize * 17]).collect();
+            let mut sigs: Vec<Vec<u8>> = msgs.iter().map(|m| kp.sign(m)).collect();
+            sigs[1][10] ^= 1;
+            sigs[2] = other.sign(&msgs[2]);
+            sigs[3].truncate(100);
+            let last = sigs[4].len() - 1;
+            sigs[4][last] ^= 0xff;
+
+            let items: Vec<(&[u8], &[u8])> = msgs
+                .iter()
+                .zip(&sigs)
+                .map(|(m, s)| (m.as_slice(), s.as_slice()))
+                .collect();
+            let expected: Vec<bool> = items
+                .iter()
+                .map(|(m, s)| kp.public().verify(m, s))
+                .collect();
+
+            assert_eq!(kp.public().verify_batch(&items), expected);
+            assert_eq!(expected.iter().filter(|ok| **ok).count(), 4);
+        }
+    }
+
+    /// Pins the byte encoding of seeded keypairs, so a change that makes it depend on the
+    /// host's byte order (or otherwise changes it) fails here instead of silently producing
+    /// keys other machines cannot import.
+    #[test]
+    fn dilithium_to_bytes_known_answer() {
+        use sha2::{Digest as _, Sha256};
+
+        let expected = [
+            (
+                DilithiumLevel::Level2,
+                3872,
+                hex_literal::hex!(
+                    "5bea59c10f0a16b3fd1c6fadc518c04f002289a462b23e93e0c66201e5f6e76e"
+                ),
+            ),
+            (
+                DilithiumLevel::Level3,
+                5984,
+                hex_literal::hex!(
+                    "af6b6aae18f839e8d64eba9b098dce5798702f9f984ecb5700af84f6bcc468ac"
+                ),
+            ),
+            (
+                DilithiumLevel::Level5,
+                7488,
+                hex_literal::hex!(
+                    "5058306dfdbe9401c94c15eabf2fba48c0d7e43c3e43fd566b7548e96e02f499"
+                ),
+            ),
+        ];
+
+        for (level, len, digest) in expected {
+            let bytes = Keypair::from_seed(level, &[42u8; 32]).to_bytes();
+
+            assert_eq!(bytes.len(), len);
+            assert_eq!(Sha256::digest(&bytes).as_slice(), digest, "{level:?}");
+            assert_eq!(Keypair::try_from_bytes(&bytes).unwrap().to_bytes(), bytes);
+        }
+    }
+
+    #[test]
+    fn dilithium_prepared_matches_verify() {
+        for level in DilithiumLevel::ALL {
+            let kp = Keypair::from_seed(level, &[5u8; 32]);
+            let other = Keypair::from_seed(level, &[6u8; 32]);
+            let prepared = kp.public().clone().into_prepared();
+            assert_eq!(prepared.public(), kp.public());
+
+            let msgs: Vec<Vec<u8>> = (0..6u8).map(|i| vec![i; i as usize * 31]).collect();
+            let mut sigs: Vec<Vec<u8>> = msgs.iter().map(|m| kp.sign(m)).collect();
+            sigs[1][20] ^= 1;
+            sigs[2] = other.sign(&msgs[2]);
+            sigs[3].truncate(64);
+
+            for (msg, sig) in msgs.iter().zip(&sigs) {
+                assert_eq!(prepared.verify(msg, sig), kp.public().verify(msg, sig));
+            }
+            assert!(prepared.verify(&msgs[0], &sigs[0]));
+            assert!(!prepared.verify(&msgs[1], &sigs[1]));
+            assert!(!prepared.verify(&msgs[2], &sigs[2]));
+            assert!(!prepared.verify(&msgs[3], &sigs[3]));
+        }
+    }
+
+    #[test]
+    fn dilithium_verify_batch() {
+        for level in DilithiumLevel::ALL {
+            let kp = Keypair::from_seed(level, &[9u8; 32]);
+            let sig_a = kp.sign(b"a");
+            let sig_b = kp.sign(b"b");
+
+            let results = kp.public().verify_batch(&[
+                (b"a", &sig_a),
+                (b"b", &sig_a),
+                (b"b", &sig_b),
+                (b"b", &[]),
+            ]);
+
+            assert_eq!(results, vec![true, false, true, false]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn dilithium_signature_from_other_level_is_rejected() {
+        let msg = "hello world".as_bytes();
+        let sig = Keypair::generate(DilithiumLevel::Level2).sign(msg);
+
+        assert!(!Keypair::generate(DilithiumLevel::Level5)
+            .public()
+            .verify(msg, &sig));
+    }
+
+    #[test]
+    fn dilithium_seed_is_deterministic() {
+        let seed = [7u8; 32];
+
+        for level in DilithiumLevel::ALL {
+            let kp1 = Keypair::from_seed(level, &seed);
+            let kp2 = Keypair::from_seed(level, &seed);
+
+            assert!(eq_keypairs(&kp1, &kp2));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium-internals")]
+    fn dilithium_keypair_from_secret() {
+        for level in DilithiumLevel::ALL {
+            let kp1 = Keypair::from_seed(level, &[3u8; 32]);
+            let sk = SecretKey::try_from_bytes(kp1.secret().as_ref()).unwrap();
+            let kp2 = Keypair::try_from(sk).unwrap();
+
+            assert!(eq_keypairs(&kp1, &kp2));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium-internals")]
+    fn dilithium_corrupted_secret_is_rejected() {
+        let kp = Keypair::from_seed(DilithiumLevel::Level2, &[3u8; 32]);
+        let mut bytes = kp.secret().to_bytes();
+        // Flip a bit in `rho`, which changes the expanded matrix and thus the public key.
+        bytes[0] ^= 1;
+
+        let sk = SecretKey::try_from_bytes(&bytes).unwrap();
+        assert!(Keypair::try_from(sk).is_err());
+    }
+
+    #[test]
+    fn dilithium_secret_key_zeroizes_on_drop() {
+        fn assert_zeroize_on_drop<T: zeroize::ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<SecretKey>();
+    }
+
+    #[test]
+    fn dilithium_cloned_secret_outlives_original() {
+        let kp = Keypair::from_seed(DilithiumLevel::Level2, &[5u8; 32]);
+        let expected = kp.secret().to_bytes();
+
+        let clone = kp.secret().clone();
+        assert_ne!(clone.bytes.as_ptr(), kp.secret().bytes.as_ptr());
+        drop(kp);
+
+        assert_eq!(clone.bytes, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn dilithium_keypair_with_foreign_public_key_is_rejected() {
+        let kp = Keypair::generate(DilithiumLevel::default());
+        let other = Keypair::generate(DilithiumLevel::default());
+
+        let mut bytes = kp.secret().to_bytes();
+        bytes.extend_from_slice(&other.public().to_bytes());
+
+        let err = Keypair::try_from_bytes(&bytes).unwrap_err();
+        assert_eq!(
+            std::error::Error::source(&err).unwrap().to_string(),
+            "the public key does not match the secret key"
+        );
+    }
+
+    #[test]
+    fn dilithium_invalid_lengths_are_rejected() {
+        assert!(PublicKey::try_from_bytes(&[0u8; 32]).is_err());
+        assert!(Keypair::try_from_bytes(&[0u8; 64]).is_err());
+        assert!(SecretKey::try_from_bytes(&[0u8; 32]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn dilithium_signs_empty_and_large_messages() {
+        let kp = Keypair::generate(DilithiumLevel::default());
+        let large = vec![0xa5; 10 * 1024 * 1024];
+
+        for msg in [&[][..], &large[..]] {
+            let sig = kp.sign(msg);
+            assert_eq!(sig.len(), DilithiumLevel::default().signature_len());
+            assert!(kp.public().verify(msg, &sig));
+        }
+
+        let sig = kp.sign(&large);
+        assert!(!kp.public().verify(&large[..large.len() - 1], &sig));
+        assert!(!kp.public().verify(&[], &sig));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn dilithium_streaming_matches_one_shot() {
+        let msg = (0..100_000u32).map(|i| i as u8).collect::<Vec<_>>();
+
+        for level in DilithiumLevel::ALL {
+            let kp = Keypair::generate(level);
+            let expected = kp.sign(&msg);
+
+            for chunk_size in [1, 135, 136, 4096, msg.len()] {
+                let mut signer = kp.signer();
+                let mut verifier = kp.public().verifier();
+                for chunk in msg.chunks(chunk_size) {
+                    signer.update(chunk);
+                    verifier.update(chunk);
+                }
+                assert!(kp.public().verify(&msg, &signer.finalize()));
+                assert!(verifier.finalize(&expected));
+            }
+
+            let mut verifier = kp.public().verifier();
+            verifier.update(&msg[1..]);
+            assert!(!verifier.finalize(&expected));
+        }
+    }
+}
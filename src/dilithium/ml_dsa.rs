@@ -0,0 +1,469 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! The steps of ML-DSA (FIPS 204) that the backend only runs as part of a whole `sign` or
+//! `verify` call.
+//!
+//! Streaming, batch signing and verification, and prepared public keys have to stop at the
+//! message representative `mu`, or keep the expanded matrix `A` across signatures. The backend does
+//! not expose these steps, so the macros below repeat its key expansion, signing and
+//! verification on top of its public `fips202`, `packing`, `poly` and `polyvec` modules, one
+//! expansion per parameter set.
+//!
+//! This is the only module that does so, and it is only compiled with the opt-in
+//! `dilithium-internals` feature. Its output must stay byte-for-byte identical to the
+//! backend's: the tests below check every macro against the backend's `keypair`, `signature`
+//! and `verify` at all three levels. The backend is pinned to an exact version in
+//! `Cargo.toml`, and these tests have to pass before that pin is moved.
+
+use qp_rusty_crystals_dilithium::{
+    fips202,
+    params::{CRHBYTES, TR_BYTES},
+};
+
+#[cfg(test)]
+use super::public_key_hash;
+
+#[cfg(feature = "dilithium")]
+/// Recompute the packed public key from a packed secret key, following the key generation
+/// steps of the given parameter set. Evaluates to `None` if the result does not hash to the
+/// `tr` value stored in the secret key.
+macro_rules! derive_public_key {
+    ($sk:expr, $params:ident, $polyvec:ident) => {{
+        use qp_rusty_crystals_dilithium::{fips202, packing, params, polyvec::$polyvec as polyvec};
+
+        let mut rho = [0u8; params::SEEDBYTES];
+        let mut tr = [0u8; params::TR_BYTES];
+        let mut key = [0u8; params::SEEDBYTES];
+        let mut t0 = polyvec::Polyveck::default();
+        let mut s1 = polyvec::Polyvecl::default();
+        let mut s2 = polyvec::Polyveck::default();
+        packing::$params::unpack_sk(&mut rho, &mut tr, &mut key, &mut t0, &mut s1, &mut s2, $sk);
+
+        let mut mat = [polyvec::Polyvecl::default(); params::$params::K];
+        polyvec::matrix_expand(&mut mat, &rho);
+
+        let mut s1hat = s1;
+        polyvec::l_ntt(&mut s1hat);
+
+        let mut t1 = polyvec::Polyveck::default();
+        polyvec::matrix_pointwise_montgomery(&mut t1, &mat, &s1hat);
+        polyvec::k_reduce(&mut t1);
+        polyvec::k_invntt_tomont(&mut t1);
+        polyvec::k_add(&mut t1, &s2);
+        polyvec::k_caddq(&mut t1);
+        polyvec::k_power2round(&mut t1, &mut t0);
+
+        let mut pk = vec![0u8; params::$params::PUBLICKEYBYTES];
+        packing::$params::pack_pk(&mut pk, &rho, &t1);
+
+        let mut expected_tr = [0u8; params::TR_BYTES];
+        fips202::shake256(
+            &mut expected_tr,
+            params::TR_BYTES,
+            &pk,
+            params::$params::PUBLICKEYBYTES,
+        );
+
+        key.zeroize();
+        for poly in s1.vec.iter_mut().chain(s1hat.vec.iter_mut()) {
+            poly.coeffs.zeroize();
+        }
+        for poly in s2.vec.iter_mut().chain(t0.vec.iter_mut()) {
+            poly.coeffs.zeroize();
+        }
+
+        (tr == expected_tr).then_some(pk)
+    }};
+}
+
+/// Unpack a packed public key into the expanded matrix `A` and `t1 * 2^d` in NTT domain,
+/// the verification state that only depends on the key.
+macro_rules! expand_public_key {
+    ($pk:expr, $params:ident, $polyvec:ident) => {{
+        use qp_rusty_crystals_dilithium::{packing, params, polyvec::$polyvec as polyvec};
+
+        let mut rho = [0u8; params::SEEDBYTES];
+        let mut t1 = polyvec::Polyveck::default();
+        packing::$params::unpack_pk(&mut rho, &mut t1, $pk);
+
+        let mut mat = [polyvec::Polyvecl::default(); params::$params::K];
+        polyvec::matrix_expand(&mut mat, &rho);
+
+        polyvec::k_shiftl(&mut t1);
+        polyvec::k_ntt(&mut t1);
+
+        (mat, t1)
+    }};
+}
+
+/// Verify a signature against the message representative `mu`, following the verification
+/// steps of the given parameter set with the state produced by [`expand_public_key`].
+macro_rules! verify_mu {
+    ($mat:expr, $t1:expr, $mu:expr, $sig:expr, $params:ident, $polyvec:ident) => {{
+        use qp_rusty_crystals_dilithium::{
+            fips202, packing, params, poly, polyvec::$polyvec as polyvec,
+        };
+        const K: usize = params::$params::K;
+
+        let sig: &[u8] = $sig;
+        let mu: &[u8; params::CRHBYTES] = $mu;
+
+        let mut c = [0u8; params::$params::C_DASH_BYTES];
+        let mut z = polyvec::Polyvecl::default();
+        let mut h = polyvec::Polyveck::default();
+        if sig.len() != params::$params::SIGNBYTES
+            || !packing::$params::unpack_sig(&mut c, &mut z, &mut h, sig)
+            || polyvec::l_chknorm(&z, (params::$params::GAMMA1 - params::$params::BETA) as i32) > 0
+        {
+            false
+        } else {
+            // w1 = UseHint(h, Az - c * t1 * 2^d)
+            let mut cp = poly::Poly::default();
+            poly::$params::challenge(&mut cp, &c);
+            poly::ntt(&mut cp);
+
+            polyvec::l_ntt(&mut z);
+            let mut w1 = polyvec::Polyveck::default();
+            polyvec::matrix_pointwise_montgomery(&mut w1, $mat, &z);
+
+            let mut ct1 = polyvec::Polyveck::default();
+            polyvec::k_pointwise_poly_montgomery(&mut ct1, &cp, $t1);
+            polyvec::k_sub(&mut w1, &ct1);
+            polyvec::k_reduce(&mut w1);
+            polyvec::k_invntt_tomont(&mut w1);
+            polyvec::k_caddq(&mut w1);
+            polyvec::k_use_hint(&mut w1, &h);
+
+            let mut buf = [0u8; K * params::$params::POLYW1_PACKEDBYTES];
+            polyvec::k_pack_w1(&mut buf, &w1);
+
+            let mut c2 = [0u8; params::$params::C_DASH_BYTES];
+            let mut state = fips202::KeccakState::default();
+            fips202::shake256_absorb(&mut state, mu, params::CRHBYTES);
+            fips202::shake256_absorb(&mut state, &buf, buf.len());
+            fips202::shake256_finalize(&mut state);
+            fips202::shake256_squeeze(&mut c2, params::$params::C_DASH_BYTES, &mut state);
+
+            c == c2
+        }
+    }};
+}
+
+/// Verify many `(message, signature)` pairs against one packed public key, with the
+/// key-dependent state computed once.
+macro_rules! verify_batch {
+    ($pk:expr, $items:expr, $params:ident, $polyvec:ident) => {{
+        let pk: &[u8] = $pk;
+        let (mat, t1) = expand_public_key!(pk, $params, $polyvec);
+        let tr = public_key_hash(pk);
+
+        $items
+            .iter()
+            .map(|(msg, sig)| {
+                let mut hasher = MessageHasher::new(&tr);
+                hasher.update(msg);
+                verify_mu!(&mat, &t1, &hasher.finalize(), sig, $params, $polyvec)
+            })
+            .collect()
+    }};
+}
+
+#[cfg(feature = "dilithium")]
+/// Sign each message representative `mu` in `$inputs` with its 32-byte `rnd`, following the
+/// signing steps of the given parameter set once `mu` is known. The secret key is unpacked
+/// and the matrix `A` expanded once for the whole slice.
+///
+/// A fresh random `rnd` gives hedged signing, an all-zero one deterministic signing.
+///
+/// Only [`super::Keypair::sign_many`] and [`super::Signer`] sign through this macro; every
+/// other signing method calls the backend signer.
+macro_rules! sign_mu {
+    ($sk:expr, $inputs:expr, $params:ident, $polyvec:ident) => {{
+        use qp_rusty_crystals_dilithium::{
+            fips202, packing, params, poly, polyvec::$polyvec as polyvec,
+        };
+        const K: usize = params::$params::K;
+
+        let inputs: &[([u8; params::CRHBYTES], [u8; params::SEEDBYTES])] = $inputs;
+        let mut rho = [0u8; params::SEEDBYTES];
+        let mut tr = [0u8; params::TR_BYTES];
+        let mut key = [0u8; params::SEEDBYTES];
+        let mut t0 = polyvec::Polyveck::default();
+        let mut s1 = polyvec::Polyvecl::default();
+        let mut s2 = polyvec::Polyveck::default();
+        packing::$params::unpack_sk(&mut rho, &mut tr, &mut key, &mut t0, &mut s1, &mut s2, $sk);
+
+        let mut mat = [polyvec::Polyvecl::default(); K];
+        polyvec::matrix_expand(&mut mat, &rho);
+        polyvec::l_ntt(&mut s1);
+        polyvec::k_ntt(&mut s2);
+        polyvec::k_ntt(&mut t0);
+
+        let mut sigs = Vec::with_capacity(inputs.len());
+        for (mu, rnd) in inputs {
+            // rho' = H(key || rnd || mu)
+            let mut state = fips202::KeccakState::default();
+            fips202::shake256_absorb(&mut state, &key, params::SEEDBYTES);
+            fips202::shake256_absorb(&mut state, rnd, params::SEEDBYTES);
+            fips202::shake256_absorb(&mut state, mu, params::CRHBYTES);
+            fips202::shake256_finalize(&mut state);
+            let mut rhoprime = [0u8; params::CRHBYTES];
+            fips202::shake256_squeeze(&mut rhoprime, params::CRHBYTES, &mut state);
+
+            let mut sig = vec![0u8; params::$params::SIGNBYTES];
+            let mut nonce: u16 = 0;
+            let mut y = polyvec::Polyvecl::default();
+            let mut z;
+            let mut w1 = polyvec::Polyveck::default();
+            let mut w0 = polyvec::Polyveck::default();
+            let mut cp = poly::Poly::default();
+            let mut h = polyvec::Polyveck::default();
+            loop {
+                polyvec::l_uniform_gamma1(&mut y, &rhoprime, nonce);
+                nonce += 1;
+
+                z = y;
+                polyvec::l_ntt(&mut z);
+                polyvec::matrix_pointwise_montgomery(&mut w1, &mat, &z);
+                polyvec::k_reduce(&mut w1);
+                polyvec::k_invntt_tomont(&mut w1);
+                polyvec::k_caddq(&mut w1);
+
+                polyvec::k_decompose(&mut w1, &mut w0);
+                polyvec::k_pack_w1(&mut sig, &w1);
+
+                state.init();
+                fips202::shake256_absorb(&mut state, mu, params::CRHBYTES);
+                fips202::shake256_absorb(&mut state, &sig, K * params::$params::POLYW1_PACKEDBYTES);
+                fips202::shake256_finalize(&mut state);
+                fips202::shake256_squeeze(&mut sig, params::$params::C_DASH_BYTES, &mut state);
+
+                poly::$params::challenge(&mut cp, &sig);
+                poly::ntt(&mut cp);
+
+                polyvec::l_pointwise_poly_montgomery(&mut z, &cp, &s1);
+                polyvec::l_invntt_tomont(&mut z);
+                polyvec::l_add(&mut z, &y);
+                polyvec::l_reduce(&mut z);
+                if polyvec::l_chknorm(&z, (params::$params::GAMMA1 - params::$params::BETA) as i32)
+                    > 0
+                {
+                    continue;
+                }
+
+                polyvec::k_pointwise_poly_montgomery(&mut h, &cp, &s2);
+                polyvec::k_invntt_tomont(&mut h);
+                polyvec::k_sub(&mut w0, &h);
+                polyvec::k_reduce(&mut w0);
+                if polyvec::k_chknorm(
+                    &w0,
+                    (params::$params::GAMMA2 - params::$params::BETA) as i32,
+                ) > 0
+                {
+                    continue;
+                }
+
+                polyvec::k_pointwise_poly_montgomery(&mut h, &cp, &t0);
+                polyvec::k_invntt_tomont(&mut h);
+                polyvec::k_reduce(&mut h);
+                if polyvec::k_chknorm(&h, params::$params::GAMMA2 as i32) > 0 {
+                    continue;
+                }
+
+                polyvec::k_add(&mut w0, &h);
+                if polyvec::k_make_hint(&mut h, &w0, &w1) > params::$params::OMEGA as i32 {
+                    continue;
+                }
+
+                packing::$params::pack_sig(&mut sig, None, &z, &h);
+                break;
+            }
+
+            // The state absorbed `key`, and `h` held `c * s2` and `c * t0` in rejected
+            // iterations.
+            rhoprime.zeroize();
+            state.s.zeroize();
+            for poly in y.vec.iter_mut().chain(z.vec.iter_mut()) {
+                poly.coeffs.zeroize();
+            }
+            for poly in w0.vec.iter_mut().chain(h.vec.iter_mut()) {
+                poly.coeffs.zeroize();
+            }
+            sigs.push(sig);
+        }
+
+        key.zeroize();
+        for poly in s1.vec.iter_mut() {
+            poly.coeffs.zeroize();
+        }
+        for poly in s2.vec.iter_mut().chain(t0.vec.iter_mut()) {
+            poly.coeffs.zeroize();
+        }
+
+        sigs
+    }};
+}
+
+/// Incrementally computes the message representative `mu = H(tr || 0 || len(ctx) || ctx ||
+/// msg)`, the pure ML-DSA framing. [`super::PublicKey::verify`] uses the empty context.
+#[derive(Clone)]
+pub(super) struct MessageHasher(fips202::KeccakState);
+
+impl MessageHasher {
+    pub(super) fn new(tr: &[u8; TR_BYTES]) -> Self {
+        Self::with_context(tr, &[])
+    }
+
+    /// Start hashing a message bound to `ctx`, which must be at most 255 bytes.
+    pub(super) fn with_context(tr: &[u8; TR_BYTES], ctx: &[u8]) -> Self {
+        let mut state = fips202::KeccakState::default();
+        fips202::shake256_absorb(&mut state, tr, TR_BYTES);
+        let ctx_len = u8::try_from(ctx.len()).expect("ML-DSA contexts are at most 255 bytes");
+        fips202::shake256_absorb(&mut state, &[0, ctx_len], 2);
+        fips202::shake256_absorb(&mut state, ctx, ctx.len());
+        MessageHasher(state)
+    }
+
+    pub(super) fn update(&mut self, chunk: &[u8]) {
+        fips202::shake256_absorb(&mut self.0, chunk, chunk.len());
+    }
+
+    pub(super) fn finalize(mut self) -> [u8; CRHBYTES] {
+        let mut mu = [0u8; CRHBYTES];
+        fips202::shake256_finalize(&mut self.0);
+        fips202::shake256_squeeze(&mut mu, CRHBYTES, &mut self.0);
+        mu
+    }
+}
+
+/// The matrix `A` and `t1 * 2^d` of a public key, expanded for one parameter set.
+macro_rules! expanded_type {
+    ($params:ident, $polyvec:ident) => {
+        Box<(
+            [qp_rusty_crystals_dilithium::polyvec::$polyvec::Polyvecl;
+                qp_rusty_crystals_dilithium::params::$params::K],
+            qp_rusty_crystals_dilithium::polyvec::$polyvec::Polyveck,
+        )>
+    };
+}
+
+#[cfg(all(test, feature = "dilithium"))]
+mod tests {
+    use super::*;
+    use zeroize::Zeroize;
+
+    /// The backend frames a message for the empty context as `0 || 0 || msg`.
+    fn framed(msg: &[u8]) -> Vec<u8> {
+        [&[0, 0][..], msg].concat()
+    }
+
+    fn mu(pk: &[u8], msg: &[u8]) -> [u8; CRHBYTES] {
+        let mut hasher = MessageHasher::new(&public_key_hash(pk));
+        hasher.update(msg);
+        hasher.finalize()
+    }
+
+    /// Check every macro against the backend for a few seeded keys and messages of one
+    /// parameter set.
+    macro_rules! matches_backend {
+        ($name:ident, $params:ident, $polyvec:ident) => {
+            #[test]
+            fn $name() {
+                use qp_rusty_crystals_dilithium::{
+                    params::$params as params, sign::$params as backend,
+                };
+
+                let msgs: [&[u8]; 4] = [b"", b"a", b"hello world", &[0xa5; 3000]];
+                for seed in 0..4u8 {
+                    let mut pk = vec![0u8; params::PUBLICKEYBYTES];
+                    let mut sk = vec![0u8; params::SECRETKEYBYTES];
+                    backend::keypair(&mut pk, &mut sk, Some(&[seed; 32]));
+
+                    assert_eq!(
+                        derive_public_key!(&sk, $params, $polyvec).as_ref(),
+                        Some(&pk)
+                    );
+                    let mut corrupted = sk.clone();
+                    corrupted[100] ^= 1;
+                    assert_eq!(derive_public_key!(&corrupted, $params, $polyvec), None);
+
+                    let (mat, t1) = expand_public_key!(&pk, $params, $polyvec);
+                    for msg in msgs {
+                        let m = framed(msg);
+                        let mu = mu(&pk, msg);
+
+                        let mut expected = vec![0u8; params::SIGNBYTES];
+                        backend::signature(&mut expected, &m, &sk, false);
+                        let sig = sign_mu!(&sk, &[(mu, [0; 32])], $params, $polyvec).remove(0);
+                        assert_eq!(sig, expected);
+
+                        let mut hedged = vec![0u8; params::SIGNBYTES];
+                        backend::signature(&mut hedged, &m, &sk, true);
+                        let ours = sign_mu!(&sk, &[(mu, [seed ^ 0x5a; 32])], $params, $polyvec);
+                        assert_ne!(ours[0], sig);
+
+                        let mut forged = vec![sig.clone(), hedged, ours[0].clone()];
+                        for i in [
+                            0,
+                            params::C_DASH_BYTES,
+                            params::SIGNBYTES / 2,
+                            params::SIGNBYTES - 1,
+                        ] {
+                            let mut flipped = sig.clone();
+                            flipped[i] ^= 1;
+                            forged.push(flipped);
+                        }
+                        forged.push(sig[..params::SIGNBYTES - 1].to_vec());
+                        forged.push(vec![0xff; params::SIGNBYTES]);
+                        forged.push(Vec::new());
+
+                        for sig in &forged {
+                            assert_eq!(
+                                verify_mu!(&mat, &t1, &mu, sig, $params, $polyvec),
+                                backend::verify(sig, &m, &pk),
+                            );
+                        }
+                        assert!(verify_mu!(&mat, &t1, &mu, &forged[0], $params, $polyvec));
+                        assert!(verify_mu!(&mat, &t1, &mu, &forged[1], $params, $polyvec));
+                        assert!(verify_mu!(&mat, &t1, &mu, &forged[2], $params, $polyvec));
+
+                        let items = [(msg, &forged[0][..]), (b"other".as_slice(), &forged[0][..])];
+                        let results: Vec<bool> = verify_batch!(&pk, items, $params, $polyvec);
+                        assert_eq!(results, [true, false]);
+                    }
+
+                    // One expansion of the key signs every message as the backend would.
+                    let inputs: Vec<_> = msgs.iter().map(|msg| (mu(&pk, msg), [0; 32])).collect();
+                    let sigs = sign_mu!(&sk, &inputs, $params, $polyvec);
+                    for (msg, sig) in msgs.iter().zip(&sigs) {
+                        let mut expected = vec![0u8; params::SIGNBYTES];
+                        backend::signature(&mut expected, &framed(msg), &sk, false);
+                        assert_eq!(sig, &expected);
+                    }
+                }
+            }
+        };
+    }
+
+    matches_backend!(ml_dsa_44_matches_backend, ml_dsa_44, lvl2);
+    matches_backend!(ml_dsa_65_matches_backend, ml_dsa_65, lvl3);
+    matches_backend!(ml_dsa_87_matches_backend, ml_dsa_87, lvl5);
+}
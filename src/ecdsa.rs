@@ -22,6 +22,7 @@
 
 use super::error::DecodingError;
 use core::cmp;
+use core::convert::Infallible;
 use core::fmt;
 use core::hash;
 use p256::{
@@ -32,7 +33,6 @@ use p256::{
     EncodedPoint,
 };
 use sec1::{DecodeEcPrivateKey, EncodeEcPrivateKey};
-use void::Void;
 use zeroize::Zeroize;
 
 /// An ECDSA keypair generated using `secp256r1` curve.
@@ -49,6 +49,12 @@ impl Keypair {
         Keypair::from(SecretKey::generate())
     }
 
+    /// Generate a new ECDSA keypair using the given RNG.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: rand::RngCore + rand::CryptoRng>(rng: &mut R) -> Keypair {
+        Keypair::from(SecretKey::generate_with_rng(rng))
+    }
+
     /// Sign a message using the private key of this keypair.
     pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
         self.secret.sign(msg)
@@ -95,14 +101,24 @@ pub struct SecretKey(SigningKey);
 impl SecretKey {
     /// Generate a new random ECDSA secret key.
     pub fn generate() -> SecretKey {
-        SecretKey(SigningKey::random(&mut rand::thread_rng()))
+        SecretKey::generate_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generate a new ECDSA secret key using the given RNG.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: rand::RngCore + rand::CryptoRng>(rng: &mut R) -> SecretKey {
+        SecretKey(SigningKey::random(rng))
     }
 
     /// Sign a message with this secret key, producing a DER-encoded ECDSA signature.
+    ///
+    /// The signature is normalized to low-s form, so it is accepted by
+    /// [`PublicKey::verify_strict`].
     pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
-        let signature: p256::ecdsa::DerSignature = self.0.sign(msg);
+        let signature: Signature = self.0.sign(msg);
+        let signature = signature.normalize_s().unwrap_or(signature);
 
-        signature.as_bytes().to_owned()
+        signature.to_der().as_bytes().to_owned()
     }
 
     /// Convert a secret key into a byte buffer containing raw scalar of the key.
@@ -158,6 +174,25 @@ impl PublicKey {
         self.0.verify(msg, &sig).is_ok()
     }
 
+    /// Verify an ECDSA signature like [`PublicKey::verify`], but reject signatures whose
+    /// `s` is in the upper half of the scalar range.
+    ///
+    /// For every valid signature `(r, s)`, `(r, n - s)` is valid too. Requiring low-s
+    /// leaves a single accepted encoding per signature, as needed when signatures are
+    /// deduplicated or otherwise compared byte for byte.
+    pub fn verify_strict(&self, msg: &[u8], sig: &[u8]) -> bool {
+        let sig = match Signature::from_der(sig) {
+            Ok(sig) => sig,
+            Err(_) => return false,
+        };
+        sig.normalize_s().is_none() && self.0.verify(msg, &sig).is_ok()
+    }
+
+    /// Whether `sig` parses as a DER-encoded ECDSA signature.
+    pub(crate) fn is_well_formed_signature(sig: &[u8]) -> bool {
+        Signature::from_der(sig).is_ok()
+    }
+
     /// Try to parse a public key from a byte buffer containing raw components of a key with or without compression.
     pub fn try_from_bytes(k: &[u8]) -> Result<PublicKey, DecodingError> {
         let enc_pt = EncodedPoint::from_bytes(k)
@@ -182,7 +217,10 @@ impl PublicKey {
     /// Try to decode a public key from a DER encoded byte buffer as defined by SEC1 standard.
     pub fn try_decode_der(k: &[u8]) -> Result<PublicKey, DecodingError> {
         let buf = Self::del_asn1_header(k).ok_or_else(|| {
-            DecodingError::failed_to_parse::<Void, _>("ASN.1-encoded ecdsa p256 public key", None)
+            DecodingError::failed_to_parse::<Infallible, _>(
+                "ASN.1-encoded ecdsa p256 public key",
+                None,
+            )
         })?;
         Self::try_from_bytes(buf)
     }
@@ -282,4 +320,30 @@ mod tests {
         let invalid_msg = "h3ll0 w0rld".as_bytes();
         assert!(!pk.verify(invalid_msg, &sig));
     }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn high_s_is_rejected_by_verify_strict() {
+        let pair = Keypair::generate();
+        let pk = pair.public();
+        let msg = "hello world".as_bytes();
+
+        let low = pair.sign(msg);
+        assert!(pk.verify_strict(msg, &low));
+
+        let sig = Signature::from_der(&low).unwrap();
+        let (r, s) = sig.split_scalars();
+        let high = Signature::from_scalars(r, -s).unwrap();
+        let high = high.to_der().as_bytes().to_vec();
+        assert!(pk.verify(msg, &high));
+        assert!(!pk.verify_strict(msg, &high));
+
+        let normalized = Signature::from_der(&high)
+            .unwrap()
+            .normalize_s()
+            .unwrap()
+            .to_der();
+        assert_eq!(normalized.as_bytes(), &low[..]);
+        assert!(pk.verify_strict(msg, normalized.as_bytes()));
+    }
 }
@@ -21,11 +21,11 @@
 //! Ed25519 keys.
 
 use super::error::DecodingError;
+use alloc::{string::ToString, vec::Vec};
 use core::cmp;
 use core::fmt;
 use core::hash;
 use ed25519_dalek::{self as ed25519, Signer as _, Verifier as _};
-use std::convert::TryFrom;
 use zeroize::Zeroize;
 
 /// An Ed25519 keypair.
@@ -39,6 +39,12 @@ impl Keypair {
         Keypair::from(SecretKey::generate())
     }
 
+    /// Generate a new Ed25519 keypair using the given RNG.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: rand::RngCore + rand::CryptoRng>(rng: &mut R) -> Keypair {
+        Keypair::from(SecretKey::generate_with_rng(rng))
+    }
+
     /// Convert the keypair into a byte array by concatenating the bytes
     /// of the secret scalar and the compressed public point,
     /// an informal standard for encoding Ed25519 keypairs.
@@ -161,6 +167,33 @@ impl PublicKey {
             .map_err(|e| DecodingError::failed_to_parse("Ed25519 public key", e))
             .map(PublicKey)
     }
+
+    /// Encode the public key in DER as a X.509 SubjectPublicKeyInfo structure,
+    /// as defined in [RFC8410].
+    ///
+    /// [RFC8410]: https://tools.ietf.org/html/rfc8410#section-4
+    pub fn encode_der(&self) -> Vec<u8> {
+        let mut der = Vec::with_capacity(Self::SPKI_HEADER.len() + 32);
+        der.extend_from_slice(&Self::SPKI_HEADER);
+        der.extend_from_slice(&self.to_bytes());
+        der
+    }
+
+    /// Decode a public key from a DER-encoded X.509 SubjectPublicKeyInfo
+    /// structure. See also `encode_der`.
+    pub fn try_decode_der(k: &[u8]) -> Result<PublicKey, DecodingError> {
+        let key = k.strip_prefix(&Self::SPKI_HEADER).ok_or_else(|| {
+            DecodingError::new("failed to parse Ed25519 X.509: unexpected header".to_string())
+        })?;
+        Self::try_from_bytes(key)
+    }
+
+    // The SubjectPublicKeyInfo up to the key itself: a SEQUENCE holding the
+    // AlgorithmIdentifier with the id-Ed25519 OID (1.3.101.112) and no parameters,
+    // followed by the 32-byte BIT STRING header.
+    const SPKI_HEADER: [u8; 12] = [
+        0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+    ];
 }
 
 /// An Ed25519 secret key.
@@ -184,7 +217,13 @@ impl SecretKey {
     /// Generate a new Ed25519 secret key.
     #[cfg(feature = "rand")]
     pub fn generate() -> SecretKey {
-        let signing = ed25519::SigningKey::generate(&mut rand::rngs::OsRng);
+        SecretKey::generate_with_rng(&mut rand::rngs::OsRng)
+    }
+
+    /// Generate a new Ed25519 secret key using the given RNG.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: rand::RngCore + rand::CryptoRng>(rng: &mut R) -> SecretKey {
+        let signing = ed25519::SigningKey::generate(rng);
         SecretKey(signing.to_bytes())
     }
 
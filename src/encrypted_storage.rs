@@ -0,0 +1,232 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Passphrase encrypted storage of protobuf encoded private keys.
+//!
+//! ```text
+//! magic "LP2PENC\0" | version (1 byte)
+//!     | Argon2id memory cost in KiB, iterations, parallelism (4 bytes each, big-endian)
+//!     | salt (16 bytes) | nonce (12 bytes) | ChaCha20-Poly1305 ciphertext and tag
+//! ```
+//!
+//! The header is authenticated as associated data, so tampering with the parameters is
+//! detected like a wrong passphrase.
+
+use crate::error::DecodingError;
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, Payload},
+    ChaCha20Poly1305, KeyInit,
+};
+use rand::RngCore as _;
+use zeroize::Zeroizing;
+
+const MAGIC: &[u8; 8] = b"LP2PENC\0";
+const VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 3 * 4 + SALT_LEN + NONCE_LEN;
+const TAG_LEN: usize = 16;
+
+/// Upper bounds on the Argon2id costs accepted when decoding, so an untrusted file
+/// cannot make us allocate more than 1 GiB or spin for minutes.
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_ITERATIONS: u32 = 64;
+const MAX_PARALLELISM: u32 = 16;
+
+/// The Argon2id cost parameters used to derive the encryption key.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct KdfParams {
+    pub(crate) memory_kib: u32,
+    pub(crate) iterations: u32,
+    pub(crate) parallelism: u32,
+}
+
+impl KdfParams {
+    /// The parameters recommended by OWASP for Argon2id: 19 MiB, 2 iterations, 1 lane.
+    pub(crate) const DEFAULT: KdfParams = KdfParams {
+        memory_kib: 19 * 1024,
+        iterations: 2,
+        parallelism: 1,
+    };
+}
+
+/// Encrypt a protobuf encoded private key under a key derived from `passphrase`.
+pub(crate) fn encrypt(protobuf: &[u8], passphrase: &[u8], params: KdfParams) -> Vec<u8> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let mut file = Vec::with_capacity(HEADER_LEN + protobuf.len() + TAG_LEN);
+    file.extend_from_slice(MAGIC);
+    file.push(VERSION);
+    file.extend_from_slice(&params.memory_kib.to_be_bytes());
+    file.extend_from_slice(&params.iterations.to_be_bytes());
+    file.extend_from_slice(&params.parallelism.to_be_bytes());
+    file.extend_from_slice(&salt);
+    file.extend_from_slice(&nonce);
+
+    let cipher = cipher(passphrase, &salt, params).expect("default parameters are valid");
+    let ciphertext = cipher
+        .encrypt(
+            (&nonce).into(),
+            Payload {
+                msg: protobuf,
+                aad: &file,
+            },
+        )
+        .expect("private keys are far below the ChaCha20-Poly1305 message limit");
+    file.extend_from_slice(&ciphertext);
+    file
+}
+
+/// Authenticate and decrypt a file produced by [`encrypt`], returning the protobuf
+/// encoding it wraps.
+///
+/// A wrong passphrase and a modified file are indistinguishable and both return
+/// [`DecodingError::AuthenticationFailed`].
+pub(crate) fn decrypt(file: &[u8], passphrase: &[u8]) -> Result<Zeroizing<Vec<u8>>, DecodingError> {
+    if file.len() < HEADER_LEN + TAG_LEN || !file.starts_with(MAGIC) {
+        return Err(DecodingError::new(
+            "not an encrypted libp2p key".to_string(),
+        ));
+    }
+
+    let version = file[MAGIC.len()];
+    if version != VERSION {
+        return Err(DecodingError::new(format!(
+            "unsupported encrypted key version {version}, expected {VERSION}"
+        )));
+    }
+
+    let (header, ciphertext) = file.split_at(HEADER_LEN);
+    let word = |i: usize| {
+        let start = MAGIC.len() + 1 + 4 * i;
+        u32::from_be_bytes(
+            header[start..start + 4]
+                .try_into()
+                .expect("slice of 4 bytes"),
+        )
+    };
+    let params = KdfParams {
+        memory_kib: word(0),
+        iterations: word(1),
+        parallelism: word(2),
+    };
+    if params.memory_kib > MAX_MEMORY_KIB
+        || params.iterations > MAX_ITERATIONS
+        || params.parallelism > MAX_PARALLELISM
+    {
+        return Err(DecodingError::new(format!(
+            "encrypted key asks for Argon2id costs of {} KiB, {} iterations and {} lanes, \
+             at most {MAX_MEMORY_KIB} KiB, {MAX_ITERATIONS} iterations and \
+             {MAX_PARALLELISM} lanes are allowed",
+            params.memory_kib, params.iterations, params.parallelism
+        )));
+    }
+
+    let salt = &header[HEADER_LEN - NONCE_LEN - SALT_LEN..HEADER_LEN - NONCE_LEN];
+    let nonce = &header[HEADER_LEN - NONCE_LEN..];
+    let cipher = cipher(passphrase, salt, params)?;
+
+    cipher
+        .decrypt(
+            nonce.into(),
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| DecodingError::AuthenticationFailed)
+}
+
+fn cipher(
+    passphrase: &[u8],
+    salt: &[u8],
+    params: KdfParams,
+) -> Result<ChaCha20Poly1305, DecodingError> {
+    let params = Params::new(
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        Some(32),
+    )
+    .map_err(|e| DecodingError::new(format!("invalid Argon2id parameters: {e}")))?;
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase, salt, &mut *key)
+        .map_err(|e| DecodingError::new(format!("Argon2id key derivation failed: {e}")))?;
+
+    Ok(ChaCha20Poly1305::new((&*key).into()))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Cheap parameters, so tests do not spend their time in Argon2id.
+    pub(crate) const TEST_PARAMS: KdfParams = KdfParams {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    #[test]
+    fn encrypt_then_decrypt() {
+        let data = (0..=255u8).collect::<Vec<_>>();
+        let file = encrypt(&data, b"hunter2", TEST_PARAMS);
+
+        assert!(!file.windows(data.len()).any(|w| w == data.as_slice()));
+        assert_eq!(*decrypt(&file, b"hunter2").unwrap(), data);
+    }
+
+    #[test]
+    fn tampering_fails_authentication() {
+        let file = encrypt(&[7; 100], b"hunter2", TEST_PARAMS);
+
+        for i in MAGIC.len() + 1..file.len() {
+            let mut tampered = file.clone();
+            tampered[i] ^= 0x01;
+            assert!(
+                decrypt(&tampered, b"hunter2").is_err(),
+                "flipped bit in byte {i}"
+            );
+        }
+        assert!(decrypt(&file[..file.len() - 1], b"hunter2").is_err());
+        assert!(decrypt(&[], b"hunter2").is_err());
+    }
+
+    #[test]
+    fn excessive_costs_are_rejected() {
+        let file = encrypt(&[7; 100], b"hunter2", TEST_PARAMS);
+
+        for i in 0..3 {
+            let start = MAGIC.len() + 1 + 4 * i;
+            let mut expensive = file.clone();
+            expensive[start..start + 4].copy_from_slice(&u32::MAX.to_be_bytes());
+
+            let err = decrypt(&expensive, b"hunter2").unwrap_err();
+            assert!(err.to_string().contains("at most"), "{err}");
+        }
+    }
+}
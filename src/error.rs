@@ -20,29 +20,69 @@
 
 //! Errors during identity key operations.
 
-use std::error::Error;
-use std::fmt;
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium-verify",
+    feature = "sphincs-plus",
+    feature = "falcon",
+    feature = "std"
+))]
+use alloc::format;
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+use alloc::{boxed::Box, string::String};
+use core::error::Error;
+use core::fmt;
 
 use crate::KeyType;
 
 /// An error during decoding of key material.
 #[derive(Debug)]
-pub struct DecodingError {
+#[non_exhaustive]
+pub enum DecodingError {
+    /// The key type is known, but support for it is not compiled into this build.
+    DisabledKeyType(KeyType),
+    /// The input is not a valid encoding of key material.
+    Malformed(MalformedKeyError),
+    /// The protobuf encoding has no key type field.
+    MissingKeyType,
+    /// The protobuf encoding names a key type this crate does not know.
+    UnknownKeyType(i32),
+    /// The protobuf encoding ends before or inside the named field.
+    TruncatedField {
+        /// The name of the field in `keys.proto`.
+        field: &'static str,
+    },
+    /// The key type is supported, but the key material is not a valid key of that type.
+    InvalidKeyMaterial {
+        /// The key type named by the encoding.
+        key_type: KeyType,
+        /// Why the key material was rejected.
+        source: MalformedKeyError,
+    },
+    /// Authenticated decryption failed: the passphrase is wrong or the data was
+    /// modified.
+    AuthenticationFailed,
+}
+
+/// The details of a [`DecodingError::Malformed`] error.
+#[derive(Debug)]
+pub struct MalformedKeyError {
     msg: String,
     source: Option<Box<dyn Error + Send + Sync>>,
 }
 
 impl DecodingError {
     #[allow(dead_code)]
-    pub(crate) fn missing_feature(feature_name: &'static str) -> Self {
-        Self {
-            msg: format!("cargo feature `{feature_name}` is not enabled"),
-            source: None,
-        }
+    pub(crate) fn disabled_key_type(key_type: KeyType) -> Self {
+        DecodingError::DisabledKeyType(key_type)
     }
 
     pub(crate) fn new(msg: String) -> Self {
-        Self { msg, source: None }
+        DecodingError::Malformed(MalformedKeyError { msg, source: None })
     }
 
     #[cfg(any(
@@ -50,20 +90,20 @@ impl DecodingError {
         feature = "secp256k1",
         feature = "ed25519",
         feature = "rsa",
-        feature = "dilithium"
+        feature = "dilithium-verify",
+        feature = "sphincs-plus",
+        feature = "falcon",
+        feature = "std"
     ))]
     pub(crate) fn failed_to_parse<E, S>(what: &'static str, source: S) -> Self
     where
-        E: Error + Send + Sync + 'static,
+        E: IntoSource,
         S: Into<Option<E>>,
     {
-        Self {
+        DecodingError::Malformed(MalformedKeyError {
             msg: format!("failed to parse {what}"),
-            source: match source.into() {
-                None => None,
-                Some(e) => Some(Box::new(e)),
-            },
-        }
+            source: source.into().map(IntoSource::into_source),
+        })
     }
 
     #[cfg(any(
@@ -71,77 +111,380 @@ impl DecodingError {
         feature = "secp256k1",
         feature = "ed25519",
         feature = "rsa",
-        feature = "dilithium"
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
     ))]
-    pub(crate) fn bad_protobuf(
-        what: &'static str,
-        source: impl Error + Send + Sync + 'static,
-    ) -> Self {
-        Self {
+    pub(crate) fn bad_protobuf(what: &'static str, source: impl IntoSource) -> Self {
+        DecodingError::Malformed(MalformedKeyError {
             msg: format!("failed to decode {what} from protobuf"),
-            source: Some(Box::new(source)),
+            source: Some(source.into_source()),
+        })
+    }
+
+    /// Attribute a [`DecodingError::Malformed`] error to the key material of `key_type`.
+    ///
+    /// Other errors are returned unchanged.
+    #[allow(dead_code)]
+    pub(crate) fn invalid_key_material(self, key_type: KeyType) -> Self {
+        match self {
+            DecodingError::Malformed(source) => {
+                DecodingError::InvalidKeyMaterial { key_type, source }
+            }
+            other => other,
         }
     }
 
     #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
     pub(crate) fn encoding_unsupported(key_type: &'static str) -> Self {
-        Self {
-            msg: format!("encoding {key_type} key to Protobuf is unsupported"),
-            source: None,
-        }
+        DecodingError::new(format!(
+            "encoding {key_type} key to Protobuf is unsupported"
+        ))
+    }
+}
+
+/// The cargo feature that enables support for `key_type`.
+fn cargo_feature(key_type: KeyType) -> &'static str {
+    match key_type {
+        KeyType::Ed25519 => "ed25519",
+        KeyType::RSA => "rsa",
+        KeyType::Secp256k1 => "secp256k1",
+        KeyType::Ecdsa => "ecdsa",
+        KeyType::Dilithium => "dilithium",
+        KeyType::HybridDilithiumEd25519 if cfg!(feature = "dilithium") => "ed25519",
+        KeyType::HybridDilithiumEd25519 => "dilithium",
+        KeyType::SphincsPlus => "sphincs-plus",
+        KeyType::Falcon => "falcon",
     }
 }
 
 impl fmt::Display for DecodingError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Key decoding error: {}", self.msg)
+        match self {
+            DecodingError::DisabledKeyType(key_type) => write!(
+                f,
+                "Key decoding error: cargo feature `{}` is not enabled",
+                cargo_feature(*key_type)
+            ),
+            DecodingError::Malformed(e) => write!(f, "Key decoding error: {}", e.msg),
+            DecodingError::MissingKeyType => write!(f, "Key decoding error: missing key type"),
+            DecodingError::UnknownKeyType(tag) => {
+                write!(f, "Key decoding error: unknown key type {tag}")
+            }
+            DecodingError::TruncatedField { field } => {
+                write!(f, "Key decoding error: truncated `{field}` field")
+            }
+            DecodingError::InvalidKeyMaterial { key_type, source } => write!(
+                f,
+                "Key decoding error: invalid {key_type} key material: {}",
+                source.msg
+            ),
+            DecodingError::AuthenticationFailed => write!(
+                f,
+                "Key decoding error: authentication failed, wrong passphrase or corrupted data"
+            ),
+        }
     }
 }
 
 impl Error for DecodingError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.source.as_ref().map(|s| &**s as &dyn Error)
+        match self {
+            DecodingError::Malformed(e) | DecodingError::InvalidKeyMaterial { source: e, .. } => {
+                e.source.as_ref().map(|s| &**s as &dyn Error)
+            }
+            DecodingError::DisabledKeyType(_)
+            | DecodingError::MissingKeyType
+            | DecodingError::UnknownKeyType(_)
+            | DecodingError::TruncatedField { .. }
+            | DecodingError::AuthenticationFailed => None,
+        }
     }
 }
 
-/// An error during signing of a message.
+/// A dependency error kept as the source of a [`DecodingError`].
+///
+/// Some dependencies only implement [`Error`] when built with `std`; without it
+/// their message is kept instead.
+#[allow(dead_code)]
+pub(crate) trait IntoSource {
+    fn into_source(self) -> Box<dyn Error + Send + Sync>;
+}
+
+#[cfg(feature = "std")]
+impl<E: Error + Send + Sync + 'static> IntoSource for E {
+    fn into_source(self) -> Box<dyn Error + Send + Sync> {
+        Box::new(self)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl<E: fmt::Display> IntoSource for E {
+    fn into_source(self) -> Box<dyn Error + Send + Sync> {
+        Box::new(SourceMessage(self.to_string()))
+    }
+}
+
+/// The message of a dependency error that does not implement [`Error`].
+#[cfg(not(feature = "std"))]
+#[allow(dead_code)]
 #[derive(Debug)]
-pub struct SigningError {
-    msg: String,
-    source: Option<Box<dyn Error + Send + Sync>>,
+struct SourceMessage(String);
+
+#[cfg(not(feature = "std"))]
+impl fmt::Display for SourceMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
+#[cfg(not(feature = "std"))]
+impl Error for SourceMessage {}
+
 /// An error during encoding of key material.
-impl SigningError {
-    #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
-    pub(crate) fn new<S: ToString>(msg: S) -> Self {
-        Self {
-            msg: msg.to_string(),
-            source: None,
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EncodingError {
+    /// The post-quantum key type has no standardised encoding in the requested format.
+    UnsupportedForPostQuantum(KeyType),
+    /// The classical key type is not supported by the requested format.
+    UnsupportedKeyType(KeyType),
+    /// The multihash code is not allowed for peer ids, or not for a key of this size.
+    UnsupportedMultihash(u64),
+    /// The raw key bytes do not have a valid length for the key type.
+    InvalidKeyLength {
+        /// The key type the bytes were given as.
+        key_type: KeyType,
+        /// The length of the bytes.
+        len: usize,
+    },
+    /// The output buffer is too small for the encoding.
+    BufferTooSmall {
+        /// The number of bytes the encoding needs.
+        needed: usize,
+        /// The length of the buffer.
+        available: usize,
+    },
+}
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodingError::UnsupportedForPostQuantum(key_type) => write!(
+                f,
+                "Key encoding error: {key_type} keys have no standard encoding in this format"
+            ),
+            EncodingError::UnsupportedKeyType(key_type) => write!(
+                f,
+                "Key encoding error: {key_type} keys are not supported by this format"
+            ),
+            EncodingError::UnsupportedMultihash(code) => write!(
+                f,
+                "Key encoding error: multihash code {code:#x} cannot be used for this peer id"
+            ),
+            EncodingError::InvalidKeyLength { key_type, len } => write!(
+                f,
+                "Key encoding error: {len} bytes is not a valid length for a {key_type} public key"
+            ),
+            EncodingError::BufferTooSmall { needed, available } => write!(
+                f,
+                "Key encoding error: the encoding needs {needed} bytes, but the buffer holds {available}"
+            ),
         }
     }
+}
 
-    #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
-    pub(crate) fn source(self, source: impl Error + Send + Sync + 'static) -> Self {
+impl Error for EncodingError {}
+
+/// An error parsing a [`KeyType`] from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKeyTypeError {
+    input: String,
+}
+
+impl ParseKeyTypeError {
+    pub(crate) fn new(input: &str) -> Self {
         Self {
-            source: Some(Box::new(source)),
-            ..self
+            input: input.into(),
         }
     }
 }
 
+impl fmt::Display for ParseKeyTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown key type `{}`, expected one of Ed25519, RSA, Secp256k1, Ecdsa, Dilithium, \
+             HybridDilithiumEd25519, SphincsPlus or Falcon",
+            self.input
+        )
+    }
+}
+
+impl Error for ParseKeyTypeError {}
+
+/// An error during signing of a message.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SigningError {
+    /// The key type cannot produce the requested signature in this build.
+    UnsupportedKeyType(KeyType),
+    /// The underlying signature implementation failed.
+    BackendFailure(BackendError),
+    /// The context passed to
+    /// [`Keypair::sign_with_context`](crate::Keypair::sign_with_context) is longer than
+    /// [`Keypair::MAX_CONTEXT_LEN`](crate::Keypair::MAX_CONTEXT_LEN) bytes.
+    ContextTooLong(usize),
+    /// A [`KeypairBuilder`](crate::KeypairBuilder) option was set that does not apply
+    /// to the key type, e.g. a Dilithium level on an Ed25519 key.
+    UnsupportedOption {
+        key_type: KeyType,
+        option: &'static str,
+    },
+}
+
+/// The details of a [`SigningError::BackendFailure`] error.
+#[derive(Debug)]
+pub struct BackendError {
+    msg: String,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl SigningError {
+    #[cfg(any(all(feature = "rsa", not(target_arch = "wasm32")), feature = "async"))]
+    pub(crate) fn backend(msg: impl ToString, source: impl IntoSource) -> Self {
+        SigningError::BackendFailure(BackendError {
+            msg: msg.to_string(),
+            source: Some(source.into_source()),
+        })
+    }
+}
+
 impl fmt::Display for SigningError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Key signing error: {}", self.msg)
+        match self {
+            SigningError::UnsupportedKeyType(key_type) => {
+                write!(f, "Key signing error: {key_type} keys are not supported")
+            }
+            SigningError::BackendFailure(e) => write!(f, "Key signing error: {}", e.msg),
+            SigningError::ContextTooLong(len) => write!(
+                f,
+                "Key signing error: context of {len} bytes exceeds the maximum of 255"
+            ),
+            SigningError::UnsupportedOption { key_type, option } => write!(
+                f,
+                "Key generation error: option `{option}` does not apply to {key_type} keys"
+            ),
+        }
     }
 }
 
 impl Error for SigningError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.source.as_ref().map(|s| &**s as &dyn Error)
+        match self {
+            SigningError::BackendFailure(e) => e.source.as_ref().map(|s| &**s as &dyn Error),
+            SigningError::UnsupportedKeyType(_)
+            | SigningError::ContextTooLong(_)
+            | SigningError::UnsupportedOption { .. } => None,
+        }
+    }
+}
+
+/// Reason why a signature failed to verify, see
+/// [`PublicKey::verify_detailed`](crate::PublicKey::verify_detailed).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerificationError {
+    /// The signature could not be parsed, e.g. it is not valid DER for an ECDSA or
+    /// Secp256k1 key, or the framing of a hybrid signature is broken.
+    MalformedSignature,
+    /// The key only produces signatures of a fixed length, and this one has a different length.
+    WrongLength { expected: usize, actual: usize },
+    /// The signature has the length of signatures of another key type, so it was most
+    /// likely produced by a key of that type.
+    KeyTypeMismatch { expected: KeyType, actual: KeyType },
+    /// The signature is well-formed but not valid for the message and key.
+    InvalidSignature,
+    /// The message is longer than the caller allowed, see
+    /// [`PublicKey::verify_bounded`](crate::PublicKey::verify_bounded).
+    InputTooLarge { max: usize, actual: usize },
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationError::MalformedSignature => {
+                f.write_str("Signature verification error: signature is malformed")
+            }
+            VerificationError::WrongLength { expected, actual } => write!(
+                f,
+                "Signature verification error: expected a signature of {expected} bytes, got {actual}"
+            ),
+            VerificationError::KeyTypeMismatch { expected, actual } => write!(
+                f,
+                "Signature verification error: expected a {expected} signature, got what looks like a {actual} signature"
+            ),
+            VerificationError::InvalidSignature => {
+                f.write_str("Signature verification error: signature is invalid")
+            }
+            VerificationError::InputTooLarge { max, actual } => write!(
+                f,
+                "Signature verification error: message of {actual} bytes exceeds the limit of {max}"
+            ),
+        }
+    }
+}
+
+impl Error for VerificationError {}
+
+/// Reason why key material failed structural validation, see
+/// [`Keypair::validate`](crate::Keypair::validate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// A coefficient of the secret key lies outside the range the scheme allows.
+    SecretCoefficientOutOfRange,
+    /// The public key is not the one the secret key derives.
+    MismatchedPublicKey,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::SecretCoefficientOutOfRange => {
+                f.write_str("Key validation error: secret key coefficient out of range")
+            }
+            ValidationError::MismatchedPublicKey => {
+                f.write_str("Key validation error: public key does not match the secret key")
+            }
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// An error during key generation, see
+/// [`Keypair::try_generate_dilithium`](crate::Keypair::try_generate_dilithium).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GenerationError {
+    /// The random number generator could not provide the seed, e.g. because the
+    /// entropy source is not available yet.
+    EntropyFailure,
+}
+
+impl fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerationError::EntropyFailure => {
+                f.write_str("Key generation error: the random number generator failed")
+            }
+        }
     }
 }
 
+impl Error for GenerationError {}
+
 /// Error produced when failing to convert [`Keypair`](crate::Keypair) to a more concrete keypair.
 #[derive(Debug)]
 pub struct OtherVariantError {
@@ -157,11 +500,164 @@ impl OtherVariantError {
 
 impl fmt::Display for OtherVariantError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&format!(
+        write!(
+            f,
             "Cannot convert to the given type, the actual key type inside is {}",
             self.actual
-        ))
+        )
     }
 }
 
 impl Error for OtherVariantError {}
+
+/// Error produced when failing to derive a child [`Keypair`](crate::Keypair).
+#[derive(Debug)]
+pub struct DeriveError {
+    key_type: KeyType,
+}
+
+impl DeriveError {
+    #[allow(dead_code)]
+    pub(crate) fn new(key_type: KeyType) -> DeriveError {
+        DeriveError { key_type }
+    }
+}
+
+impl fmt::Display for DeriveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cannot derive a child key from a {} keypair",
+            self.key_type
+        )
+    }
+}
+
+impl Error for DeriveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_is_error<T: Error + Send + Sync + 'static>() {}
+
+    #[test]
+    fn error_types_are_send_sync_static() {
+        assert_is_error::<DecodingError>();
+        assert_is_error::<SigningError>();
+        assert_is_error::<OtherVariantError>();
+        assert_is_error::<DeriveError>();
+        assert_is_error::<VerificationError>();
+        assert_is_error::<EncodingError>();
+        assert_is_error::<ValidationError>();
+    }
+
+    #[test]
+    fn decoding_error_messages() {
+        assert_eq!(
+            DecodingError::new("bad key".to_string()).to_string(),
+            "Key decoding error: bad key"
+        );
+        assert_eq!(
+            DecodingError::disabled_key_type(KeyType::Ed25519).to_string(),
+            "Key decoding error: cargo feature `ed25519` is not enabled"
+        );
+        assert!(DecodingError::new("bad key".to_string()).source().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn decoding_error_chains_source() {
+        let source = std::fmt::Error;
+        let error = DecodingError::bad_protobuf("public key bytes", source);
+
+        assert_eq!(
+            error.to_string(),
+            "Key decoding error: failed to decode public key bytes from protobuf"
+        );
+        assert_eq!(
+            error.source().map(ToString::to_string),
+            Some(source.to_string())
+        );
+
+        let error = DecodingError::failed_to_parse::<std::fmt::Error, _>("Dilithium keypair", None);
+        assert_eq!(
+            error.to_string(),
+            "Key decoding error: failed to parse Dilithium keypair"
+        );
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn parse_key_type_error_message() {
+        assert_eq!(
+            "ed448".parse::<KeyType>().unwrap_err().to_string(),
+            "unknown key type `ed448`, expected one of Ed25519, RSA, Secp256k1, Ecdsa, \
+             Dilithium, HybridDilithiumEd25519, SphincsPlus or Falcon"
+        );
+    }
+
+    #[test]
+    fn signing_error_message() {
+        assert_eq!(
+            SigningError::UnsupportedKeyType(KeyType::RSA).to_string(),
+            "Key signing error: RSA keys are not supported"
+        );
+        assert!(SigningError::UnsupportedKeyType(KeyType::RSA)
+            .source()
+            .is_none());
+        assert_eq!(
+            SigningError::ContextTooLong(300).to_string(),
+            "Key signing error: context of 300 bytes exceeds the maximum of 255"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn signing_error_chains_source() {
+        let source = std::fmt::Error;
+        let error = SigningError::backend("backend unavailable", source);
+
+        assert_eq!(error.to_string(), "Key signing error: backend unavailable");
+        assert_eq!(
+            error.source().map(ToString::to_string),
+            Some(source.to_string())
+        );
+    }
+
+    #[test]
+    fn verification_error_message() {
+        assert_eq!(
+            VerificationError::WrongLength {
+                expected: 4627,
+                actual: 4626
+            }
+            .to_string(),
+            "Signature verification error: expected a signature of 4627 bytes, got 4626"
+        );
+        assert_eq!(
+            VerificationError::KeyTypeMismatch {
+                expected: KeyType::Dilithium,
+                actual: KeyType::Ed25519
+            }
+            .to_string(),
+            "Signature verification error: expected a Dilithium signature, got what looks like a Ed25519 signature"
+        );
+    }
+
+    #[test]
+    fn other_variant_error_message() {
+        assert_eq!(
+            OtherVariantError::new(KeyType::Dilithium).to_string(),
+            "Cannot convert to the given type, the actual key type inside is Dilithium"
+        );
+    }
+
+    #[test]
+    fn derive_error_message() {
+        assert_eq!(
+            DeriveError::new(KeyType::Ed25519).to_string(),
+            "Cannot derive a child key from a Ed25519 keypair"
+        );
+    }
+}
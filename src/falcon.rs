@@ -0,0 +1,327 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Falcon (FN-DSA) keys.
+//!
+//! Keys use Falcon-1024 (FN-DSA with `logn = 10`), at NIST security category 5,
+//! the same category as the default Dilithium level. Signatures are about a quarter
+//! of the size of Dilithium ones, at the cost of slower, floating-point heavy signing.
+//!
+//! FN-DSA is still being standardised, so keys and signatures may not interoperate
+//! with the final standard.
+
+use super::error::{DecodingError, SigningError};
+use core::fmt;
+use fn_dsa::{
+    sign_key_size, signature_size, vrfy_key_size, DomainContext, KeyPairGenerator,
+    KeyPairGenerator1024, SigningKey, SigningKey1024, VerifyingKey, VerifyingKey1024,
+    FN_DSA_LOGN_1024, HASH_ID_RAW,
+};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Length in bytes of an encoded Falcon public key.
+pub const PUBLIC_KEY_LEN: usize = vrfy_key_size(FN_DSA_LOGN_1024);
+
+/// Length in bytes of an encoded Falcon secret key.
+pub const SECRET_KEY_LEN: usize = sign_key_size(FN_DSA_LOGN_1024);
+
+/// Maximum length in bytes of a Falcon signature.
+///
+/// FN-DSA pads signatures to this length, but callers should not rely on it:
+/// the original Falcon encoding is variable-length.
+pub const MAX_SIGNATURE_LEN: usize = signature_size(FN_DSA_LOGN_1024);
+
+/// A Falcon keypair.
+#[derive(Clone)]
+pub struct Keypair {
+    secret: SecretKey,
+    public: PublicKey,
+}
+
+impl Keypair {
+    /// Generate a new random Falcon keypair.
+    #[cfg(feature = "rand")]
+    pub fn generate() -> Keypair {
+        Keypair::generate_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generate a new Falcon keypair using the given RNG.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: rand::RngCore + rand::CryptoRng>(rng: &mut R) -> Keypair {
+        let mut secret = Zeroizing::new(vec![0u8; SECRET_KEY_LEN]);
+        let mut public = vec![0u8; PUBLIC_KEY_LEN];
+        KeyPairGenerator1024::default().keygen(FN_DSA_LOGN_1024, rng, &mut secret, &mut public);
+
+        Keypair {
+            secret: SecretKey(secret.to_vec()),
+            public: PublicKey(public),
+        }
+    }
+
+    /// Encode the keypair as its secret key, which embeds the public key.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.secret.to_bytes()
+    }
+
+    /// Try to parse a keypair from the format produced by [`Keypair::to_bytes`].
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Keypair, DecodingError> {
+        SecretKey::try_from_bytes(bytes).map(Keypair::from)
+    }
+
+    /// Sign a message using the private key of this keypair.
+    ///
+    /// Falcon signing is randomised, so signing the same message twice yields
+    /// different signatures.
+    pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
+        self.secret.sign(&[], msg, &mut rand::rngs::OsRng)
+    }
+
+    /// Sign a message like [`Keypair::sign`], drawing the randomness from `rng`.
+    pub fn sign_with_rng<R: rand::RngCore + rand::CryptoRng>(
+        &self,
+        msg: &[u8],
+        rng: &mut R,
+    ) -> Vec<u8> {
+        self.secret.sign(&[], msg, rng)
+    }
+
+    /// Sign a message bound to the context string `ctx`, using the FN-DSA context field.
+    ///
+    /// The signature only verifies with [`PublicKey::verify_with_context`] under the
+    /// same context. An empty context is the same as [`Keypair::sign`].
+    ///
+    /// Contexts longer than 255 bytes are rejected with [`SigningError::ContextTooLong`].
+    pub fn sign_with_context(&self, ctx: &[u8], msg: &[u8]) -> Result<Vec<u8>, SigningError> {
+        if ctx.len() > 255 {
+            return Err(SigningError::ContextTooLong(ctx.len()));
+        }
+        Ok(self.secret.sign(ctx, msg, &mut rand::rngs::OsRng))
+    }
+
+    /// Get the public key of this keypair.
+    pub fn public(&self) -> &PublicKey {
+        &self.public
+    }
+
+    /// Get the secret key of this keypair.
+    pub fn secret(&self) -> &SecretKey {
+        &self.secret
+    }
+}
+
+impl fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keypair")
+            .field("public", &self.public)
+            .finish()
+    }
+}
+
+/// Demote a Falcon keypair to a secret key.
+impl From<Keypair> for SecretKey {
+    fn from(kp: Keypair) -> SecretKey {
+        kp.secret
+    }
+}
+
+/// Promote a Falcon secret key into a keypair.
+impl From<SecretKey> for Keypair {
+    fn from(secret: SecretKey) -> Keypair {
+        let mut public = vec![0u8; PUBLIC_KEY_LEN];
+        secret.signing_key().to_verifying_key(&mut public);
+        Keypair {
+            secret,
+            public: PublicKey(public),
+        }
+    }
+}
+
+/// A Falcon public key.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PublicKey(Vec<u8>);
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PublicKey: ")?;
+        for byte in &self.0 {
+            write!(f, "{byte:x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PublicKey {
+    /// Verify the Falcon signature on a message using the public key.
+    ///
+    /// The signature length is checked by the decoder, so signatures of any
+    /// length are accepted as input.
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
+        self.verify_with_context(&[], msg, sig)
+    }
+
+    /// Verify a signature made with [`Keypair::sign_with_context`] under the context `ctx`.
+    ///
+    /// Contexts longer than 255 bytes never verify.
+    pub fn verify_with_context(&self, ctx: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+        if ctx.len() > 255 || sig.is_empty() || sig.len() > MAX_SIGNATURE_LEN {
+            return false;
+        }
+        VerifyingKey1024::decode(&self.0)
+            .map(|vk| vk.verify(sig, &DomainContext(ctx), &HASH_ID_RAW, msg))
+            .unwrap_or(false)
+    }
+
+    /// Convert the public key to its raw byte representation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+
+    /// The raw bytes of the public key, without copying them.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Try to parse a public key from the raw bytes produced by [`PublicKey::to_bytes`].
+    pub fn try_from_bytes(k: &[u8]) -> Result<PublicKey, DecodingError> {
+        if k.len() != PUBLIC_KEY_LEN {
+            return Err(DecodingError::new(format!(
+                "failed to parse Falcon public key: expected {PUBLIC_KEY_LEN} bytes, got {}",
+                k.len()
+            )));
+        }
+        if VerifyingKey1024::decode(k).is_none() {
+            return Err(DecodingError::new(
+                "failed to parse Falcon public key: invalid encoding".to_string(),
+            ));
+        }
+        Ok(PublicKey(k.to_vec()))
+    }
+}
+
+/// A Falcon secret key.
+///
+/// The key material is wiped when the secret key is dropped.
+#[derive(Clone)]
+pub struct SecretKey(Vec<u8>);
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretKey")
+    }
+}
+
+/// Wipe the secret key bytes before the buffer is freed.
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl zeroize::ZeroizeOnDrop for SecretKey {}
+
+impl SecretKey {
+    /// Decode the secret key into the expanded form used for signing, which is wiped
+    /// when it is dropped.
+    fn signing_key(&self) -> Zeroizing<SigningKey1024> {
+        Zeroizing::new(
+            SigningKey1024::decode(&self.0).expect("secret key is validated on construction"),
+        )
+    }
+
+    fn sign<R: rand::RngCore + rand::CryptoRng>(
+        &self,
+        ctx: &[u8],
+        msg: &[u8],
+        rng: &mut R,
+    ) -> Vec<u8> {
+        let mut sig = vec![0u8; MAX_SIGNATURE_LEN];
+        self.signing_key()
+            .sign(rng, &DomainContext(ctx), &HASH_ID_RAW, msg, &mut sig)
+            .expect("secret key is validated on construction");
+        sig
+    }
+
+    /// Try to parse a Falcon secret key from a byte slice containing the actual key.
+    pub fn try_from_bytes(sk: &[u8]) -> Result<SecretKey, DecodingError> {
+        if SigningKey1024::decode(sk).is_none_or(|k| k.get_logn() != FN_DSA_LOGN_1024) {
+            return Err(DecodingError::new(format!(
+                "failed to parse Falcon secret key: expected a valid Falcon-1024 key of {SECRET_KEY_LEN} bytes, got {} bytes",
+                sk.len()
+            )));
+        }
+        Ok(SecretKey(sk.to_vec()))
+    }
+
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falcon_keypair_encode_decode() {
+        let kp1 = Keypair::generate();
+        let kp2 = Keypair::try_from_bytes(&kp1.to_bytes()).unwrap();
+
+        assert_eq!(kp1.public(), kp2.public());
+        assert_eq!(kp1.secret().to_bytes(), kp2.secret().to_bytes());
+        assert_eq!(kp1.to_bytes().len(), SECRET_KEY_LEN);
+        assert_eq!(kp1.public().to_bytes().len(), PUBLIC_KEY_LEN);
+    }
+
+    #[test]
+    fn falcon_signature() {
+        let kp = Keypair::generate();
+        let pk = kp.public();
+
+        let msg = "hello world".as_bytes();
+        let sig = kp.sign(msg);
+        assert!(sig.len() <= MAX_SIGNATURE_LEN);
+        assert!(pk.verify(msg, &sig));
+
+        let mut invalid_sig = sig.clone();
+        invalid_sig[3..6].copy_from_slice(&[10, 23, 42]);
+        assert!(!pk.verify(msg, &invalid_sig));
+
+        let invalid_msg = "h3ll0 w0rld".as_bytes();
+        assert!(!pk.verify(invalid_msg, &sig));
+
+        assert!(!pk.verify(msg, &[]));
+        assert!(!pk.verify(msg, &sig[..sig.len() - 1]));
+        assert!(!pk.verify(msg, &[sig.as_slice(), &[0]].concat()));
+    }
+
+    #[test]
+    fn falcon_invalid_keys_are_rejected() {
+        assert!(PublicKey::try_from_bytes(&[0u8; 32]).is_err());
+        assert!(PublicKey::try_from_bytes(&[0u8; PUBLIC_KEY_LEN]).is_err());
+        assert!(Keypair::try_from_bytes(&[0u8; 64]).is_err());
+        assert!(SecretKey::try_from_bytes(&[0u8; SECRET_KEY_LEN]).is_err());
+    }
+
+    #[test]
+    fn falcon_secret_key_zeroizes_on_drop() {
+        fn assert_zeroize_on_drop<T: zeroize::ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<SecretKey>();
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! C API for verifying signatures and deriving peer ids from non-Rust services.
+//!
+//! Public keys are passed in their protobuf encoding, as produced by
+//! [`PublicKey::encode_protobuf`]. Every function returns [`LIBP2P_PQC_OK`] or one of
+//! the negative `LIBP2P_PQC_ERR_*` codes, and never unwinds across the boundary.
+//!
+//! To link against it, build the crate as a C library, e.g.
+//! `cargo rustc --release --features ffi --crate-type cdylib`.
+//!
+//! # Pointer contract
+//!
+//! Every `(ptr, len)` pair must describe `len` initialized bytes that stay valid and
+//! unmodified for the duration of the call. A pointer may be null only if its length
+//! is zero. Output pointers must be valid for writes of the given length.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::{PublicKey, VerificationError};
+
+/// The call succeeded; for [`libp2p_pqc_verify`], the signature is valid.
+pub const LIBP2P_PQC_OK: i32 = 0;
+/// A pointer was null while its length was not zero.
+pub const LIBP2P_PQC_ERR_NULL_POINTER: i32 = -1;
+/// The public key is not a valid protobuf encoded key of an enabled key type.
+pub const LIBP2P_PQC_ERR_INVALID_PUBLIC_KEY: i32 = -2;
+/// See [`VerificationError::MalformedSignature`].
+pub const LIBP2P_PQC_ERR_MALFORMED_SIGNATURE: i32 = -3;
+/// See [`VerificationError::WrongLength`].
+pub const LIBP2P_PQC_ERR_WRONG_LENGTH: i32 = -4;
+/// See [`VerificationError::KeyTypeMismatch`].
+pub const LIBP2P_PQC_ERR_KEY_TYPE_MISMATCH: i32 = -5;
+/// See [`VerificationError::InvalidSignature`].
+pub const LIBP2P_PQC_ERR_INVALID_SIGNATURE: i32 = -6;
+/// The output buffer is too small; the required length has been written out.
+pub const LIBP2P_PQC_ERR_BUFFER_TOO_SMALL: i32 = -7;
+/// An unexpected internal error, including a caught panic.
+pub const LIBP2P_PQC_ERR_INTERNAL: i32 = -8;
+
+/// Verify `sig` over `msg` with the protobuf encoded public key `pubkey`.
+///
+/// Returns [`LIBP2P_PQC_OK`] if the signature is valid, and otherwise the error code
+/// of the matching [`VerificationError`] variant, or of the invalid input.
+///
+/// # Safety
+///
+/// All three `(ptr, len)` pairs must follow the [pointer contract](self#pointer-contract).
+#[no_mangle]
+pub unsafe extern "C" fn libp2p_pqc_verify(
+    pubkey_ptr: *const u8,
+    pubkey_len: usize,
+    msg_ptr: *const u8,
+    msg_len: usize,
+    sig_ptr: *const u8,
+    sig_len: usize,
+) -> i32 {
+    let (Some(pubkey), Some(msg), Some(sig)) = (
+        slice(pubkey_ptr, pubkey_len),
+        slice(msg_ptr, msg_len),
+        slice(sig_ptr, sig_len),
+    ) else {
+        return LIBP2P_PQC_ERR_NULL_POINTER;
+    };
+
+    guard(|| {
+        let Ok(public) = PublicKey::try_decode_protobuf(pubkey) else {
+            return LIBP2P_PQC_ERR_INVALID_PUBLIC_KEY;
+        };
+        match public.verify_detailed(msg, sig) {
+            Ok(()) => LIBP2P_PQC_OK,
+            Err(e) => verification_error_code(&e),
+        }
+    })
+    .unwrap_or_else(|code| code)
+}
+
+/// Write the [`PeerId`](crate::PeerId) bytes of the protobuf encoded public key
+/// `pubkey` to `out`.
+///
+/// The length of the peer id is written to `written` on success and, with
+/// [`LIBP2P_PQC_ERR_BUFFER_TOO_SMALL`], when `out_len` is too small, so callers can
+/// retry with a large enough buffer. 64 bytes are always enough.
+///
+/// # Safety
+///
+/// `(pubkey_ptr, pubkey_len)` and `(out_ptr, out_len)` must follow the
+/// [pointer contract](self#pointer-contract), and `written` must be valid for a
+/// write of a `usize`.
+#[no_mangle]
+pub unsafe extern "C" fn libp2p_pqc_peer_id(
+    pubkey_ptr: *const u8,
+    pubkey_len: usize,
+    out_ptr: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> i32 {
+    if written.is_null() || (out_ptr.is_null() && out_len != 0) {
+        return LIBP2P_PQC_ERR_NULL_POINTER;
+    }
+    let Some(pubkey) = slice(pubkey_ptr, pubkey_len) else {
+        return LIBP2P_PQC_ERR_NULL_POINTER;
+    };
+
+    let peer_id = match guard(|| PublicKey::try_decode_protobuf(pubkey).map(|k| k.to_peer_id())) {
+        Ok(Ok(peer_id)) => peer_id.to_bytes(),
+        Ok(Err(_)) => return LIBP2P_PQC_ERR_INVALID_PUBLIC_KEY,
+        Err(code) => return code,
+    };
+
+    written.write(peer_id.len());
+    if peer_id.len() > out_len {
+        return LIBP2P_PQC_ERR_BUFFER_TOO_SMALL;
+    }
+    out_ptr.copy_from_nonoverlapping(peer_id.as_ptr(), peer_id.len());
+    LIBP2P_PQC_OK
+}
+
+fn verification_error_code(e: &VerificationError) -> i32 {
+    match e {
+        VerificationError::MalformedSignature => LIBP2P_PQC_ERR_MALFORMED_SIGNATURE,
+        VerificationError::WrongLength { .. } => LIBP2P_PQC_ERR_WRONG_LENGTH,
+        VerificationError::KeyTypeMismatch { .. } => LIBP2P_PQC_ERR_KEY_TYPE_MISMATCH,
+        VerificationError::InvalidSignature => LIBP2P_PQC_ERR_INVALID_SIGNATURE,
+        #[allow(unreachable_patterns)]
+        _ => LIBP2P_PQC_ERR_INTERNAL,
+    }
+}
+
+/// Borrow a `(ptr, len)` pair as a slice, or `None` if it is null with a nonzero length.
+///
+/// # Safety
+///
+/// The pair must follow the [pointer contract](self#pointer-contract).
+unsafe fn slice<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        return Some(&[]);
+    }
+    if ptr.is_null() {
+        return None;
+    }
+    Some(std::slice::from_raw_parts(ptr, len))
+}
+
+/// Run `f`, turning a panic into [`LIBP2P_PQC_ERR_INTERNAL`] instead of unwinding
+/// into the caller.
+fn guard<T>(f: impl FnOnce() -> T) -> Result<T, i32> {
+    catch_unwind(AssertUnwindSafe(f)).map_err(|_| LIBP2P_PQC_ERR_INTERNAL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Keypair, PeerId};
+
+    fn verify(pubkey: &[u8], msg: &[u8], sig: &[u8]) -> i32 {
+        unsafe {
+            libp2p_pqc_verify(
+                pubkey.as_ptr(),
+                pubkey.len(),
+                msg.as_ptr(),
+                msg.len(),
+                sig.as_ptr(),
+                sig.len(),
+            )
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn verify_maps_verification_errors() {
+        let keypair = Keypair::generate_dilithium();
+        let pubkey = keypair.public().encode_protobuf();
+        let msg = b"hello";
+        let sig = keypair.sign(msg).unwrap();
+
+        assert_eq!(verify(&pubkey, msg, &sig), LIBP2P_PQC_OK);
+        assert_eq!(
+            verify(&pubkey, b"other", &sig),
+            LIBP2P_PQC_ERR_INVALID_SIGNATURE
+        );
+        assert_eq!(verify(&pubkey, msg, &sig[1..]), LIBP2P_PQC_ERR_WRONG_LENGTH);
+        assert_eq!(
+            verify(b"not a key", msg, &sig),
+            LIBP2P_PQC_ERR_INVALID_PUBLIC_KEY
+        );
+        assert_eq!(
+            unsafe {
+                libp2p_pqc_verify(
+                    pubkey.as_ptr(),
+                    pubkey.len(),
+                    std::ptr::null(),
+                    1,
+                    sig.as_ptr(),
+                    sig.len(),
+                )
+            },
+            LIBP2P_PQC_ERR_NULL_POINTER
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn verify_reports_key_type_mismatch() {
+        let dilithium = Keypair::generate_dilithium().public().encode_protobuf();
+        let sig = Keypair::generate_ed25519().sign(b"hello").unwrap();
+
+        assert_eq!(
+            verify(&dilithium, b"hello", &sig),
+            LIBP2P_PQC_ERR_KEY_TYPE_MISMATCH
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn peer_id_is_written_to_buffer() {
+        let public = Keypair::generate_dilithium().public();
+        let pubkey = public.encode_protobuf();
+        let expected = public.to_peer_id();
+
+        let mut out = [0u8; 64];
+        let mut written = 0;
+        let code = unsafe {
+            libp2p_pqc_peer_id(
+                pubkey.as_ptr(),
+                pubkey.len(),
+                out.as_mut_ptr(),
+                out.len(),
+                &mut written,
+            )
+        };
+        assert_eq!(code, LIBP2P_PQC_OK);
+        assert_eq!(PeerId::from_bytes(&out[..written]).unwrap(), expected);
+
+        let mut small = [0u8; 4];
+        let mut required = 0;
+        let code = unsafe {
+            libp2p_pqc_peer_id(
+                pubkey.as_ptr(),
+                pubkey.len(),
+                small.as_mut_ptr(),
+                small.len(),
+                &mut required,
+            )
+        };
+        assert_eq!(code, LIBP2P_PQC_ERR_BUFFER_TOO_SMALL);
+        assert_eq!(required, written);
+        assert_eq!(small, [0; 4]);
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Splitting signatures into fragments for transports with a small MTU.
+//!
+//! Every fragment carries its index, the number of fragments and the length of the
+//! whole signature, so the receiver can reassemble fragments arriving in any order
+//! and detect missing, duplicated or mixed-up ones.
+
+use crate::error::DecodingError;
+use alloc::{format, string::ToString, vec::Vec};
+
+/// Length of the header [`SignatureFragment::to_bytes`] puts in front of the data.
+const HEADER_LEN: usize = 12;
+
+/// One piece of a signature, produced by [`fragment_signature`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureFragment {
+    index: u32,
+    total: u32,
+    signature_len: u32,
+    data: Vec<u8>,
+}
+
+impl SignatureFragment {
+    /// The position of this fragment, starting at `0`.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The number of fragments the signature was split into.
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    /// The length in bytes of the whole signature.
+    pub fn signature_len(&self) -> u32 {
+        self.signature_len
+    }
+
+    /// The part of the signature carried by this fragment.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Encode the fragment for sending: the index, total and signature length as
+    /// big-endian `u32`s, followed by the data.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.data.len());
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        bytes.extend_from_slice(&self.total.to_be_bytes());
+        bytes.extend_from_slice(&self.signature_len.to_be_bytes());
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+
+    /// Decode a fragment encoded by [`SignatureFragment::to_bytes`].
+    ///
+    /// Only the framing is checked here; consistency with the other fragments is
+    /// checked by [`reassemble_signature`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodingError> {
+        if bytes.len() < HEADER_LEN {
+            return Err(DecodingError::new(
+                "signature fragment is shorter than its header".to_string(),
+            ));
+        }
+        let field = |i: usize| u32::from_be_bytes(bytes[4 * i..4 * i + 4].try_into().unwrap());
+
+        Ok(SignatureFragment {
+            index: field(0),
+            total: field(1),
+            signature_len: field(2),
+            data: bytes[HEADER_LEN..].to_vec(),
+        })
+    }
+}
+
+/// Split a signature into fragments carrying at most `max_chunk` bytes of it each.
+///
+/// An empty signature yields a single empty fragment.
+///
+/// # Panics
+///
+/// Panics if `max_chunk` is `0`, or if the signature is 4 GiB or longer.
+pub fn fragment_signature(sig: &[u8], max_chunk: usize) -> Vec<SignatureFragment> {
+    assert!(max_chunk > 0, "fragments must carry at least one byte");
+    let signature_len = u32::try_from(sig.len()).expect("signatures are far below 4 GiB");
+
+    if sig.is_empty() {
+        return alloc::vec![SignatureFragment {
+            index: 0,
+            total: 1,
+            signature_len,
+            data: Vec::new(),
+        }];
+    }
+
+    let total = sig.len().div_ceil(max_chunk) as u32;
+    sig.chunks(max_chunk)
+        .zip(0..)
+        .map(|(data, index)| SignatureFragment {
+            index,
+            total,
+            signature_len,
+            data: data.to_vec(),
+        })
+        .collect()
+}
+
+/// Reassemble a signature from the fragments produced by [`fragment_signature`], in
+/// any order.
+///
+/// Fails if a fragment is missing or duplicated, if the fragments disagree on the
+/// number of fragments or the signature length, or if their data does not add up to
+/// that length.
+///
+/// The fragment count and signature length in the headers are untrusted, so nothing
+/// is allocated from them until they agree with the fragments actually given.
+pub fn reassemble_signature(frags: &[SignatureFragment]) -> Result<Vec<u8>, DecodingError> {
+    let first = frags
+        .first()
+        .ok_or_else(|| DecodingError::new("no signature fragments".to_string()))?;
+    let (total, signature_len) = (first.total, first.signature_len);
+
+    if frags
+        .iter()
+        .any(|f| f.total != total || f.signature_len != signature_len)
+    {
+        return Err(DecodingError::new(
+            "signature fragments belong to different signatures".to_string(),
+        ));
+    }
+
+    if total as usize > frags.len() {
+        // Some index below `frags.len() + 1` is missing, so no slot is needed beyond it.
+        let mut seen = alloc::vec![false; frags.len() + 1];
+        for frag in frags {
+            if let Some(seen) = seen.get_mut(frag.index as usize) {
+                *seen = true;
+            }
+        }
+        let index = seen
+            .iter()
+            .position(|seen| !seen)
+            .expect("one index is missing");
+        return Err(DecodingError::new(format!(
+            "missing signature fragment {index} of {total}"
+        )));
+    }
+
+    let data_len = frags.iter().map(|f| f.data.len()).sum::<usize>();
+    if data_len != signature_len as usize {
+        return Err(DecodingError::new(format!(
+            "reassembled signature is {data_len} bytes, expected {signature_len}"
+        )));
+    }
+
+    let mut slots: Vec<Option<&[u8]>> = alloc::vec![None; total as usize];
+    for frag in frags {
+        match slots.get_mut(frag.index as usize) {
+            None => {
+                return Err(DecodingError::new(format!(
+                    "signature fragment {} is out of range for {total} fragments",
+                    frag.index
+                )))
+            }
+            Some(Some(_)) => {
+                return Err(DecodingError::new(format!(
+                    "duplicate signature fragment {}",
+                    frag.index
+                )))
+            }
+            Some(slot) => *slot = Some(&frag.data),
+        }
+    }
+
+    let mut sig = Vec::with_capacity(data_len);
+    for (index, slot) in slots.into_iter().enumerate() {
+        let data = slot.ok_or_else(|| {
+            DecodingError::new(format!("missing signature fragment {index} of {total}"))
+        })?;
+        sig.extend_from_slice(data);
+    }
+
+    Ok(sig)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn dilithium_signature_roundtrip() {
+        let keypair = crate::Keypair::generate_dilithium();
+        let sig = keypair.sign(b"too big for one datagram").unwrap();
+
+        let mut frags = fragment_signature(&sig, 512);
+
+        assert_eq!(frags.len(), sig.len().div_ceil(512));
+        assert!(frags.iter().all(|f| f.data().len() <= 512));
+        frags.reverse();
+        assert_eq!(reassemble_signature(&frags).unwrap(), sig);
+
+        let sent = frags.iter().map(SignatureFragment::to_bytes);
+        let received = sent
+            .map(|bytes| SignatureFragment::from_bytes(&bytes).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(received, frags);
+    }
+
+    #[test]
+    fn missing_fragment_is_detected() {
+        let sig = [7u8; 1000];
+        let mut frags = fragment_signature(&sig, 300);
+        frags.remove(2);
+
+        let err = reassemble_signature(&frags).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("missing signature fragment 2 of 4"));
+    }
+
+    #[test]
+    fn inconsistent_fragments_are_rejected() {
+        let sig = [7u8; 1000];
+        let frags = fragment_signature(&sig, 300);
+
+        let duplicated = [frags.clone(), alloc::vec![frags[1].clone()]].concat();
+        assert!(reassemble_signature(&duplicated).is_err());
+
+        let mut other = fragment_signature(&[8u8; 1100], 300);
+        other[3] = frags[3].clone();
+        assert!(reassemble_signature(&other).is_err());
+
+        let mut truncated = frags.clone();
+        truncated[3].data.pop();
+        assert!(reassemble_signature(&truncated).is_err());
+
+        assert!(reassemble_signature(&[]).is_err());
+        assert!(SignatureFragment::from_bytes(&[0; HEADER_LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn forged_header_sizes_are_rejected_before_allocating() {
+        let mut frag = fragment_signature(&[7u8; 100], 100).remove(0);
+        frag.total = u32::MAX;
+
+        let err = reassemble_signature(&[frag.clone()]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&format!("missing signature fragment 1 of {}", u32::MAX)));
+
+        frag.total = 1;
+        frag.signature_len = u32::MAX;
+        let err = reassemble_signature(&[frag]).unwrap_err();
+        assert!(err.to_string().contains("expected 4294967295"));
+    }
+
+    #[test]
+    fn empty_signature() {
+        let frags = fragment_signature(&[], 512);
+
+        assert_eq!(frags.len(), 1);
+        assert_eq!(reassemble_signature(&frags).unwrap(), Vec::<u8>::new());
+    }
+}
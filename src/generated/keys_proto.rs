@@ -12,6 +12,8 @@
 use quick_protobuf::{MessageInfo, MessageRead, MessageWrite, BytesReader, Writer, WriterBackend, Result};
 use quick_protobuf::sizeofs::*;
 use super::*;
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum KeyType {
@@ -20,6 +22,9 @@ pub enum KeyType {
     Secp256k1 = 2,
     ECDSA = 3,
     Dilithium = 4,
+    HybridDilithiumEd25519 = 5,
+    SphincsPlus = 6,
+    Falcon = 7,
 }
 
 impl Default for KeyType {
@@ -36,6 +41,9 @@ impl From<i32> for KeyType {
             2 => KeyType::Secp256k1,
             3 => KeyType::ECDSA,
             4 => KeyType::Dilithium,
+            5 => KeyType::HybridDilithiumEd25519,
+            6 => KeyType::SphincsPlus,
+            7 => KeyType::Falcon,
             _ => Self::default(),
         }
     }
@@ -49,6 +57,9 @@ impl<'a> From<&'a str> for KeyType {
             "Secp256k1" => KeyType::Secp256k1,
             "ECDSA" => KeyType::ECDSA,
             "Dilithium" => KeyType::Dilithium,
+            "HybridDilithiumEd25519" => KeyType::HybridDilithiumEd25519,
+            "SphincsPlus" => KeyType::SphincsPlus,
+            "Falcon" => KeyType::Falcon,
             _ => Self::default(),
         }
     }
@@ -95,6 +106,7 @@ impl MessageWrite for PublicKey {
 pub struct PrivateKey {
     pub Type: keys_proto::KeyType,
     pub Data: Vec<u8>,
+    pub PreviousKeyFingerprint: Option<Vec<u8>>,
 }
 
 impl<'a> MessageRead<'a> for PrivateKey {
@@ -104,6 +116,7 @@ impl<'a> MessageRead<'a> for PrivateKey {
             match r.next_tag(bytes) {
                 Ok(8) => msg.Type = r.read_enum(bytes)?,
                 Ok(18) => msg.Data = r.read_bytes(bytes)?.to_owned(),
+                Ok(26) => msg.PreviousKeyFingerprint = Some(r.read_bytes(bytes)?.to_owned()),
                 Ok(t) => { r.read_unknown(bytes, t)?; }
                 Err(e) => return Err(e),
             }
@@ -117,11 +130,13 @@ impl MessageWrite for PrivateKey {
         0
         + 1 + sizeof_varint(*(&self.Type) as u64)
         + 1 + sizeof_len((&self.Data).len())
+        + self.PreviousKeyFingerprint.as_ref().map_or(0, |m| 1 + sizeof_len((m).len()))
     }
 
     fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
         w.write_with_tag(8, |w| w.write_enum(*&self.Type as i32))?;
         w.write_with_tag(18, |w| w.write_bytes(&**&self.Data))?;
+        if let Some(ref s) = self.PreviousKeyFingerprint { w.write_with_tag(26, |w| w.write_bytes(&**s))?; }
         Ok(())
     }
 }
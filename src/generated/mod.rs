@@ -1,2 +1,3 @@
 // Automatically generated mod.rs
+pub mod envelope_proto;
 pub mod keys_proto;
@@ -0,0 +1,114 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Hex encoding of protobuf encoded private keys, e.g. for environment variables.
+
+use alloc::{format, string::String, vec::Vec};
+use zeroize::Zeroizing;
+
+use crate::error::DecodingError;
+
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Encode `bytes` as lowercase hex into a buffer that is wiped on drop.
+pub(crate) fn encode(bytes: &[u8]) -> Zeroizing<String> {
+    let mut hex = Zeroizing::new(String::with_capacity(2 * bytes.len()));
+    for byte in bytes {
+        hex.push(DIGITS[usize::from(byte >> 4)].into());
+        hex.push(DIGITS[usize::from(byte & 0xf)].into());
+    }
+    hex
+}
+
+/// Decode hex in either case, ignoring surrounding whitespace and an optional `0x` prefix.
+pub(crate) fn decode(hex: &str) -> Result<Zeroizing<Vec<u8>>, DecodingError> {
+    let trimmed = hex.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed)
+        .as_bytes();
+
+    if !digits.len().is_multiple_of(2) {
+        return Err(DecodingError::new(format!(
+            "hex input has an odd number of digits ({})",
+            digits.len()
+        )));
+    }
+
+    let mut bytes = Zeroizing::new(Vec::with_capacity(digits.len() / 2));
+    for (i, [high, low]) in digits.as_chunks::<2>().0.iter().enumerate() {
+        let high = nibble(*high, 2 * i)?;
+        let low = nibble(*low, 2 * i + 1)?;
+        bytes.push(high << 4 | low);
+    }
+    Ok(bytes)
+}
+
+fn nibble(digit: u8, position: usize) -> Result<u8, DecodingError> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(DecodingError::new(format!(
+            "invalid hex digit {:?} at position {position}",
+            char::from(digit)
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_then_decode() {
+        let data = (0..=255u8).collect::<Vec<_>>();
+        let hex = encode(&data);
+
+        assert_eq!(&hex[..8], "00010203");
+        assert_eq!(*decode(&hex).unwrap(), data);
+        assert_eq!(*decode(&hex.to_uppercase()).unwrap(), data);
+    }
+
+    #[test]
+    fn prefix_and_whitespace_are_ignored() {
+        assert_eq!(*decode("0xdeadBEEF").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(*decode("0XdeadBEEF").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(*decode("  \tdeadbeef\n").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(*decode(" 0x00ff\r\n").unwrap(), [0x00, 0xff]);
+        assert!(decode("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid_hex_is_rejected() {
+        assert_eq!(
+            decode("abc").unwrap_err().to_string(),
+            "Key decoding error: hex input has an odd number of digits (3)"
+        );
+        assert_eq!(
+            decode("0xabzz").unwrap_err().to_string(),
+            "Key decoding error: invalid hex digit 'z' at position 2"
+        );
+        assert!(decode("de ad").is_err());
+        assert!(decode("0x0x00").is_err());
+        assert!(decode("é0").is_err());
+    }
+}
@@ -0,0 +1,369 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Composite keys pairing a Dilithium key with an Ed25519 key.
+//!
+//! A hybrid signature is only valid if both of its component signatures are, so it
+//! remains secure for as long as either scheme is unbroken.
+//!
+//! Public keys, keypairs and signatures are all encoded as the Dilithium component
+//! followed by the Ed25519 component, each prefixed with its unsigned-varint length.
+
+use crate::{dilithium, ed25519, error::DecodingError};
+use std::fmt;
+use unsigned_varint::encode::usize_buffer;
+use zeroize::Zeroizing;
+
+/// Prepended to the message before it is signed by either component, so that a component
+/// signature cannot be lifted out of a hybrid signature and passed off as a plain one.
+const DOMAIN_SEPARATION: &[u8] = b"libp2p-pqc-hybrid-dilithium-ed25519";
+
+/// A hybrid Dilithium and Ed25519 keypair.
+#[derive(Clone)]
+pub struct Keypair {
+    dilithium: dilithium::Keypair,
+    ed25519: ed25519::Keypair,
+}
+
+impl Keypair {
+    /// Generate a new hybrid keypair with a Dilithium key at the default level.
+    #[cfg(feature = "rand")]
+    pub fn generate() -> Keypair {
+        Keypair {
+            dilithium: dilithium::Keypair::generate(dilithium::DilithiumLevel::default()),
+            ed25519: ed25519::Keypair::generate(),
+        }
+    }
+
+    /// Generate a new hybrid keypair with a Dilithium key at the default level,
+    /// drawing the randomness for both halves from `rng`.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: rand::RngCore + rand::CryptoRng>(rng: &mut R) -> Keypair {
+        Keypair {
+            dilithium: dilithium::Keypair::generate_with_rng(
+                dilithium::DilithiumLevel::default(),
+                rng,
+            ),
+            ed25519: ed25519::Keypair::generate_with_rng(rng),
+        }
+    }
+
+    /// Combine existing Dilithium and Ed25519 keypairs into a hybrid keypair.
+    pub fn from_components(dilithium: dilithium::Keypair, ed25519: ed25519::Keypair) -> Keypair {
+        Keypair { dilithium, ed25519 }
+    }
+
+    /// The Dilithium half of this keypair.
+    pub fn dilithium(&self) -> &dilithium::Keypair {
+        &self.dilithium
+    }
+
+    /// The Ed25519 half of this keypair.
+    pub fn ed25519(&self) -> &ed25519::Keypair {
+        &self.ed25519
+    }
+
+    /// Get the public key of this keypair.
+    pub fn public(&self) -> PublicKey {
+        PublicKey {
+            dilithium: self.dilithium.public().clone(),
+            ed25519: self.ed25519.public(),
+        }
+    }
+
+    /// Sign a message with both component keys.
+    ///
+    /// The Dilithium component is hedged, see [`dilithium::Keypair::sign`].
+    pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
+        let msg = domain_separated(msg);
+        encode_pair(&self.dilithium.sign(&msg), &self.ed25519.sign(&msg))
+    }
+
+    /// Sign a message with deterministic signing in both components, see
+    /// [`dilithium::Keypair::sign_deterministic`].
+    pub fn sign_deterministic(&self, msg: &[u8]) -> Vec<u8> {
+        let msg = domain_separated(msg);
+        encode_pair(
+            &self.dilithium.sign_deterministic(&msg),
+            &self.ed25519.sign(&msg),
+        )
+    }
+
+    /// Encode both component keypairs, as accepted by [`Keypair::try_from_bytes`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let dilithium = Zeroizing::new(self.dilithium.to_bytes());
+        let ed25519 = Zeroizing::new(self.ed25519.to_bytes());
+        encode_pair(&dilithium, ed25519.as_ref())
+    }
+
+    /// Decode a keypair produced by [`Keypair::to_bytes`].
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Keypair, DecodingError> {
+        let (dilithium, ed25519) = decode_pair(bytes, "hybrid keypair")?;
+
+        Ok(Keypair {
+            dilithium: dilithium::Keypair::try_from_bytes(dilithium)?,
+            ed25519: ed25519::Keypair::try_from_bytes(&mut Zeroizing::new(ed25519.to_vec()))?,
+        })
+    }
+
+    /// Encode only the component secret keys, as accepted by [`Keypair::try_from_secret_bytes`].
+    pub(crate) fn secret_to_bytes(&self) -> Vec<u8> {
+        let dilithium = Zeroizing::new(self.dilithium.secret().to_bytes());
+        encode_pair(&dilithium, self.ed25519.secret().as_ref())
+    }
+
+    /// Rebuild a keypair from the secret keys produced by [`Keypair::secret_to_bytes`],
+    /// recomputing both public keys.
+    pub(crate) fn try_from_secret_bytes(bytes: &[u8]) -> Result<Keypair, DecodingError> {
+        let (dilithium, ed25519) = decode_pair(bytes, "hybrid secret key")?;
+
+        Ok(Keypair {
+            dilithium: dilithium::SecretKey::try_from_bytes(dilithium)
+                .and_then(dilithium::Keypair::try_from)?,
+            ed25519: ed25519::SecretKey::try_from_bytes(ed25519.to_vec())?.into(),
+        })
+    }
+}
+
+impl fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keypair")
+            .field("public", &self.public())
+            .finish()
+    }
+}
+
+/// A hybrid Dilithium and Ed25519 public key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PublicKey {
+    dilithium: dilithium::PublicKey,
+    ed25519: ed25519::PublicKey,
+}
+
+impl PublicKey {
+    /// Combine existing Dilithium and Ed25519 public keys into a hybrid public key.
+    pub fn from_components(dilithium: dilithium::PublicKey, ed25519: ed25519::PublicKey) -> Self {
+        PublicKey { dilithium, ed25519 }
+    }
+
+    /// The Dilithium half of this public key.
+    pub fn dilithium(&self) -> &dilithium::PublicKey {
+        &self.dilithium
+    }
+
+    /// The Ed25519 half of this public key.
+    pub fn ed25519(&self) -> &ed25519::PublicKey {
+        &self.ed25519
+    }
+
+    /// Verify a hybrid signature, which requires both component signatures to be valid.
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
+        let Ok((dilithium_sig, ed25519_sig)) = decode_pair(sig, "hybrid signature") else {
+            return false;
+        };
+        let msg = domain_separated(msg);
+
+        self.dilithium.verify(&msg, dilithium_sig) && self.ed25519.verify(&msg, ed25519_sig)
+    }
+
+    /// Verify only the Dilithium component of a hybrid signature, against the Dilithium
+    /// half of a hybrid key, e.g. once the classical half is no longer trusted.
+    ///
+    /// The components sign the domain-separated message, so they never verify as
+    /// plain signatures with [`dilithium::PublicKey::verify`].
+    pub fn verify_dilithium_component(
+        dilithium: &dilithium::PublicKey,
+        msg: &[u8],
+        sig: &[u8],
+    ) -> bool {
+        decode_pair(sig, "hybrid signature")
+            .is_ok_and(|(sig, _)| dilithium.verify(&domain_separated(msg), sig))
+    }
+
+    /// Verify only the Ed25519 component of a hybrid signature, against the Ed25519 half
+    /// of a hybrid key, like [`PublicKey::verify_dilithium_component`].
+    pub fn verify_ed25519_component(ed25519: &ed25519::PublicKey, msg: &[u8], sig: &[u8]) -> bool {
+        decode_pair(sig, "hybrid signature")
+            .is_ok_and(|(_, sig)| ed25519.verify(&domain_separated(msg), sig))
+    }
+
+    /// The length of the hybrid signatures verifiable with this key.
+    pub(crate) fn signature_len(&self) -> usize {
+        let dilithium_len = self.dilithium.level().signature_len();
+        let ed25519_len = 64;
+
+        varint_len(dilithium_len) + dilithium_len + varint_len(ed25519_len) + ed25519_len
+    }
+
+    /// Whether `sig` consists of a Dilithium and an Ed25519 component of the sizes
+    /// expected by this key.
+    pub(crate) fn is_well_formed_signature(&self, sig: &[u8]) -> bool {
+        decode_pair(sig, "hybrid signature").is_ok_and(|(dilithium, ed25519)| {
+            dilithium.len() == self.dilithium.level().signature_len() && ed25519.len() == 64
+        })
+    }
+
+    /// Encode both component public keys, as accepted by [`PublicKey::try_from_bytes`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode_pair(&self.dilithium.to_bytes(), &self.ed25519.to_bytes())
+    }
+
+    /// Decode a public key produced by [`PublicKey::to_bytes`].
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<PublicKey, DecodingError> {
+        let (dilithium, ed25519) = decode_pair(bytes, "hybrid public key")?;
+
+        Ok(PublicKey {
+            dilithium: dilithium::PublicKey::try_from_bytes(dilithium)?,
+            ed25519: ed25519::PublicKey::try_from_bytes(ed25519)?,
+        })
+    }
+}
+
+fn domain_separated(msg: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(DOMAIN_SEPARATION.len() + msg.len());
+    buf.extend_from_slice(DOMAIN_SEPARATION);
+    buf.extend_from_slice(msg);
+    buf
+}
+
+fn varint_len(n: usize) -> usize {
+    unsigned_varint::encode::usize(n, &mut usize_buffer()).len()
+}
+
+/// Concatenate the Dilithium and Ed25519 components, each prefixed with its length.
+fn encode_pair(dilithium: &[u8], ed25519: &[u8]) -> Vec<u8> {
+    let mut dilithium_len_buf = usize_buffer();
+    let dilithium_len = unsigned_varint::encode::usize(dilithium.len(), &mut dilithium_len_buf);
+    let mut ed25519_len_buf = usize_buffer();
+    let ed25519_len = unsigned_varint::encode::usize(ed25519.len(), &mut ed25519_len_buf);
+
+    let mut buf = Vec::with_capacity(
+        dilithium_len.len() + dilithium.len() + ed25519_len.len() + ed25519.len(),
+    );
+    buf.extend_from_slice(dilithium_len);
+    buf.extend_from_slice(dilithium);
+    buf.extend_from_slice(ed25519_len);
+    buf.extend_from_slice(ed25519);
+    buf
+}
+
+/// Split bytes produced by [`encode_pair`], requiring the input to be consumed exactly.
+fn decode_pair<'a>(
+    bytes: &'a [u8],
+    what: &'static str,
+) -> Result<(&'a [u8], &'a [u8]), DecodingError> {
+    fn take<'a>(
+        bytes: &'a [u8],
+        what: &'static str,
+    ) -> Result<(&'a [u8], &'a [u8]), DecodingError> {
+        let (len, rest) = unsigned_varint::decode::usize(bytes)
+            .map_err(|e| DecodingError::failed_to_parse(what, e))?;
+        if rest.len() < len {
+            return Err(DecodingError::new(format!(
+                "failed to parse {what}: component is truncated"
+            )));
+        }
+        Ok(rest.split_at(len))
+    }
+
+    let (dilithium, rest) = take(bytes, what)?;
+    let (ed25519, rest) = take(rest, what)?;
+    if !rest.is_empty() {
+        return Err(DecodingError::new(format!(
+            "failed to parse {what}: {} trailing bytes",
+            rest.len()
+        )));
+    }
+
+    Ok((dilithium, ed25519))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() {
+        let keypair = Keypair::generate();
+        let msg = b"hello world";
+        let sig = keypair.sign(msg);
+
+        assert!(keypair.public().verify(msg, &sig));
+        assert!(!keypair.public().verify(b"other message", &sig));
+        assert!(!Keypair::generate().public().verify(msg, &sig));
+    }
+
+    #[test]
+    fn corrupted_component_signature_is_rejected() {
+        let keypair = Keypair::generate();
+        let msg = b"hello world";
+        let sig = keypair.sign(msg);
+        let (dilithium_sig, ed25519_sig) = decode_pair(&sig, "hybrid signature").unwrap();
+
+        let mut bad_dilithium = dilithium_sig.to_vec();
+        bad_dilithium[0] ^= 1;
+        assert!(!keypair
+            .public()
+            .verify(msg, &encode_pair(&bad_dilithium, ed25519_sig)));
+
+        let mut bad_ed25519 = ed25519_sig.to_vec();
+        bad_ed25519[0] ^= 1;
+        assert!(!keypair
+            .public()
+            .verify(msg, &encode_pair(dilithium_sig, &bad_ed25519)));
+    }
+
+    #[test]
+    fn component_signatures_do_not_verify_alone() {
+        let keypair = Keypair::generate();
+        let msg = b"hello world";
+        let sig = keypair.sign(msg);
+        let (dilithium_sig, ed25519_sig) = decode_pair(&sig, "hybrid signature").unwrap();
+
+        assert!(!keypair.dilithium().public().verify(msg, dilithium_sig));
+        assert!(!keypair.ed25519().public().verify(msg, ed25519_sig));
+    }
+
+    #[test]
+    fn encode_decode() {
+        let keypair = Keypair::generate();
+
+        let decoded = Keypair::try_from_bytes(&keypair.to_bytes()).unwrap();
+        assert_eq!(decoded.public(), keypair.public());
+
+        let restored = Keypair::try_from_secret_bytes(&keypair.secret_to_bytes());
+        #[cfg(feature = "dilithium-internals")]
+        assert_eq!(restored.unwrap().public(), keypair.public());
+        #[cfg(not(feature = "dilithium-internals"))]
+        assert!(restored.is_err());
+
+        let public = PublicKey::try_from_bytes(&keypair.public().to_bytes()).unwrap();
+        assert_eq!(public, keypair.public());
+    }
+
+    #[test]
+    fn malformed_encodings_are_rejected() {
+        let bytes = Keypair::generate().public().to_bytes();
+
+        assert!(PublicKey::try_from_bytes(&[]).is_err());
+        assert!(PublicKey::try_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(PublicKey::try_from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Self-checking identity files wrapping protobuf encoded private keys.
+//!
+//! ```text
+//! magic "LP2PKEY\0" | version (1 byte) | key type tag (1 byte)
+//!     | payload length (4 bytes, big-endian) | protobuf payload | SHA-256 of all of the above
+//! ```
+
+use crate::{error::DecodingError, KeyType};
+use sha2::{Digest as _, Sha256};
+use zeroize::Zeroizing;
+
+const MAGIC: &[u8; 8] = b"LP2PKEY\0";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 1 + 4;
+const CHECKSUM_LEN: usize = 32;
+
+/// Wrap a protobuf encoded private key of the given type in an identity file.
+#[cfg_attr(
+    not(any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn encode(key_type: KeyType, protobuf: &[u8]) -> Zeroizing<Vec<u8>> {
+    let payload_len = u32::try_from(protobuf.len()).expect("private keys are far below 4 GiB");
+
+    let mut file = Zeroizing::new(Vec::with_capacity(
+        HEADER_LEN + protobuf.len() + CHECKSUM_LEN,
+    ));
+    file.extend_from_slice(MAGIC);
+    file.push(VERSION);
+    file.push(key_type.to_protobuf_tag() as u8);
+    file.extend_from_slice(&payload_len.to_be_bytes());
+    file.extend_from_slice(protobuf);
+    let checksum = Sha256::digest(&*file);
+    file.extend_from_slice(&checksum);
+    file
+}
+
+/// Check an identity file produced by [`encode`], returning the declared key type and
+/// the protobuf encoding it wraps.
+///
+/// The version is checked before the checksum, so files written by a newer format
+/// are reported as such rather than as corrupt.
+pub(crate) fn decode(file: &[u8]) -> Result<(KeyType, Zeroizing<Vec<u8>>), DecodingError> {
+    if file.len() < HEADER_LEN + CHECKSUM_LEN || !file.starts_with(MAGIC) {
+        return Err(DecodingError::new("not a libp2p identity file".to_string()));
+    }
+
+    let version = file[MAGIC.len()];
+    if version != VERSION {
+        return Err(DecodingError::new(format!(
+            "unsupported identity file version {version}, expected {VERSION}"
+        )));
+    }
+
+    let (body, checksum) = file.split_at(file.len() - CHECKSUM_LEN);
+    if Sha256::digest(body).as_slice() != checksum {
+        return Err(DecodingError::new(
+            "identity file checksum mismatch, the file is corrupted".to_string(),
+        ));
+    }
+
+    let tag = file[MAGIC.len() + 1];
+    let key_type = KeyType::from_protobuf_tag(tag.into())
+        .ok_or_else(|| DecodingError::new(format!("unknown key type {tag} in identity file")))?;
+
+    let len_bytes: [u8; 4] = body[HEADER_LEN - 4..HEADER_LEN]
+        .try_into()
+        .expect("slice of 4 bytes");
+    let payload = &body[HEADER_LEN..];
+    if payload.len() != u32::from_be_bytes(len_bytes) as usize {
+        return Err(DecodingError::new(
+            "identity file payload length does not match its header".to_string(),
+        ));
+    }
+
+    Ok((key_type, Zeroizing::new(payload.to_vec())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_then_decode() {
+        let data = (0..=255u8).collect::<Vec<_>>();
+        let file = encode(KeyType::Dilithium, &data);
+
+        let (key_type, decoded) = decode(&file).unwrap();
+        assert_eq!(key_type, KeyType::Dilithium);
+        assert_eq!(*decoded, data);
+    }
+
+    #[test]
+    fn version_mismatch_is_rejected() {
+        let mut file = encode(KeyType::Dilithium, &[1, 2, 3]);
+        file[MAGIC.len()] = VERSION + 1;
+
+        let err = decode(&file).unwrap_err();
+        assert!(err.to_string().contains("version 2"), "{err}");
+    }
+
+    #[test]
+    fn corruption_is_rejected() {
+        let file = encode(KeyType::Dilithium, &[7; 100]);
+
+        for i in 0..file.len() {
+            let mut corrupted = file.clone();
+            corrupted[i] ^= 0x10;
+            assert!(decode(&corrupted).is_err(), "flipped bit in byte {i}");
+        }
+        assert!(decode(&file[..file.len() - 1]).is_err());
+        assert!(decode(&[]).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Proofs that a peer controls the key behind its [`PeerId`](crate::PeerId).
+
+use alloc::vec::Vec;
+
+/// The signing context of identity proofs, passed to [`Keypair::sign_with_context`].
+///
+/// The challenge is picked by the remote peer. Without a context of its own, a verifier
+/// could send as its challenge a message the prover would otherwise sign, say a signed
+/// envelope payload, and keep the proof as the prover's signature over it.
+///
+/// [`Keypair::sign_with_context`]: crate::Keypair::sign_with_context
+pub(crate) const CONTEXT: &[u8] = b"libp2p-pqc-identity-proof";
+
+/// A signature over a challenge, produced by [`Keypair::prove_identity`] and checked
+/// by [`PublicKey::verify_identity_proof`].
+///
+/// [`Keypair::prove_identity`]: crate::Keypair::prove_identity
+/// [`PublicKey::verify_identity_proof`]: crate::PublicKey::verify_identity_proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentityProof {
+    signature: Vec<u8>,
+}
+
+impl IdentityProof {
+    /// Wrap a proof received from a peer.
+    pub fn from_bytes(signature: Vec<u8>) -> Self {
+        IdentityProof { signature }
+    }
+
+    /// The bytes to send to the verifier.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /// Consume the proof, returning its bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.signature
+    }
+}
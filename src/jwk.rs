@@ -0,0 +1,214 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! JSON Web Key (RFC 7517) encoding of public keys.
+//!
+//! Ed25519 keys use the `OKP` key type of RFC 8037. Dilithium has no registered JWK
+//! representation yet, so it uses a custom `PQK` key type naming the ML-DSA parameter
+//! set in `alg`:
+//!
+//! ```text
+//! { "kty": "PQK", "alg": "ML-DSA-87", "x": "<base64url public key>" }
+//! ```
+
+#[cfg(feature = "dilithium")]
+use crate::dilithium::{self, DilithiumLevel};
+#[cfg(feature = "ed25519")]
+use crate::ed25519;
+#[cfg(any(feature = "dilithium", feature = "ed25519"))]
+use crate::keypair::PublicKeyInner;
+use crate::{
+    error::{DecodingError, EncodingError},
+    keypair::PublicKey,
+    KeyType,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+#[cfg(any(feature = "dilithium", feature = "ed25519"))]
+use serde_json::json;
+use serde_json::{Map, Value};
+
+const ED25519_CURVE: &str = "Ed25519";
+const ED25519_ALG: &str = "EdDSA";
+
+/// Encode a public key as a JWK.
+pub(crate) fn encode(key: &PublicKey) -> Result<Value, EncodingError> {
+    match key.publickey {
+        #[cfg(feature = "ed25519")]
+        PublicKeyInner::Ed25519(ref pk) => Ok(json!({
+            "kty": "OKP",
+            "crv": ED25519_CURVE,
+            "alg": ED25519_ALG,
+            "x": URL_SAFE_NO_PAD.encode(pk.to_bytes()),
+        })),
+        #[cfg(feature = "dilithium")]
+        PublicKeyInner::Dilithium(ref pk) => Ok(json!({
+            "kty": "PQK",
+            "alg": pk.level().to_string(),
+            "x": URL_SAFE_NO_PAD.encode(pk.to_bytes()),
+        })),
+        #[allow(unreachable_patterns)]
+        _ => {
+            let key_type = key.key_type();
+            Err(if key_type.is_post_quantum() {
+                EncodingError::UnsupportedForPostQuantum(key_type)
+            } else {
+                EncodingError::UnsupportedKeyType(key_type)
+            })
+        }
+    }
+}
+
+/// Decode a JWK produced by [`encode`], rejecting unknown or inconsistent `kty`, `crv`
+/// and `alg` values.
+#[allow(unused_variables)]
+pub(crate) fn decode(jwk: &Value) -> Result<PublicKey, DecodingError> {
+    let jwk = jwk
+        .as_object()
+        .ok_or_else(|| DecodingError::new("JWK must be a JSON object".to_string()))?;
+
+    match required(jwk, "kty")? {
+        "OKP" => {
+            let crv = required(jwk, "crv")?;
+            if crv != ED25519_CURVE {
+                return Err(DecodingError::new(format!("unsupported JWK curve `{crv}`")));
+            }
+            if let Some(alg) = optional(jwk, "alg")? {
+                if alg != ED25519_ALG {
+                    return Err(DecodingError::new(format!(
+                        "JWK `alg` `{alg}` does not match curve `{crv}`"
+                    )));
+                }
+            }
+            let x = key_material(jwk)?;
+
+            #[cfg(feature = "ed25519")]
+            return ed25519::PublicKey::try_from_bytes(&x).map(PublicKey::from);
+            #[allow(unreachable_code)]
+            Err(DecodingError::disabled_key_type(KeyType::Ed25519))
+        }
+        "PQK" => {
+            if jwk.contains_key("crv") {
+                return Err(DecodingError::new(
+                    "JWK of key type `PQK` must not have a `crv`".to_string(),
+                ));
+            }
+            let alg = required(jwk, "alg")?;
+            let x = key_material(jwk)?;
+
+            #[cfg(feature = "dilithium")]
+            {
+                let level = DilithiumLevel::ALL
+                    .into_iter()
+                    .find(|level| level.to_string() == alg)
+                    .ok_or_else(|| DecodingError::new(format!("unsupported JWK `alg` `{alg}`")))?;
+                if x.len() != level.public_key_len() {
+                    return Err(DecodingError::new(format!(
+                        "JWK `alg` is `{alg}` but the key is {} bytes long",
+                        x.len()
+                    )));
+                }
+                return dilithium::PublicKey::try_from_bytes(&x).map(PublicKey::from);
+            }
+            #[allow(unreachable_code)]
+            Err(DecodingError::disabled_key_type(KeyType::Dilithium))
+        }
+        other => Err(DecodingError::new(format!(
+            "unsupported JWK `kty` `{other}`"
+        ))),
+    }
+}
+
+fn required<'a>(jwk: &'a Map<String, Value>, name: &str) -> Result<&'a str, DecodingError> {
+    optional(jwk, name)?
+        .ok_or_else(|| DecodingError::new(format!("JWK is missing the `{name}` member")))
+}
+
+fn optional<'a>(jwk: &'a Map<String, Value>, name: &str) -> Result<Option<&'a str>, DecodingError> {
+    jwk.get(name)
+        .map(|value| {
+            value
+                .as_str()
+                .ok_or_else(|| DecodingError::new(format!("JWK member `{name}` must be a string")))
+        })
+        .transpose()
+}
+
+fn key_material(jwk: &Map<String, Value>) -> Result<Vec<u8>, DecodingError> {
+    URL_SAFE_NO_PAD
+        .decode(required(jwk, "x")?)
+        .map_err(|e| DecodingError::failed_to_parse("JWK `x` member", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Keypair;
+    use serde_json::json;
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn dilithium_roundtrip() {
+        for level in DilithiumLevel::ALL {
+            let public = Keypair::generate_dilithium_level(level).public();
+            let jwk = encode(&public).unwrap();
+
+            assert_eq!(jwk["kty"], "PQK");
+            assert_eq!(jwk["alg"], level.to_string());
+            assert_eq!(decode(&jwk).unwrap(), public);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ed25519")]
+    fn ed25519_roundtrip() {
+        let public = Keypair::generate_ed25519().public();
+        let jwk = encode(&public).unwrap();
+
+        assert_eq!(jwk["kty"], "OKP");
+        assert_eq!(jwk["crv"], "Ed25519");
+        assert_eq!(decode(&jwk).unwrap(), public);
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn mismatched_alg_is_rejected() {
+        let mut jwk = encode(&Keypair::generate_dilithium().public()).unwrap();
+        jwk["alg"] = "ML-DSA-65".into();
+        assert!(decode(&jwk).is_err());
+
+        jwk["alg"] = "EdDSA".into();
+        assert!(decode(&jwk).is_err());
+
+        jwk["alg"] = "ML-DSA-87".into();
+        jwk["crv"] = "Ed25519".into();
+        assert!(decode(&jwk).is_err());
+    }
+
+    #[test]
+    fn malformed_jwk_is_rejected() {
+        assert!(decode(&json!("PQK")).is_err());
+        assert!(decode(&json!({ "alg": "ML-DSA-87", "x": "AAAA" })).is_err());
+        assert!(decode(&json!({ "kty": "RSA", "n": "AAAA", "e": "AQAB" })).is_err());
+        assert!(decode(&json!({ "kty": "OKP", "crv": "X25519", "x": "AAAA" })).is_err());
+        assert!(decode(&json!({ "kty": "OKP", "crv": "Ed25519", "alg": "ML-DSA-87" })).is_err());
+        assert!(decode(&json!({ "kty": "PQK", "alg": "ML-DSA-87", "x": "!!" })).is_err());
+        assert!(decode(&json!({ "kty": "PQK", "alg": 87, "x": "AAAA" })).is_err());
+    }
+}
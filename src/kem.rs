@@ -0,0 +1,362 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Key encapsulation with Kyber (ML-KEM).
+//!
+//! Signatures authenticate a peer but do not agree on a key. A KEM lets a peer send a
+//! fresh shared secret to the holder of a public key, e.g. inside a post-quantum Noise
+//! handshake. KEM keys cannot sign, so they are kept apart from [`crate::Keypair`] and
+//! [`crate::KeyType`].
+//!
+//! ```
+//! use libp2p_identity::kem::Kyber768;
+//!
+//! let keypair = Kyber768::generate_kem_keypair();
+//! let (ciphertext, sent) = Kyber768::encapsulate(keypair.public());
+//! let received = Kyber768::decapsulate(keypair.secret(), &ciphertext);
+//! assert_eq!(sent, received);
+//! ```
+
+use super::error::DecodingError;
+use core::fmt;
+use ml_kem::{
+    kem::{Decapsulate as _, DecapsulationKey, Encapsulate as _, EncapsulationKey},
+    EncodedSizeUser, KemCore, MlKem768, MlKem768Params,
+};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Length in bytes of an encoded Kyber768 public (encapsulation) key.
+pub const PUBLIC_KEY_LEN: usize = 1184;
+
+/// Length in bytes of an encoded Kyber768 secret (decapsulation) key.
+pub const SECRET_KEY_LEN: usize = 2400;
+
+/// Length in bytes of a Kyber768 ciphertext.
+pub const CIPHERTEXT_LEN: usize = 1088;
+
+/// Length in bytes of a shared secret.
+pub const SHARED_SECRET_LEN: usize = 32;
+
+/// Kyber768 (ML-KEM-768), at NIST security category 3.
+#[derive(Debug, Clone, Copy)]
+pub struct Kyber768;
+
+impl Kyber768 {
+    /// Generate a new random Kyber768 keypair.
+    pub fn generate_kem_keypair() -> Keypair {
+        let (secret, public) = MlKem768::generate(&mut rand::thread_rng());
+        Keypair {
+            secret: SecretKey(secret),
+            public: PublicKey(public),
+        }
+    }
+
+    /// Create a fresh shared secret and encapsulate it to `public`.
+    ///
+    /// The ciphertext is sent to the holder of the secret key, who recovers the same
+    /// shared secret with [`Kyber768::decapsulate`].
+    pub fn encapsulate(public: &PublicKey) -> (Ciphertext, SharedSecret) {
+        let (ciphertext, shared) = public
+            .0
+            .encapsulate(&mut rand::rngs::OsRng)
+            .expect("ML-KEM encapsulation is infallible");
+        (Ciphertext(ciphertext.to_vec()), SharedSecret(shared.into()))
+    }
+
+    /// Recover the shared secret encapsulated in `ciphertext`.
+    ///
+    /// ML-KEM uses implicit rejection: a tampered ciphertext does not fail, but
+    /// yields an unrelated shared secret, so the peers' keys simply won't match.
+    pub fn decapsulate(secret: &SecretKey, ciphertext: &Ciphertext) -> SharedSecret {
+        let ciphertext = ciphertext
+            .0
+            .as_slice()
+            .try_into()
+            .expect("ciphertext length is validated on construction");
+        let shared = secret
+            .0
+            .decapsulate(&ciphertext)
+            .expect("ML-KEM decapsulation is infallible");
+        SharedSecret(shared.into())
+    }
+}
+
+/// A Kyber768 keypair.
+#[derive(Clone)]
+pub struct Keypair {
+    secret: SecretKey,
+    public: PublicKey,
+}
+
+impl Keypair {
+    /// Get the public key of this keypair.
+    pub fn public(&self) -> &PublicKey {
+        &self.public
+    }
+
+    /// Get the secret key of this keypair.
+    pub fn secret(&self) -> &SecretKey {
+        &self.secret
+    }
+}
+
+impl fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keypair")
+            .field("public", &self.public)
+            .finish()
+    }
+}
+
+/// Promote a Kyber768 secret key into a keypair.
+impl From<SecretKey> for Keypair {
+    fn from(secret: SecretKey) -> Keypair {
+        let public = PublicKey(secret.0.encapsulation_key().clone());
+        Keypair { secret, public }
+    }
+}
+
+/// A Kyber768 public (encapsulation) key.
+#[derive(Clone, PartialEq)]
+pub struct PublicKey(EncapsulationKey<MlKem768Params>);
+
+impl Eq for PublicKey {}
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PublicKey: ")?;
+        for byte in self.0.as_bytes() {
+            write!(f, "{byte:x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PublicKey {
+    /// Convert the public key to its raw byte representation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.as_bytes().to_vec()
+    }
+
+    /// Try to parse a public key from the raw bytes produced by [`PublicKey::to_bytes`].
+    ///
+    /// Keys with coefficients that are not reduced modulo `q` are rejected, as
+    /// required by FIPS 203.
+    pub fn try_from_bytes(k: &[u8]) -> Result<PublicKey, DecodingError> {
+        let encoded = k.try_into().map_err(|_| {
+            DecodingError::new(format!(
+                "failed to parse Kyber768 public key: expected {PUBLIC_KEY_LEN} bytes, got {}",
+                k.len()
+            ))
+        })?;
+        let key = EncapsulationKey::from_bytes(encoded);
+        if key.as_bytes().as_slice() != k {
+            return Err(DecodingError::new(
+                "failed to parse Kyber768 public key: invalid encoding".to_string(),
+            ));
+        }
+        Ok(PublicKey(key))
+    }
+}
+
+/// A Kyber768 secret (decapsulation) key.
+///
+/// The key material is wiped when the secret key is dropped.
+#[derive(Clone)]
+pub struct SecretKey(DecapsulationKey<MlKem768Params>);
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretKey")
+    }
+}
+
+impl zeroize::ZeroizeOnDrop for SecretKey {}
+
+impl SecretKey {
+    /// Convert the secret key to its raw byte representation.
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut encoded = self.0.as_bytes();
+        let bytes = Zeroizing::new(encoded.to_vec());
+        encoded.zeroize();
+        bytes
+    }
+
+    /// Try to parse a secret key from the raw bytes produced by [`SecretKey::to_bytes`].
+    ///
+    /// The embedded public key and its hash are checked, as required by FIPS 203.
+    pub fn try_from_bytes(sk: &[u8]) -> Result<SecretKey, DecodingError> {
+        let encoded = sk.try_into().map_err(|_| {
+            DecodingError::new(format!(
+                "failed to parse Kyber768 secret key: expected {SECRET_KEY_LEN} bytes, got {}",
+                sk.len()
+            ))
+        })?;
+        let key = SecretKey(DecapsulationKey::from_bytes(encoded));
+        // Re-deriving the public key recomputes its hash, which the decoder copies as is.
+        let public = key.0.encapsulation_key();
+        if key.to_bytes().as_slice() != sk
+            || *public != EncapsulationKey::from_bytes(&public.as_bytes())
+        {
+            return Err(DecodingError::new(
+                "failed to parse Kyber768 secret key: invalid encoding".to_string(),
+            ));
+        }
+        Ok(key)
+    }
+}
+
+/// A Kyber768 ciphertext, carrying an encapsulated shared secret.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Ciphertext(Vec<u8>);
+
+impl fmt::Debug for Ciphertext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Ciphertext: ")?;
+        for byte in &self.0 {
+            write!(f, "{byte:x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Ciphertext {
+    /// Get the raw bytes of the ciphertext.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Try to parse a ciphertext received from a peer.
+    pub fn try_from_bytes(ct: &[u8]) -> Result<Ciphertext, DecodingError> {
+        if ct.len() != CIPHERTEXT_LEN {
+            return Err(DecodingError::new(format!(
+                "failed to parse Kyber768 ciphertext: expected {CIPHERTEXT_LEN} bytes, got {}",
+                ct.len()
+            )));
+        }
+        Ok(Ciphertext(ct.to_vec()))
+    }
+}
+
+/// A shared secret agreed through [`Kyber768`].
+///
+/// Comparison is constant time, and the secret is wiped when dropped.
+#[derive(Clone)]
+pub struct SharedSecret([u8; SHARED_SECRET_LEN]);
+
+impl SharedSecret {
+    /// Get the raw bytes of the shared secret.
+    pub fn as_bytes(&self) -> &[u8; SHARED_SECRET_LEN] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SharedSecret")
+    }
+}
+
+impl PartialEq for SharedSecret {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+}
+
+impl Eq for SharedSecret {}
+
+/// Wipe the shared secret before it is freed.
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl zeroize::ZeroizeOnDrop for SharedSecret {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encapsulate_and_decapsulate_agree() {
+        let keypair = Kyber768::generate_kem_keypair();
+
+        let (ciphertext, sent) = Kyber768::encapsulate(keypair.public());
+        let received = Kyber768::decapsulate(keypair.secret(), &ciphertext);
+
+        assert_eq!(sent, received);
+        assert_eq!(ciphertext.as_bytes().len(), CIPHERTEXT_LEN);
+
+        let (_, other) = Kyber768::encapsulate(keypair.public());
+        assert_ne!(sent, other);
+    }
+
+    #[test]
+    fn tampered_ciphertext_yields_a_different_secret() {
+        let keypair = Kyber768::generate_kem_keypair();
+        let (ciphertext, sent) = Kyber768::encapsulate(keypair.public());
+
+        let mut tampered = ciphertext.as_bytes().to_vec();
+        tampered[0] ^= 1;
+        let tampered = Ciphertext::try_from_bytes(&tampered).unwrap();
+
+        assert_ne!(Kyber768::decapsulate(keypair.secret(), &tampered), sent);
+    }
+
+    #[test]
+    fn wrong_secret_key_yields_a_different_secret() {
+        let keypair = Kyber768::generate_kem_keypair();
+        let (ciphertext, sent) = Kyber768::encapsulate(keypair.public());
+
+        let other = Kyber768::generate_kem_keypair();
+        assert_ne!(Kyber768::decapsulate(other.secret(), &ciphertext), sent);
+    }
+
+    #[test]
+    fn keys_encode_decode() {
+        let keypair = Kyber768::generate_kem_keypair();
+
+        let public = PublicKey::try_from_bytes(&keypair.public().to_bytes()).unwrap();
+        let secret = SecretKey::try_from_bytes(&keypair.secret().to_bytes()).unwrap();
+        assert_eq!(&public, keypair.public());
+        assert_eq!(keypair.public().to_bytes().len(), PUBLIC_KEY_LEN);
+        assert_eq!(keypair.secret().to_bytes().len(), SECRET_KEY_LEN);
+
+        let restored = Keypair::from(secret);
+        let (ciphertext, sent) = Kyber768::encapsulate(&public);
+        assert_eq!(Kyber768::decapsulate(restored.secret(), &ciphertext), sent);
+    }
+
+    #[test]
+    fn invalid_encodings_are_rejected() {
+        assert!(PublicKey::try_from_bytes(&[0u8; 32]).is_err());
+        assert!(PublicKey::try_from_bytes(&[0xffu8; PUBLIC_KEY_LEN]).is_err());
+        assert!(SecretKey::try_from_bytes(&[0u8; 32]).is_err());
+        assert!(Ciphertext::try_from_bytes(&[0u8; CIPHERTEXT_LEN - 1]).is_err());
+
+        // A secret key whose embedded public key hash does not match.
+        let keypair = Kyber768::generate_kem_keypair();
+        let mut secret = keypair.secret().to_bytes();
+        secret[SECRET_KEY_LEN - 2 * SHARED_SECRET_LEN] ^= 1;
+        assert!(SecretKey::try_from_bytes(&secret).is_err());
+    }
+}
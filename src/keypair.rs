@@ -18,50 +18,102 @@
 // FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
-#[cfg(feature = "ecdsa")]
-use crate::ecdsa;
 #[cfg(any(
     feature = "ecdsa",
     feature = "secp256k1",
     feature = "ed25519",
     feature = "rsa",
-    feature = "dilithium"
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
 ))]
+#[cfg(feature = "dilithium")]
+use crate::dilithium::{self, DilithiumLevel};
+#[cfg(feature = "ecdsa")]
+use crate::ecdsa;
 #[cfg(feature = "ed25519")]
 use crate::ed25519;
+#[cfg(feature = "dilithium")]
+use crate::error::DeriveError;
+#[cfg(feature = "dilithium")]
+use crate::error::GenerationError;
 #[cfg(any(
     feature = "ecdsa",
     feature = "secp256k1",
     feature = "ed25519",
     feature = "rsa",
-    feature = "dilithium"
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
 ))]
 use crate::error::OtherVariantError;
+#[cfg(feature = "falcon")]
+use crate::falcon;
+#[cfg(all(feature = "dilithium", feature = "ed25519"))]
+use crate::hybrid;
 #[cfg(any(
     feature = "ecdsa",
     feature = "secp256k1",
     feature = "ed25519",
     feature = "rsa",
-    feature = "dilithium"
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
 ))]
 use crate::proto;
 #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
 use crate::rsa;
 #[cfg(feature = "secp256k1")]
 use crate::secp256k1;
+#[cfg(feature = "sphincs-plus")]
+use crate::sphincs_plus;
 use crate::{
-    error::{DecodingError, SigningError},
-    KeyType,
+    error::{DecodingError, EncodingError, SigningError, ValidationError, VerificationError},
+    KeyType, Signature,
 };
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
+))]
+use alloc::vec;
+use alloc::{string::ToString, vec::Vec};
+#[cfg(feature = "dilithium")]
 use qp_rusty_crystals_dilithium::ml_dsa_87;
 #[cfg(any(
     feature = "ecdsa",
     feature = "secp256k1",
     feature = "ed25519",
     feature = "rsa",
-    feature = "dilithium"
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
+))]
+use quick_protobuf::{BytesReader, BytesWriter, Writer};
+#[cfg(all(
+    feature = "rand",
+    any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
+    )
 ))]
-use quick_protobuf::{BytesReader, Writer};
+use rand::{CryptoRng, RngCore};
+
+/// HKDF salt separating child keys from secrets obtained through [`Keypair::derive_secret`].
+#[cfg(feature = "dilithium")]
+const DERIVE_CHILD_SALT: &[u8] = b"libp2p-pqc-derive-child";
+
+/// HKDF salt separating per-protocol subkeys from child keys and derived secrets.
+#[cfg(feature = "dilithium")]
+const DERIVE_SUBKEY_SALT: &[u8] = b"libp2p-pqc-derive-subkey";
 
 /// Identity keypair of a node.
 ///
@@ -79,9 +131,36 @@ use quick_protobuf::{BytesReader, Writer};
 /// let mut bytes = std::fs::read("private.pk8").unwrap();
 /// let keypair = Keypair::rsa_from_pkcs8(&mut bytes);
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Keypair {
     keypair: KeyPairInner,
+    /// Fingerprint of the key this one supersedes, see [`Keypair::set_supersedes`].
+    previous_key_fingerprint: Option<Vec<u8>>,
+}
+
+/// Prints only the key type and, with the `peerid` feature, the
+/// [fingerprint](PublicKey::fingerprint) of the public key, so that logging a keypair
+/// never leaks key material.
+impl core::fmt::Debug for Keypair {
+    #[cfg_attr(
+        not(any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "rsa",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )),
+        allow(unreachable_code)
+    )]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut debug = f.debug_struct("Keypair");
+        debug.field("key_type", &self.key_type());
+        #[cfg(feature = "peerid")]
+        debug.field("fingerprint", &self.public().fingerprint());
+        debug.finish_non_exhaustive()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -99,47 +178,347 @@ enum KeyPairInner {
     /// An ECDSA keypair.
     #[cfg(feature = "ecdsa")]
     Ecdsa(ecdsa::Keypair),
+    /// A Dilithium keypair.
     #[cfg(feature = "dilithium")]
-    Dilithium(ml_dsa_87::Keypair),
+    Dilithium(dilithium::Keypair),
+    /// A hybrid Dilithium and Ed25519 keypair.
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    HybridDilithiumEd25519(hybrid::Keypair),
+    /// A SPHINCS+ keypair.
+    #[cfg(feature = "sphincs-plus")]
+    SphincsPlus(sphincs_plus::Keypair),
+    #[cfg(feature = "falcon")]
+    Falcon(falcon::Keypair),
 }
 
 impl Keypair {
+    /// Generate a new random keypair of the given type, e.g. one picked from configuration
+    /// at runtime.
+    ///
+    /// Dilithium keys use the default [`DilithiumLevel`]. Returns
+    /// [`SigningError::UnsupportedKeyType`] for key types not enabled in this build and
+    /// for RSA, which has no key generation.
+    #[cfg(feature = "rand")]
+    pub fn generate(key_type: KeyType) -> Result<Keypair, SigningError> {
+        crate::KeypairBuilder::new(key_type).build()
+    }
+
     /// Generate a new Ed25519 keypair.
     #[cfg(all(feature = "ed25519", feature = "rand"))]
     pub fn generate_ed25519() -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::Ed25519,
+            crate::metrics::CryptoOperation::Generate,
+        );
         log::info!(target: "libp2p-identity", "🔑 Generating Ed25519 keypair");
         Keypair {
             keypair: KeyPairInner::Ed25519(ed25519::Keypair::generate()),
+            previous_key_fingerprint: None,
+        }
+    }
+
+    /// Generate a new Ed25519 keypair using the given RNG.
+    #[cfg(all(feature = "ed25519", feature = "rand"))]
+    pub fn generate_ed25519_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::Ed25519,
+            crate::metrics::CryptoOperation::Generate,
+        );
+        log::info!(target: "libp2p-identity", "🔑 Generating Ed25519 keypair");
+        Keypair {
+            keypair: KeyPairInner::Ed25519(ed25519::Keypair::generate_with_rng(rng)),
+            previous_key_fingerprint: None,
         }
     }
 
     /// Generate a new Secp256k1 keypair.
     #[cfg(all(feature = "secp256k1", feature = "rand"))]
     pub fn generate_secp256k1() -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::Secp256k1,
+            crate::metrics::CryptoOperation::Generate,
+        );
         log::info!(target: "libp2p-identity", "🔐 Generating Secp256k1 keypair");
         Keypair {
             keypair: KeyPairInner::Secp256k1(secp256k1::Keypair::generate()),
+            previous_key_fingerprint: None,
+        }
+    }
+
+    /// Generate a new Secp256k1 keypair using the given RNG.
+    #[cfg(all(feature = "secp256k1", feature = "rand"))]
+    pub fn generate_secp256k1_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::Secp256k1,
+            crate::metrics::CryptoOperation::Generate,
+        );
+        log::info!(target: "libp2p-identity", "🔐 Generating Secp256k1 keypair");
+        Keypair {
+            keypair: KeyPairInner::Secp256k1(secp256k1::Keypair::generate_with_rng(rng)),
+            previous_key_fingerprint: None,
         }
     }
 
     /// Generate a new ECDSA keypair.
     #[cfg(all(feature = "ecdsa", feature = "rand"))]
     pub fn generate_ecdsa() -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer =
+            crate::metrics::Timer::start(KeyType::Ecdsa, crate::metrics::CryptoOperation::Generate);
         log::info!(target: "libp2p-identity", "🔒 Generating ECDSA keypair");
         Keypair {
             keypair: KeyPairInner::Ecdsa(ecdsa::Keypair::generate()),
+            previous_key_fingerprint: None,
+        }
+    }
+
+    /// Generate a new ECDSA keypair using the given RNG.
+    #[cfg(all(feature = "ecdsa", feature = "rand"))]
+    pub fn generate_ecdsa_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer =
+            crate::metrics::Timer::start(KeyType::Ecdsa, crate::metrics::CryptoOperation::Generate);
+        log::info!(target: "libp2p-identity", "🔒 Generating ECDSA keypair");
+        Keypair {
+            keypair: KeyPairInner::Ecdsa(ecdsa::Keypair::generate_with_rng(rng)),
+            previous_key_fingerprint: None,
         }
     }
 
+    /// Generate a new Dilithium keypair at the default [`DilithiumLevel`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system has no entropy, see
+    /// [`Keypair::try_generate_dilithium`].
     #[cfg(feature = "dilithium")]
     pub fn generate_dilithium() -> Keypair {
-        log::info!(target: "libp2p-identity", "🛡️  Generating Dilithium (Post-Quantum) keypair");
-        // Use random entropy for std environments
-        use rand::RngCore;
-        let mut entropy = [0u8; 32];
-        rand::thread_rng().fill_bytes(&mut entropy);
+        Keypair::try_generate_dilithium().expect("the operating system to provide entropy")
+    }
+
+    /// Generate a new Dilithium keypair at the default [`DilithiumLevel`], returning
+    /// [`GenerationError::EntropyFailure`] instead of panicking if the operating system
+    /// has no entropy yet, e.g. on embedded targets at boot.
+    ///
+    /// The seed is read from [`OsRng`](rand::rngs::OsRng) directly: the thread-local
+    /// RNG panics when it cannot seed itself, before any error could be returned.
+    #[cfg(feature = "dilithium")]
+    pub fn try_generate_dilithium() -> Result<Keypair, GenerationError> {
+        Keypair::try_generate_dilithium_with_rng(&mut rand::rngs::OsRng)
+    }
+
+    /// Like [`Keypair::try_generate_dilithium`], but seeded from the given RNG.
+    #[cfg(feature = "dilithium")]
+    pub fn try_generate_dilithium_with_rng<R: RngCore + CryptoRng>(
+        rng: &mut R,
+    ) -> Result<Keypair, GenerationError> {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::Dilithium,
+            crate::metrics::CryptoOperation::Generate,
+        );
+        let level = DilithiumLevel::default();
+        log::info!(target: "libp2p-identity", "🛡️  Generating Dilithium (Post-Quantum) {} keypair", level);
+        let keypair = dilithium::Keypair::try_generate_with_rng(level, rng).map_err(|e| {
+            log::debug!(target: "libp2p-identity", "🛡️ RNG failed during Dilithium key generation: {e}");
+            GenerationError::EntropyFailure
+        })?;
+
+        Ok(Keypair {
+            keypair: KeyPairInner::Dilithium(keypair),
+            previous_key_fingerprint: None,
+        })
+    }
+
+    /// Generate a new Dilithium keypair at the given [`DilithiumLevel`].
+    #[cfg(feature = "dilithium")]
+    pub fn generate_dilithium_level(level: DilithiumLevel) -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::Dilithium,
+            crate::metrics::CryptoOperation::Generate,
+        );
+        log::info!(target: "libp2p-identity", "🛡️  Generating Dilithium (Post-Quantum) {} keypair", level);
+        Keypair {
+            keypair: KeyPairInner::Dilithium(dilithium::Keypair::generate(level)),
+            previous_key_fingerprint: None,
+        }
+    }
+
+    /// Generate a new Dilithium keypair at the default [`DilithiumLevel`], seeded
+    /// from the given RNG.
+    ///
+    /// Passing a seeded RNG makes the generated key, and therefore its [`PeerId`],
+    /// reproducible.
+    ///
+    /// In the browser (`wasm32-unknown-unknown`), enable the `wasm` feature so that
+    /// `rand::rngs::OsRng`, which [`Keypair::generate_dilithium`] also uses, draws
+    /// from `crypto.getRandomValues`, or pass an
+    /// RNG seeded from another entropy source:
+    ///
+    /// ```
+    /// # use libp2p_identity::Keypair;
+    /// let keypair = Keypair::generate_dilithium_with_rng(&mut rand::rngs::OsRng);
+    /// ```
+    ///
+    /// [`PeerId`]: crate::PeerId
+    #[cfg(feature = "dilithium")]
+    pub fn generate_dilithium_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Keypair {
+        Keypair::generate_dilithium_level_with_rng(DilithiumLevel::default(), rng)
+    }
+
+    /// Generate a new Dilithium keypair at the given [`DilithiumLevel`], seeded from
+    /// the given RNG.
+    #[cfg(feature = "dilithium")]
+    pub fn generate_dilithium_level_with_rng<R: RngCore + CryptoRng>(
+        level: DilithiumLevel,
+        rng: &mut R,
+    ) -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::Dilithium,
+            crate::metrics::CryptoOperation::Generate,
+        );
+        log::info!(target: "libp2p-identity", "🛡️  Generating Dilithium (Post-Quantum) {} keypair", level);
+        Keypair {
+            keypair: KeyPairInner::Dilithium(dilithium::Keypair::generate_with_rng(level, rng)),
+            previous_key_fingerprint: None,
+        }
+    }
+
+    /// Generate `n` independent Dilithium keypairs at the default [`DilithiumLevel`].
+    ///
+    /// With the `parallel` feature the keys are generated on the `rayon` thread pool,
+    /// otherwise one after the other. Either way every key is seeded from the
+    /// thread-local RNG of the thread generating it.
+    #[cfg(feature = "dilithium")]
+    pub fn generate_dilithium_batch(n: usize) -> Vec<Keypair> {
+        let level = DilithiumLevel::default();
+        log::info!(target: "libp2p-identity", "🛡️  Generating {} Dilithium (Post-Quantum) {} keypairs", n, level);
+        let generate = |_| {
+            #[cfg(feature = "metrics")]
+            let _timer = crate::metrics::Timer::start(
+                KeyType::Dilithium,
+                crate::metrics::CryptoOperation::Generate,
+            );
+            Keypair {
+                keypair: KeyPairInner::Dilithium(dilithium::Keypair::generate(level)),
+                previous_key_fingerprint: None,
+            }
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            (0..n).into_par_iter().map(generate).collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            (0..n).map(generate).collect()
+        }
+    }
+
+    /// Generate a new hybrid keypair whose signatures are only valid if both its
+    /// Dilithium and its Ed25519 component signatures are.
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    pub fn generate_hybrid_dilithium_ed25519() -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::HybridDilithiumEd25519,
+            crate::metrics::CryptoOperation::Generate,
+        );
+        log::info!(target: "libp2p-identity", "🛡️🔑 Generating hybrid Dilithium+Ed25519 keypair");
+        Keypair {
+            keypair: KeyPairInner::HybridDilithiumEd25519(hybrid::Keypair::generate()),
+            previous_key_fingerprint: None,
+        }
+    }
+
+    /// Generate a new hybrid Dilithium and Ed25519 keypair, drawing the randomness
+    /// for both components from the given RNG.
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    pub fn generate_hybrid_dilithium_ed25519_with_rng<R: RngCore + CryptoRng>(
+        rng: &mut R,
+    ) -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::HybridDilithiumEd25519,
+            crate::metrics::CryptoOperation::Generate,
+        );
+        log::info!(target: "libp2p-identity", "🛡️🔑 Generating hybrid Dilithium+Ed25519 keypair");
+        Keypair {
+            keypair: KeyPairInner::HybridDilithiumEd25519(hybrid::Keypair::generate_with_rng(rng)),
+            previous_key_fingerprint: None,
+        }
+    }
+
+    /// Generate a new SPHINCS+ keypair.
+    ///
+    /// SPHINCS+ only relies on the security of hash functions, at the cost of
+    /// signatures of about 49 KB that are also slow to produce.
+    #[cfg(feature = "sphincs-plus")]
+    pub fn generate_sphincs_plus() -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::SphincsPlus,
+            crate::metrics::CryptoOperation::Generate,
+        );
+        log::info!(target: "libp2p-identity", "🌳 Generating SPHINCS+ keypair (Post-Quantum)");
+        Keypair {
+            keypair: KeyPairInner::SphincsPlus(sphincs_plus::Keypair::generate()),
+            previous_key_fingerprint: None,
+        }
+    }
+
+    /// Generate a new SPHINCS+ keypair using the given RNG.
+    #[cfg(feature = "sphincs-plus")]
+    pub fn generate_sphincs_plus_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::SphincsPlus,
+            crate::metrics::CryptoOperation::Generate,
+        );
+        log::info!(target: "libp2p-identity", "🌳 Generating SPHINCS+ keypair (Post-Quantum)");
+        Keypair {
+            keypair: KeyPairInner::SphincsPlus(sphincs_plus::Keypair::generate_with_rng(rng)),
+            previous_key_fingerprint: None,
+        }
+    }
+
+    /// Generate a new Falcon keypair.
+    ///
+    /// Falcon signatures are much smaller than Dilithium ones, at the cost of
+    /// slower, floating-point heavy signing.
+    #[cfg(feature = "falcon")]
+    pub fn generate_falcon() -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::Falcon,
+            crate::metrics::CryptoOperation::Generate,
+        );
+        log::info!(target: "libp2p-identity", "🦅 Generating Falcon keypair (Post-Quantum)");
+        Keypair {
+            keypair: KeyPairInner::Falcon(falcon::Keypair::generate()),
+            previous_key_fingerprint: None,
+        }
+    }
+
+    /// Generate a new Falcon keypair using the given RNG.
+    #[cfg(feature = "falcon")]
+    pub fn generate_falcon_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Keypair {
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::Timer::start(
+            KeyType::Falcon,
+            crate::metrics::CryptoOperation::Generate,
+        );
+        log::info!(target: "libp2p-identity", "🦅 Generating Falcon keypair (Post-Quantum)");
         Keypair {
-            keypair: KeyPairInner::Dilithium(ml_dsa_87::Keypair::generate(Some(&entropy))),
+            keypair: KeyPairInner::Falcon(falcon::Keypair::generate_with_rng(rng)),
+            previous_key_fingerprint: None,
         }
     }
 
@@ -163,6 +542,101 @@ impl Keypair {
         self.try_into()
     }
 
+    #[cfg(feature = "dilithium")]
+    pub fn try_into_dilithium(self) -> Result<dilithium::Keypair, OtherVariantError> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "sphincs-plus")]
+    pub fn try_into_sphincs_plus(self) -> Result<sphincs_plus::Keypair, OtherVariantError> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "falcon")]
+    pub fn try_into_falcon(self) -> Result<falcon::Keypair, OtherVariantError> {
+        self.try_into()
+    }
+
+    /// Borrow the Ed25519 keypair, or `None` if this is another key type.
+    #[cfg(feature = "ed25519")]
+    pub fn as_ed25519(&self) -> Option<&ed25519::Keypair> {
+        match self.keypair {
+            KeyPairInner::Ed25519(ref pair) => Some(pair),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Borrow the Secp256k1 keypair, or `None` if this is another key type.
+    #[cfg(feature = "secp256k1")]
+    pub fn as_secp256k1(&self) -> Option<&secp256k1::Keypair> {
+        match self.keypair {
+            KeyPairInner::Secp256k1(ref pair) => Some(pair),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Borrow the RSA keypair, or `None` if this is another key type.
+    #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+    pub fn as_rsa(&self) -> Option<&rsa::Keypair> {
+        match self.keypair {
+            KeyPairInner::Rsa(ref pair) => Some(pair),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Borrow the ECDSA keypair, or `None` if this is another key type.
+    #[cfg(feature = "ecdsa")]
+    pub fn as_ecdsa(&self) -> Option<&ecdsa::Keypair> {
+        match self.keypair {
+            KeyPairInner::Ecdsa(ref pair) => Some(pair),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Borrow the Dilithium keypair, or `None` if this is another key type.
+    #[cfg(feature = "dilithium")]
+    pub fn as_dilithium(&self) -> Option<&dilithium::Keypair> {
+        match self.keypair {
+            KeyPairInner::Dilithium(ref pair) => Some(pair),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Borrow the hybrid Dilithium+Ed25519 keypair, or `None` if this is another key type.
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    pub fn as_hybrid_dilithium_ed25519(&self) -> Option<&hybrid::Keypair> {
+        match self.keypair {
+            KeyPairInner::HybridDilithiumEd25519(ref pair) => Some(pair),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Borrow the SPHINCS+ keypair, or `None` if this is another key type.
+    #[cfg(feature = "sphincs-plus")]
+    pub fn as_sphincs_plus(&self) -> Option<&sphincs_plus::Keypair> {
+        match self.keypair {
+            KeyPairInner::SphincsPlus(ref pair) => Some(pair),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Borrow the Falcon keypair, or `None` if this is another key type.
+    #[cfg(feature = "falcon")]
+    pub fn as_falcon(&self) -> Option<&falcon::Keypair> {
+        match self.keypair {
+            KeyPairInner::Falcon(ref pair) => Some(pair),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
     /// Decode an keypair from a DER-encoded secret key in PKCS#8 PrivateKeyInfo
     /// format (i.e. unencrypted) as defined in [RFC5208].
     ///
@@ -172,6 +646,7 @@ impl Keypair {
         log::debug!(target: "libp2p-identity", "🗝️ Loading RSA keypair from PKCS8");
         rsa::Keypair::try_decode_pkcs8(pkcs8_der).map(|kp| Keypair {
             keypair: KeyPairInner::Rsa(kp),
+            previous_key_fingerprint: None,
         })
     }
 
@@ -184,6 +659,7 @@ impl Keypair {
         log::debug!(target: "libp2p-identity", "🔐 Loading Secp256k1 keypair from DER");
         secp256k1::SecretKey::from_der(der).map(|sk| Keypair {
             keypair: KeyPairInner::Secp256k1(secp256k1::Keypair::from(sk)),
+            previous_key_fingerprint: None,
         })
     }
 
@@ -194,31 +670,232 @@ impl Keypair {
             keypair: KeyPairInner::Ed25519(ed25519::Keypair::from(
                 ed25519::SecretKey::try_from_bytes(bytes)?,
             )),
+            previous_key_fingerprint: None,
+        })
+    }
+
+    /// Import an Ed25519 keypair stored by upstream `libp2p-identity` as the 64-byte
+    /// concatenation of the secret key and the public key.
+    ///
+    /// The public key must belong to the secret key, so existing node keys keep their
+    /// `PeerId` when moved to this crate.
+    #[cfg(feature = "ed25519")]
+    pub fn from_legacy_ed25519_bytes(bytes: &[u8]) -> Result<Keypair, DecodingError> {
+        log::debug!(target: "libp2p-identity", "🔑 Importing legacy Ed25519 keypair");
+        let mut bytes = zeroize::Zeroizing::new(bytes.to_vec());
+        Ok(Keypair {
+            keypair: KeyPairInner::Ed25519(ed25519::Keypair::try_from_bytes(&mut bytes)?),
+            previous_key_fingerprint: None,
         })
     }
 
+    /// Decode a Dilithium keypair from the format produced by [`Keypair::dilithium_to_bytes`]:
+    /// the secret key followed by the public key.
+    ///
+    /// The level is inferred from the length, and the public key must belong to the
+    /// secret key.
     #[cfg(feature = "dilithium")]
     pub fn dilithium_from_bytes(bytes: &[u8]) -> Result<Keypair, DecodingError> {
         log::debug!(target: "libp2p-identity", "🔐 Loading Dilithium keypair from bytes");
         Ok(Keypair {
-            keypair: KeyPairInner::Dilithium(ml_dsa_87::Keypair::from_bytes(bytes).map_err(
-                |e| DecodingError::new(format!("failed to decode Dilithium keypair: {}", e)),
-            )?),
+            keypair: KeyPairInner::Dilithium(dilithium::Keypair::try_from_bytes(bytes)?),
+            previous_key_fingerprint: None,
         })
     }
 
+    /// Deterministically derive a Dilithium keypair at the default [`DilithiumLevel`]
+    /// from a 32-byte seed.
+    ///
+    /// The same seed always yields the same public key and therefore the same [`PeerId`](crate::PeerId).
+    #[cfg(feature = "dilithium")]
+    pub fn dilithium_from_seed(seed: &[u8; 32]) -> Keypair {
+        log::debug!(target: "libp2p-identity", "🛡️ Deriving Dilithium keypair from seed");
+        Keypair {
+            keypair: KeyPairInner::Dilithium(dilithium::Keypair::from_seed(
+                DilithiumLevel::default(),
+                seed,
+            )),
+            previous_key_fingerprint: None,
+        }
+    }
+
+    /// Deterministically derive a Dilithium keypair from a BIP39 mnemonic phrase.
+    ///
+    /// The phrase and passphrase are stretched into the BIP39 seed with PBKDF2, and
+    /// its first 32 bytes are passed to [`Keypair::dilithium_from_seed`]. Phrases with
+    /// an unknown word, an invalid word count or a bad checksum are rejected.
+    #[cfg(feature = "bip39")]
+    pub fn dilithium_from_mnemonic(
+        phrase: &str,
+        passphrase: &str,
+    ) -> Result<Keypair, DecodingError> {
+        let mnemonic = bip39::Mnemonic::parse_in(bip39::Language::English, phrase)
+            .map_err(|e| DecodingError::failed_to_parse("BIP39 mnemonic", e))?;
+        let bip39_seed = zeroize::Zeroizing::new(mnemonic.to_seed(passphrase));
+        let mut seed = zeroize::Zeroizing::new([0u8; 32]);
+        seed.copy_from_slice(&bip39_seed[..32]);
+
+        Ok(Keypair::dilithium_from_seed(&seed))
+    }
+
+    /// Encode a Dilithium keypair as its secret key followed by its public key, as
+    /// accepted by [`Keypair::dilithium_from_bytes`].
+    ///
+    /// The encoding does not depend on the host's byte order, so keys exported on one
+    /// architecture import on any other.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not a Dilithium keypair.
     #[cfg(feature = "dilithium")]
     pub fn dilithium_to_bytes(&self) -> Vec<u8> {
+        #[allow(unreachable_patterns)]
         match self.keypair {
-            KeyPairInner::Dilithium(ref pair) => pair.to_bytes().to_vec(),
+            KeyPairInner::Dilithium(ref pair) => pair.to_bytes(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Write the [`Keypair::dilithium_to_bytes`] encoding into a caller-owned buffer.
+    ///
+    /// `out` is cleared first. If it already has enough capacity, the secret key is
+    /// copied straight into it without any intermediate allocation; otherwise its old
+    /// contents are wiped and it is replaced with a buffer of exactly the right size,
+    /// so no secret-bearing allocation is freed without being zeroized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not a Dilithium keypair.
+    #[cfg(feature = "dilithium")]
+    pub fn secret_bytes_into(&self, out: &mut zeroize::Zeroizing<Vec<u8>>) {
+        #[allow(unreachable_patterns)]
+        let pair = match self.keypair {
+            KeyPairInner::Dilithium(ref pair) => pair,
             _ => unreachable!(),
+        };
+
+        let len = pair.encoded_len();
+        zeroize::Zeroize::zeroize(out);
+        if out.capacity() < len {
+            *out = zeroize::Zeroizing::new(Vec::with_capacity(len));
+        }
+        pair.write_bytes(out);
+    }
+
+    /// Encode the secret key of this keypair in its algorithm-specific raw form,
+    /// as accepted by [`Keypair::from_secret_bytes`].
+    ///
+    /// RSA keypairs have no such encoding and return an error.
+    pub fn secret_to_bytes(&self) -> Result<Vec<u8>, DecodingError> {
+        match self.keypair {
+            #[cfg(feature = "ed25519")]
+            KeyPairInner::Ed25519(ref pair) => Ok(pair.secret().to_bytes().to_vec()),
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            KeyPairInner::Rsa(_) => Err(DecodingError::new(
+                "encoding RSA secret key to raw bytes is unsupported".to_string(),
+            )),
+            #[cfg(feature = "secp256k1")]
+            KeyPairInner::Secp256k1(ref pair) => Ok(pair.secret().to_bytes().to_vec()),
+            #[cfg(feature = "ecdsa")]
+            KeyPairInner::Ecdsa(ref pair) => Ok(pair.secret().to_bytes()),
+            #[cfg(feature = "dilithium")]
+            KeyPairInner::Dilithium(ref pair) => Ok(pair.secret().to_bytes()),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(ref pair) => Ok(pair.secret_to_bytes()),
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(ref pair) => Ok(pair.secret().to_bytes()),
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(ref pair) => Ok(pair.secret().to_bytes()),
+        }
+    }
+
+    /// Reconstruct a keypair of the given [`KeyType`] from the raw secret key
+    /// produced by [`Keypair::secret_to_bytes`].
+    ///
+    /// For Dilithium the level is inferred from the length of `bytes`, and the
+    /// public key is recomputed from the secret key. That needs the
+    /// `dilithium-internals` feature; without it Dilithium and hybrid secret keys are
+    /// rejected.
+    #[allow(unused_variables, unreachable_code)]
+    pub fn from_secret_bytes(key_type: KeyType, bytes: &[u8]) -> Result<Keypair, DecodingError> {
+        match key_type {
+            KeyType::Ed25519 => {
+                #[cfg(feature = "ed25519")]
+                return ed25519::SecretKey::try_from_bytes(bytes.to_vec()).map(|sk| Keypair {
+                    keypair: KeyPairInner::Ed25519(sk.into()),
+                    previous_key_fingerprint: None,
+                });
+                Err(DecodingError::disabled_key_type(KeyType::Ed25519))
+            }
+            KeyType::RSA => Err(DecodingError::new(
+                "decoding RSA secret key from raw bytes is unsupported".to_string(),
+            )),
+            KeyType::Secp256k1 => {
+                #[cfg(feature = "secp256k1")]
+                return secp256k1::SecretKey::try_from_bytes(bytes.to_vec()).map(|sk| Keypair {
+                    keypair: KeyPairInner::Secp256k1(sk.into()),
+                    previous_key_fingerprint: None,
+                });
+                Err(DecodingError::disabled_key_type(KeyType::Secp256k1))
+            }
+            KeyType::Ecdsa => {
+                #[cfg(feature = "ecdsa")]
+                return ecdsa::SecretKey::try_from_bytes(bytes).map(|sk| Keypair {
+                    keypair: KeyPairInner::Ecdsa(sk.into()),
+                    previous_key_fingerprint: None,
+                });
+                Err(DecodingError::disabled_key_type(KeyType::Ecdsa))
+            }
+            KeyType::Dilithium => {
+                #[cfg(feature = "dilithium")]
+                return dilithium::SecretKey::try_from_bytes(bytes)
+                    .and_then(dilithium::Keypair::try_from)
+                    .map(|kp| Keypair {
+                        keypair: KeyPairInner::Dilithium(kp),
+                        previous_key_fingerprint: None,
+                    });
+                Err(DecodingError::disabled_key_type(KeyType::Dilithium))
+            }
+            KeyType::HybridDilithiumEd25519 => {
+                #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+                return hybrid::Keypair::try_from_secret_bytes(bytes).map(|kp| Keypair {
+                    keypair: KeyPairInner::HybridDilithiumEd25519(kp),
+                    previous_key_fingerprint: None,
+                });
+                Err(DecodingError::disabled_key_type(
+                    KeyType::HybridDilithiumEd25519,
+                ))
+            }
+            KeyType::SphincsPlus => {
+                #[cfg(feature = "sphincs-plus")]
+                return sphincs_plus::SecretKey::try_from_bytes(bytes).map(|sk| Keypair {
+                    keypair: KeyPairInner::SphincsPlus(sk.into()),
+                    previous_key_fingerprint: None,
+                });
+                Err(DecodingError::disabled_key_type(KeyType::SphincsPlus))
+            }
+            KeyType::Falcon => {
+                #[cfg(feature = "falcon")]
+                return falcon::SecretKey::try_from_bytes(bytes).map(|sk| Keypair {
+                    keypair: KeyPairInner::Falcon(sk.into()),
+                    previous_key_fingerprint: None,
+                });
+                Err(DecodingError::disabled_key_type(KeyType::Falcon))
+            }
         }
     }
 
     /// Sign a message using the private key of this keypair, producing
     /// a signature that can be verified using the corresponding public key.
+    ///
+    /// Empty messages are accepted by every key type, and messages are never truncated
+    /// or limited in length. Failures of the underlying implementation are reported as
+    /// [`SigningError::BackendFailure`].
     #[allow(unused_variables)]
     pub fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, SigningError> {
+        #[cfg(feature = "metrics")]
+        let _timer =
+            crate::metrics::Timer::start(self.key_type(), crate::metrics::CryptoOperation::Sign);
         match self.keypair {
             #[cfg(feature = "ed25519")]
             KeyPairInner::Ed25519(ref pair) => {
@@ -243,17 +920,286 @@ impl Keypair {
             #[cfg(feature = "dilithium")]
             KeyPairInner::Dilithium(ref pair) => {
                 log::trace!(target: "libp2p-identity", "🛡️ Signing with Dilithium (Post-Quantum)");
-                Ok(pair.sign(msg, None, false).to_vec())
+                Ok(pair.sign(msg))
+            }
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(ref pair) => {
+                log::trace!(target: "libp2p-identity", "🛡️🔑 Signing with hybrid Dilithium+Ed25519");
+                Ok(pair.sign(msg))
+            }
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(ref pair) => {
+                log::trace!(target: "libp2p-identity", "🌳 Signing with SPHINCS+ (Post-Quantum)");
+                Ok(pair.sign(msg))
+            }
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(ref pair) => {
+                log::trace!(target: "libp2p-identity", "🦅 Signing with Falcon (Post-Quantum)");
+                Ok(pair.sign(msg))
             }
         }
     }
 
-    /// Get the public key of this keypair.
-    pub fn public(&self) -> PublicKey {
+    /// Sign every message in `msgs` with this keypair, returning the signatures in the
+    /// same order.
+    ///
+    /// Each message is signed as [`Keypair::sign`] signs it, and each signature verifies
+    /// on its own. With the `dilithium-internals` feature, Dilithium keys unpack the secret
+    /// key and expand the public matrix once for the whole batch; otherwise each message
+    /// is signed in turn.
+    pub fn sign_many(&self, msgs: &[&[u8]]) -> Result<Vec<Vec<u8>>, SigningError> {
         match self.keypair {
-            #[cfg(feature = "ed25519")]
-            KeyPairInner::Ed25519(ref pair) => PublicKey {
-                publickey: PublicKeyInner::Ed25519(pair.public()),
+            #[cfg(feature = "dilithium")]
+            KeyPairInner::Dilithium(ref pair) => {
+                log::trace!(target: "libp2p-identity", "🛡️ Signing {} messages with Dilithium (Post-Quantum)", msgs.len());
+                Ok(pair.sign_many(msgs))
+            }
+            #[allow(unreachable_patterns)]
+            _ => msgs.iter().map(|msg| self.sign(msg)).collect(),
+        }
+    }
+
+    /// Sign a message on tokio's blocking thread pool, so that slow post-quantum
+    /// signing does not stall the async executor.
+    ///
+    /// The message is signed as by [`Keypair::sign`]. The keypair is cloned into
+    /// the blocking task, and the task runs to completion even if the future is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime, and resumes the panic should
+    /// signing panic.
+    #[cfg(feature = "async")]
+    #[cfg_attr(
+        not(any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "rsa",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )),
+        allow(unreachable_code, unused_variables)
+    )]
+    pub fn sign_async(
+        &self,
+        msg: Vec<u8>,
+    ) -> impl core::future::Future<Output = Result<Vec<u8>, SigningError>> + Send + 'static {
+        let keypair = self.clone();
+        let task = tokio::task::spawn_blocking(move || keypair.sign(&msg));
+
+        async move {
+            match task.await {
+                Ok(result) => result,
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                Err(e) => Err(SigningError::backend(
+                    "signing task was cancelled by runtime shutdown",
+                    e,
+                )),
+            }
+        }
+    }
+
+    /// Prove control of this keypair's [`PeerId`](crate::PeerId) by signing a challenge
+    /// chosen by the verifier, e.g. during an authenticated connection upgrade.
+    ///
+    /// The challenge is signed with [`Keypair::sign_with_context`] under a context
+    /// reserved for identity proofs, so the proof is not a plain signature over the
+    /// challenge. Check it with [`PublicKey::verify_identity_proof`].
+    #[cfg(feature = "peerid")]
+    pub fn prove_identity(&self, challenge: &[u8]) -> Result<crate::IdentityProof, SigningError> {
+        self.sign_with_context(crate::identity_proof::CONTEXT, challenge)
+            .map(crate::IdentityProof::from_bytes)
+    }
+
+    /// Sign a message such that the same keypair and message always yield the same
+    /// signature, e.g. for reproducible tests.
+    ///
+    /// Dilithium and hybrid keys switch to deterministic ML-DSA, while the other key
+    /// types except Falcon already sign deterministically with [`Keypair::sign`]. Falcon
+    /// signing is always randomized, so Falcon keys return
+    /// [`SigningError::UnsupportedKeyType`].
+    pub fn sign_deterministic(&self, msg: &[u8]) -> Result<Vec<u8>, SigningError> {
+        match self.keypair {
+            #[cfg(feature = "dilithium")]
+            KeyPairInner::Dilithium(ref pair) => Ok(pair.sign_deterministic(msg)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(ref pair) => Ok(pair.sign_deterministic(msg)),
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(_) => Err(SigningError::UnsupportedKeyType(KeyType::Falcon)),
+            #[allow(unreachable_patterns)]
+            _ => self.sign(msg),
+        }
+    }
+
+    /// Sign a message like [`Keypair::sign`], drawing the signing randomness of Falcon
+    /// keys from `rng` rather than from the operating system.
+    ///
+    /// Dilithium and hybrid keys are signed by the backend, which draws its own
+    /// randomness, so they sign exactly as with [`Keypair::sign`]. The other key types
+    /// sign deterministically. Neither uses `rng`.
+    #[cfg(all(
+        feature = "rand",
+        any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )
+    ))]
+    #[allow(unused_variables)]
+    pub fn sign_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        msg: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>, SigningError> {
+        match self.keypair {
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(ref pair) => Ok(pair.sign_with_rng(msg, rng)),
+            #[allow(unreachable_patterns)]
+            _ => self.sign(msg),
+        }
+    }
+
+    /// Sign a message with a Secp256k1 key, returning a 64-byte `r || s` signature and
+    /// its recovery id, as used by Ethereum-adjacent tooling.
+    ///
+    /// The message is hashed with SHA-256, as for [`Keypair::sign`]; pass both to
+    /// [`PublicKey::recover_from_signature`] to recover the public key. Other key types
+    /// return [`SigningError::UnsupportedKeyType`].
+    #[allow(unused_variables)]
+    pub fn sign_recoverable(&self, msg: &[u8]) -> Result<(Vec<u8>, u8), SigningError> {
+        match self.keypair {
+            #[cfg(feature = "secp256k1")]
+            KeyPairInner::Secp256k1(ref pair) => {
+                log::trace!(target: "libp2p-identity", "🔐 Recoverable signing with Secp256k1");
+                let (sig, recovery_id) = pair.secret().sign_recoverable(msg);
+                Ok((sig.to_vec(), recovery_id))
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(SigningError::UnsupportedKeyType(self.key_type())),
+        }
+    }
+
+    /// Sign `msg` and return a single blob carrying both the signature and the message,
+    /// for protocols that do not transmit them separately.
+    ///
+    /// The blob is the unsigned-varint length of the signature, the signature and then
+    /// the message, so the split is unambiguous for variable-length signatures such as
+    /// Falcon's. Use [`PublicKey::verify_attached`] to check it and recover the message.
+    pub fn sign_attached(&self, msg: &[u8]) -> Result<Vec<u8>, SigningError> {
+        let sig = self.sign(msg)?;
+        let mut len_buf = unsigned_varint::encode::usize_buffer();
+        let len = unsigned_varint::encode::usize(sig.len(), &mut len_buf);
+
+        let mut blob = Vec::with_capacity(len.len() + sig.len() + msg.len());
+        blob.extend_from_slice(len);
+        blob.extend_from_slice(&sig);
+        blob.extend_from_slice(msg);
+        Ok(blob)
+    }
+
+    /// Like [`Keypair::sign`], but tags the signature with the key type, see
+    /// [`PublicKey::verify_typed`].
+    pub fn sign_typed(&self, msg: &[u8]) -> Result<Signature, SigningError> {
+        self.sign(msg)
+            .map(|bytes| Signature::new(self.key_type(), bytes))
+    }
+
+    /// The longest context accepted by [`Keypair::sign_with_context`], the limit
+    /// ML-DSA and SLH-DSA place on their context string.
+    pub const MAX_CONTEXT_LEN: usize = 255;
+
+    /// Sign a message bound to the context string `ctx`, so that the signature cannot
+    /// be replayed in a protocol using a different context.
+    ///
+    /// Dilithium, SPHINCS+ and Falcon use the context field of ML-DSA, SLH-DSA and FN-DSA.
+    /// The other key types sign the message prefixed with a domain separator and the context. An
+    /// empty context is the same as [`Keypair::sign`].
+    ///
+    /// Contexts longer than [`Keypair::MAX_CONTEXT_LEN`] are rejected with
+    /// [`SigningError::ContextTooLong`].
+    pub fn sign_with_context(&self, ctx: &[u8], msg: &[u8]) -> Result<Vec<u8>, SigningError> {
+        if ctx.is_empty() {
+            return self.sign(msg);
+        }
+
+        match self.keypair {
+            #[cfg(feature = "dilithium")]
+            KeyPairInner::Dilithium(ref pair) => pair.sign_with_context(ctx, msg),
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(ref pair) => pair.sign_with_context(ctx, msg),
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(ref pair) => pair.sign_with_context(ctx, msg),
+            #[allow(unreachable_patterns)]
+            _ => self.sign(&context_framed(ctx, msg)?),
+        }
+    }
+
+    /// Sign a message bound to additional associated data, such as a timestamp or topic,
+    /// that is not transmitted with the message.
+    ///
+    /// Unlike a context, the AAD is expected to change from message to message and may
+    /// be of any length, so it cannot be the context itself. Instead the varint length of
+    /// `aad` and `aad` are signed in front of `msg` with [`Keypair::sign_with_context`],
+    /// under a context reserved for AAD. Empty AAD is the same as [`Keypair::sign`].
+    /// Verify with [`PublicKey::verify_with_aad`].
+    pub fn sign_with_aad(&self, aad: &[u8], msg: &[u8]) -> Result<Vec<u8>, SigningError> {
+        if aad.is_empty() {
+            return self.sign(msg);
+        }
+        self.sign_with_context(AAD_CONTEXT, &aad_framed(aad, msg))
+    }
+
+    /// Start signing a message that is fed in chunks, see [`Signer`].
+    pub fn signer(&self) -> Signer {
+        let inner = match self.keypair {
+            #[cfg(feature = "dilithium")]
+            KeyPairInner::Dilithium(ref pair) => SignerInner::Dilithium(pair.signer()),
+            #[allow(unreachable_patterns)]
+            _ => SignerInner::Buffered {
+                keypair: self.clone(),
+                msg: Vec::new(),
+            },
+        };
+        Signer { inner }
+    }
+
+    /// Compare the secret key material of two keypairs in constant time.
+    ///
+    /// Keys of different types never compare equal; the key type itself is not secret.
+    /// RSA keypairs do not expose their secret key and are compared by their public key.
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
+    ))]
+    pub fn ct_eq(&self, other: &Keypair) -> subtle::Choice {
+        use subtle::ConstantTimeEq;
+
+        if core::mem::discriminant(&self.keypair) != core::mem::discriminant(&other.keypair) {
+            return subtle::Choice::from(0);
+        }
+
+        match (self.secret_to_bytes(), other.secret_to_bytes()) {
+            (Ok(a), Ok(b)) => zeroize::Zeroizing::new(a).ct_eq(&zeroize::Zeroizing::new(b)),
+            _ => self.public().ct_eq(&other.public()),
+        }
+    }
+
+    /// Get the public key of this keypair.
+    pub fn public(&self) -> PublicKey {
+        match self.keypair {
+            #[cfg(feature = "ed25519")]
+            KeyPairInner::Ed25519(ref pair) => PublicKey {
+                publickey: PublicKeyInner::Ed25519(pair.public()),
             },
             #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
             KeyPairInner::Rsa(ref pair) => PublicKey {
@@ -269,55 +1215,66 @@ impl Keypair {
             },
             #[cfg(feature = "dilithium")]
             KeyPairInner::Dilithium(ref pair) => PublicKey {
-                publickey: PublicKeyInner::Dilithium(pair.public.clone()),
+                publickey: PublicKeyInner::Dilithium(pair.public().clone()),
+            },
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(ref pair) => PublicKey {
+                publickey: PublicKeyInner::HybridDilithiumEd25519(pair.public()),
+            },
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(ref pair) => PublicKey {
+                publickey: PublicKeyInner::SphincsPlus(pair.public().clone()),
+            },
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(ref pair) => PublicKey {
+                publickey: PublicKeyInner::Falcon(pair.public().clone()),
             },
         }
     }
 
+    /// Whether `pk` is the public key of this keypair, e.g. to check that a keypair
+    /// imported from an HSM or backup matches the expected identity.
+    ///
+    /// The key material is compared with [`PublicKey::ct_eq`].
+    #[cfg_attr(
+        not(any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "rsa",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )),
+        allow(unreachable_code, unused_variables)
+    )]
+    pub fn has_public_key(&self, pk: &PublicKey) -> bool {
+        self.public().ct_eq(pk).into()
+    }
+
     /// Encode a private key as protobuf structure.
+    ///
+    /// The returned buffer holds the secret key; prefer
+    /// [`Keypair::to_protobuf_encoding_zeroizing`] when it is not handed off elsewhere.
+    /// The error type is kept for compatibility with `libp2p-identity`.
     pub fn to_protobuf_encoding(&self) -> Result<Vec<u8>, DecodingError> {
         #[cfg(any(
             feature = "ecdsa",
             feature = "secp256k1",
             feature = "ed25519",
             feature = "rsa",
-            feature = "dilithium"
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
         ))]
-        {
-            use quick_protobuf::MessageWrite;
-            log::trace!(target: "libp2p-identity", "📦 Encoding keypair to protobuf");
-            let pk: proto::PrivateKey = match self.keypair {
-                #[cfg(feature = "ed25519")]
-                KeyPairInner::Ed25519(ref data) => proto::PrivateKey {
-                    Type: proto::KeyType::Ed25519,
-                    Data: data.to_bytes().to_vec(),
-                },
-                #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
-                KeyPairInner::Rsa(_) => return Err(DecodingError::encoding_unsupported("RSA")),
-                #[cfg(feature = "secp256k1")]
-                KeyPairInner::Secp256k1(ref data) => proto::PrivateKey {
-                    Type: proto::KeyType::Secp256k1,
-                    Data: data.secret().to_bytes().to_vec(),
-                },
-                #[cfg(feature = "ecdsa")]
-                KeyPairInner::Ecdsa(ref data) => proto::PrivateKey {
-                    Type: proto::KeyType::ECDSA,
-                    Data: data.secret().encode_der(),
-                },
-                #[cfg(feature = "dilithium")]
-                // NOTE: this is actually a Keypair but libp2p assumes Keypairs can always be derived from SecretKeys
-                KeyPairInner::Dilithium(ref data) => proto::PrivateKey {
-                    Type: proto::KeyType::Dilithium,
-                    Data: data.to_bytes().to_vec(),
-                },
-            };
-
-            let mut buf = Vec::with_capacity(pk.get_size());
-            let mut writer = Writer::new(&mut buf);
-            pk.write_message(&mut writer).expect("Encoding to succeed");
-
-            Ok(buf)
-        }
+        return match self.to_protobuf_encoding_zeroizing() {
+            Ok(mut buf) => Ok(core::mem::take(&mut *buf)),
+            // RSA keypairs are the only ones that cannot be encoded.
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            Err(_) => Err(DecodingError::encoding_unsupported("RSA")),
+            #[cfg(not(all(feature = "rsa", not(target_arch = "wasm32"))))]
+            Err(_) => unreachable!("only RSA keypairs cannot be encoded"),
+        };
 
         #[cfg(not(any(
             feature = "ecdsa",
@@ -325,79 +1282,232 @@ impl Keypair {
             feature = "ed25519",
             feature = "rsa",
             feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon",
         )))]
         unreachable!()
     }
 
+    /// Encode a private key as protobuf structure into a buffer that is wiped on drop.
+    ///
+    /// Intermediate copies of the secret key are wiped as well. RSA keypairs cannot be
+    /// encoded and return [`EncodingError::UnsupportedKeyType`].
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
+    ))]
+    pub fn to_protobuf_encoding_zeroizing(
+        &self,
+    ) -> Result<zeroize::Zeroizing<Vec<u8>>, EncodingError> {
+        use quick_protobuf::MessageWrite;
+        log::trace!(target: "libp2p-identity", "📦 Encoding keypair to protobuf");
+        let (key_type, data) = match self.keypair {
+            #[cfg(feature = "ed25519")]
+            KeyPairInner::Ed25519(ref data) => (proto::KeyType::Ed25519, data.to_bytes().to_vec()),
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            KeyPairInner::Rsa(_) => return Err(EncodingError::UnsupportedKeyType(KeyType::RSA)),
+            #[cfg(feature = "secp256k1")]
+            KeyPairInner::Secp256k1(ref data) => {
+                (proto::KeyType::Secp256k1, data.secret().to_bytes().to_vec())
+            }
+            #[cfg(feature = "ecdsa")]
+            KeyPairInner::Ecdsa(ref data) => (proto::KeyType::ECDSA, data.secret().encode_der()),
+            #[cfg(feature = "dilithium")]
+            // NOTE: this is actually a Keypair but libp2p assumes Keypairs can always be derived from SecretKeys
+            KeyPairInner::Dilithium(ref data) => (proto::KeyType::Dilithium, data.to_bytes()),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(ref data) => {
+                (proto::KeyType::HybridDilithiumEd25519, data.to_bytes())
+            }
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(ref data) => (proto::KeyType::SphincsPlus, data.to_bytes()),
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(ref data) => (proto::KeyType::Falcon, data.to_bytes()),
+        };
+        let pk = zeroize::Zeroizing::new(proto::PrivateKey {
+            Type: key_type,
+            Data: data,
+            PreviousKeyFingerprint: self.previous_key_fingerprint.clone(),
+        });
+
+        let mut buf = zeroize::Zeroizing::new(vec![0; pk.get_size()]);
+        let mut writer = Writer::new(BytesWriter::new(&mut buf));
+        pk.write_message(&mut writer).expect("Encoding to succeed");
+
+        Ok(buf)
+    }
+
+    /// Encode the keypair as lowercase hex of its protobuf encoding, e.g. to pass it
+    /// in an environment variable. The string is wiped on drop.
+    ///
+    /// RSA keypairs cannot be encoded and return [`EncodingError::UnsupportedKeyType`].
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
+    ))]
+    pub fn to_hex(&self) -> Result<zeroize::Zeroizing<alloc::string::String>, EncodingError> {
+        let protobuf = self.to_protobuf_encoding_zeroizing()?;
+        Ok(crate::hex::encode(&protobuf))
+    }
+
+    /// Decode a keypair from the hex produced by [`Keypair::to_hex`].
+    ///
+    /// Surrounding whitespace and a `0x` prefix are ignored, and digits may be in
+    /// either case. Odd-length input and non-hex characters are rejected.
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
+    ))]
+    pub fn from_hex(hex: &str) -> Result<Keypair, DecodingError> {
+        Keypair::from_protobuf_encoding(&crate::hex::decode(hex)?)
+    }
+
+    /// Decode a protobuf encoded key that may be either a full keypair or only a
+    /// public key.
+    ///
+    /// Private and public keys share the same protobuf layout, so the blob is tried as
+    /// a keypair first and then as a public key. If neither fits, the keypair error is
+    /// returned.
+    pub fn try_from_protobuf_with_kind(bytes: &[u8]) -> Result<KeypairOrPublic, DecodingError> {
+        match Keypair::from_protobuf_encoding(bytes) {
+            Ok(keypair) => Ok(KeypairOrPublic::Keypair(keypair)),
+            Err(err) => PublicKey::try_decode_protobuf(bytes)
+                .map(KeypairOrPublic::Public)
+                .map_err(|_| err),
+        }
+    }
+
     /// Decode a private key from a protobuf structure and parse it as a [`Keypair`].
-    #[allow(unused_variables)]
+    ///
+    /// For Dilithium keypairs, the only branches on the input are on protobuf tags and
+    /// lengths and on the public parts of the secret key; the secret parts are copied
+    /// without being inspected. See [`dilithium::Keypair::try_from_bytes`].
+    #[allow(unused_variables, unused_mut)]
     pub fn from_protobuf_encoding(bytes: &[u8]) -> Result<Keypair, DecodingError> {
         #[cfg(any(
             feature = "ecdsa",
             feature = "secp256k1",
             feature = "ed25519",
             feature = "rsa",
-            feature = "dilithium"
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
         ))]
         {
             log::trace!(target: "libp2p-identity", "📦 Decoding keypair from protobuf");
             use quick_protobuf::MessageRead;
+            let (key_type, _) = check_key_fields(bytes)?;
+            let invalid = |e: DecodingError| e.invalid_key_material(key_type);
             let mut reader = BytesReader::from_bytes(bytes);
             let mut private_key = proto::PrivateKey::from_reader(&mut reader, bytes)
                 .map_err(|e| DecodingError::bad_protobuf("private key bytes", e))
                 .map(zeroize::Zeroizing::new)?;
+            let previous_key_fingerprint = private_key.PreviousKeyFingerprint.take();
 
             #[allow(unreachable_code)]
             match private_key.Type {
                 proto::KeyType::Ed25519 => {
                     #[cfg(feature = "ed25519")]
-                    return ed25519::Keypair::try_from_bytes(&mut private_key.Data).map(|sk| {
-                        Keypair {
+                    return ed25519::Keypair::try_from_bytes(&mut private_key.Data)
+                        .map(|sk| Keypair {
                             keypair: KeyPairInner::Ed25519(sk),
-                        }
-                    });
-                    Err(DecodingError::missing_feature("ed25519"))
+                            previous_key_fingerprint,
+                        })
+                        .map_err(invalid);
+                    Err(DecodingError::disabled_key_type(KeyType::Ed25519))
                 }
                 proto::KeyType::RSA => {
                     #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
-                    return rsa::Keypair::try_decode_pkcs1(&mut private_key.Data).map(|sk| {
-                        Keypair {
+                    return rsa::Keypair::try_decode_pkcs1(&mut private_key.Data)
+                        .map(|sk| Keypair {
                             keypair: KeyPairInner::Rsa(sk),
-                        }
-                    });
-                    Err(DecodingError::missing_feature("rsa"))
+                            previous_key_fingerprint,
+                        })
+                        .map_err(invalid);
+                    Err(DecodingError::disabled_key_type(KeyType::RSA))
                 }
                 proto::KeyType::Secp256k1 => {
                     #[cfg(feature = "secp256k1")]
-                    return secp256k1::SecretKey::try_from_bytes(&mut private_key.Data).map(
-                        |key| Keypair {
+                    return secp256k1::SecretKey::try_from_bytes(&mut private_key.Data)
+                        .map(|key| Keypair {
                             keypair: KeyPairInner::Secp256k1(key.into()),
-                        },
-                    );
+                            previous_key_fingerprint,
+                        })
+                        .map_err(invalid);
 
-                    Err(DecodingError::missing_feature("secp256k1"))
+                    Err(DecodingError::disabled_key_type(KeyType::Secp256k1))
                 }
                 proto::KeyType::ECDSA => {
                     #[cfg(feature = "ecdsa")]
-                    return ecdsa::SecretKey::try_decode_der(&mut private_key.Data).map(|key| {
-                        Keypair {
+                    return ecdsa::SecretKey::try_decode_der(&mut private_key.Data)
+                        .map(|key| Keypair {
                             keypair: KeyPairInner::Ecdsa(key.into()),
-                        }
-                    });
+                            previous_key_fingerprint,
+                        })
+                        .map_err(invalid);
 
-                    Err(DecodingError::missing_feature("ecdsa"))
+                    Err(DecodingError::disabled_key_type(KeyType::Ecdsa))
                 }
                 proto::KeyType::Dilithium => {
                     #[cfg(feature = "dilithium")]
-                    return ml_dsa_87::Keypair::from_bytes(&*private_key.Data)
+                    return dilithium::Keypair::try_from_bytes(&private_key.Data)
+                        .map(|key| Keypair {
+                            keypair: KeyPairInner::Dilithium(key),
+                            previous_key_fingerprint,
+                        })
+                        .map_err(invalid);
+
+                    Err(DecodingError::disabled_key_type(KeyType::Dilithium))
+                }
+                proto::KeyType::HybridDilithiumEd25519 => {
+                    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+                    return hybrid::Keypair::try_from_bytes(&private_key.Data)
+                        .map(|key| Keypair {
+                            keypair: KeyPairInner::HybridDilithiumEd25519(key),
+                            previous_key_fingerprint,
+                        })
+                        .map_err(invalid);
+
+                    Err(DecodingError::disabled_key_type(
+                        KeyType::HybridDilithiumEd25519,
+                    ))
+                }
+                proto::KeyType::SphincsPlus => {
+                    #[cfg(feature = "sphincs-plus")]
+                    return sphincs_plus::Keypair::try_from_bytes(&private_key.Data)
+                        .map(|key| Keypair {
+                            keypair: KeyPairInner::SphincsPlus(key),
+                            previous_key_fingerprint,
+                        })
+                        .map_err(invalid);
+
+                    Err(DecodingError::disabled_key_type(KeyType::SphincsPlus))
+                }
+                proto::KeyType::Falcon => {
+                    #[cfg(feature = "falcon")]
+                    return falcon::Keypair::try_from_bytes(&private_key.Data)
                         .map(|key| Keypair {
-                            keypair: KeyPairInner::Dilithium(key.into()),
+                            keypair: KeyPairInner::Falcon(key),
+                            previous_key_fingerprint,
                         })
-                        .map_err(|e| {
-                            DecodingError::new(format!("Bad dilithium private key protobuf: {}", e))
-                        });
+                        .map_err(invalid);
 
-                    Err(DecodingError::missing_feature("dilithium"))
+                    Err(DecodingError::disabled_key_type(KeyType::Falcon))
                 }
             }
         }
@@ -408,14 +1518,148 @@ impl Keypair {
             feature = "ed25519",
             feature = "rsa",
             feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon",
         )))]
         unreachable!()
     }
 
+    /// Record that this keypair supersedes the key with fingerprint `fp`, e.g. when a
+    /// node rotates from a classical key to a Dilithium one.
+    ///
+    /// The fingerprint is carried in the protobuf encoding of the keypair. Decoders
+    /// predating this field ignore it.
+    pub fn set_supersedes(&mut self, fp: &[u8]) {
+        self.previous_key_fingerprint = Some(fp.to_vec());
+    }
+
+    /// The fingerprint of the key this keypair supersedes, if any, see
+    /// [`Keypair::set_supersedes`].
+    pub fn previous_key_fingerprint(&self) -> Option<&[u8]> {
+        self.previous_key_fingerprint.as_deref()
+    }
+
+    /// Encode the keypair as a `LIBP2P PRIVATE KEY` PEM block wrapping its protobuf encoding.
+    ///
+    /// The block carries a `Key-Type` header so readers can reject unsupported keys early.
+    /// The string is wiped on drop. RSA keypairs cannot be encoded and return
+    /// [`EncodingError::UnsupportedKeyType`].
+    #[cfg(feature = "pem")]
+    pub fn to_pem(&self) -> Result<zeroize::Zeroizing<String>, EncodingError> {
+        let protobuf = self.to_protobuf_encoding_zeroizing()?;
+        Ok(crate::pem::encode(self.key_type(), &protobuf))
+    }
+
+    /// Decode a keypair from a PEM block produced by [`Keypair::to_pem`].
+    #[cfg(feature = "pem")]
+    pub fn from_pem(pem: &str) -> Result<Keypair, DecodingError> {
+        let (key_type, protobuf) = crate::pem::decode(pem)?;
+        let keypair = Keypair::from_protobuf_encoding(&protobuf)?;
+
+        if keypair.key_type() != key_type {
+            return Err(DecodingError::new(format!(
+                "PEM declares a {key_type} key but contains a {} key",
+                keypair.key_type()
+            )));
+        }
+
+        Ok(keypair)
+    }
+
+    /// Encode the keypair as an identity file: a magic header, format version and key
+    /// type, the protobuf encoding, and a SHA-256 checksum over all of it.
+    ///
+    /// Unlike raw or hex encoded key bytes, a flipped bit in the file is detected by
+    /// [`Keypair::from_identity_file`] instead of silently yielding another key.
+    /// The file is wiped on drop; RSA keypairs cannot be encoded and return
+    /// [`EncodingError::UnsupportedKeyType`].
+    #[cfg(all(
+        feature = "identity-file",
+        any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "rsa",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )
+    ))]
+    pub fn to_identity_file(&self) -> Result<zeroize::Zeroizing<Vec<u8>>, EncodingError> {
+        let protobuf = self.to_protobuf_encoding_zeroizing()?;
+        Ok(crate::identity_file::encode(self.key_type(), &protobuf))
+    }
+
+    /// Decode a keypair from an identity file produced by [`Keypair::to_identity_file`].
+    ///
+    /// The magic header, format version and checksum are checked before the key is
+    /// decoded.
+    #[cfg(feature = "identity-file")]
+    pub fn from_identity_file(file: &[u8]) -> Result<Keypair, DecodingError> {
+        let (key_type, protobuf) = crate::identity_file::decode(file)?;
+        let keypair = Keypair::from_protobuf_encoding(&protobuf)?;
+
+        if keypair.key_type() != key_type {
+            return Err(DecodingError::new(format!(
+                "identity file declares a {key_type} key but contains a {} key",
+                keypair.key_type()
+            )));
+        }
+
+        Ok(keypair)
+    }
+
+    /// Encrypt the keypair under `passphrase`, for storage on disks other users can read.
+    ///
+    /// The key is derived with Argon2id from the passphrase and a random salt, and the
+    /// protobuf encoding is sealed with ChaCha20-Poly1305 under a random nonce. RSA
+    /// keypairs cannot be encoded and return [`EncodingError::UnsupportedKeyType`].
+    #[cfg(feature = "encrypted-storage")]
+    pub fn to_encrypted_bytes(&self, passphrase: &[u8]) -> Result<Vec<u8>, EncodingError> {
+        self.to_encrypted_bytes_with_params(
+            passphrase,
+            crate::encrypted_storage::KdfParams::DEFAULT,
+        )
+    }
+
+    #[cfg(feature = "encrypted-storage")]
+    pub(crate) fn to_encrypted_bytes_with_params(
+        &self,
+        passphrase: &[u8],
+        params: crate::encrypted_storage::KdfParams,
+    ) -> Result<Vec<u8>, EncodingError> {
+        let protobuf = self.to_protobuf_encoding_zeroizing()?;
+        Ok(crate::encrypted_storage::encrypt(
+            &protobuf, passphrase, params,
+        ))
+    }
+
+    /// Decrypt a keypair produced by [`Keypair::to_encrypted_bytes`].
+    ///
+    /// The ciphertext is authenticated before anything is decrypted; a wrong passphrase
+    /// or modified data returns [`DecodingError::AuthenticationFailed`].
+    #[cfg(feature = "encrypted-storage")]
+    pub fn from_encrypted_bytes(bytes: &[u8], passphrase: &[u8]) -> Result<Keypair, DecodingError> {
+        let protobuf = crate::encrypted_storage::decrypt(bytes, passphrase)?;
+        Keypair::from_protobuf_encoding(&protobuf)
+    }
+
     /// Return a [`KeyType`] of the [`Keypair`].
     /// Get the key type of the keypair.
+    #[cfg_attr(
+        not(any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "rsa",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )),
+        allow(unreachable_code)
+    )]
     pub fn key_type(&self) -> KeyType {
-        let key_type = match &self.keypair {
+        let key_type = match self.keypair {
             #[cfg(feature = "ed25519")]
             KeyPairInner::Ed25519(_) => KeyType::Ed25519,
             #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
@@ -426,6 +1670,12 @@ impl Keypair {
             KeyPairInner::Ecdsa(_) => KeyType::Ecdsa,
             #[cfg(feature = "dilithium")]
             KeyPairInner::Dilithium(_) => KeyType::Dilithium,
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(_) => KeyType::HybridDilithiumEd25519,
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(_) => KeyType::SphincsPlus,
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(_) => KeyType::Falcon,
         };
 
         let emoji = match key_type {
@@ -434,12 +1684,47 @@ impl Keypair {
             KeyType::Secp256k1 => "🔐",
             KeyType::Ecdsa => "🔒",
             KeyType::Dilithium => "🛡️",
+            KeyType::HybridDilithiumEd25519 => "🛡️🔑",
+            KeyType::SphincsPlus => "🌳",
+            KeyType::Falcon => "🦅",
         };
 
         log::trace!(target: "libp2p-identity", "{} Keypair type: {}", emoji, key_type);
         key_type
     }
 
+    /// Return the [`DilithiumLevel`] of this keypair, or `None` if it is not a Dilithium keypair.
+    #[cfg(feature = "dilithium")]
+    pub fn dilithium_level(&self) -> Option<DilithiumLevel> {
+        match self.keypair {
+            KeyPairInner::Dilithium(ref pair) => Some(pair.level()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Run scheme-specific structural checks on the key material, e.g. after loading
+    /// it from untrusted storage.
+    ///
+    /// Dilithium keypairs, including the Dilithium half of a hybrid keypair, are
+    /// checked for out-of-range secret coefficients and for a public key the secret key
+    /// does not derive, which decoding alone does not catch. The decoders of the other
+    /// key types already reject every invalid key, so they always pass.
+    ///
+    /// There is no public key counterpart: decoding fully checks every public key, and
+    /// a Dilithium public key packs `t1` in 10-bit coefficients, so every encoding of
+    /// the right length is in range.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        match self.keypair {
+            #[cfg(feature = "dilithium")]
+            KeyPairInner::Dilithium(ref pair) => pair.validate(),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(ref pair) => pair.dilithium().validate(),
+            #[allow(unreachable_patterns)]
+            _ => Ok(()),
+        }
+    }
+
     /// Deterministically derive a new secret from this [`Keypair`],
     /// taking into account the provided domain.
     ///
@@ -462,7 +1747,9 @@ impl Keypair {
         feature = "secp256k1",
         feature = "ed25519",
         feature = "rsa",
-        feature = "dilithium"
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
     ))]
     pub fn derive_secret(&self, domain: &[u8]) -> Option<[u8; 32]> {
         let mut okm = [0u8; 32];
@@ -479,32 +1766,116 @@ impl Keypair {
         feature = "secp256k1",
         feature = "ed25519",
         feature = "rsa",
-        feature = "dilithium"
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
     )))]
     pub fn derive_secret(&self, _: &[u8]) -> Option<[u8; 32]> {
         None
     }
 
-    /// Return the secret key of the [`Keypair`].
-    #[allow(dead_code)]
-    pub fn secret(&self) -> Option<Vec<u8>> {
+    /// Deterministically derive the child [`Keypair`] at `index` from this [`Keypair`].
+    ///
+    /// The parent's secret key and the index are fed through HKDF to produce the seed
+    /// of the child key, so the same parent and index always yield the same child.
+    ///
+    /// Only Dilithium keypairs can be derived from; other key types return a [`DeriveError`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use libp2p_identity as identity;
+    /// let parent = identity::Keypair::generate_dilithium();
+    ///
+    /// let child = parent.derive_child(0).expect("can derive child for dilithium");
+    /// assert_eq!(child.public(), parent.derive_child(0).unwrap().public());
+    /// ```
+    #[cfg(feature = "dilithium")]
+    pub fn derive_child(&self, index: u32) -> Result<Keypair, DeriveError> {
         match self.keypair {
-            #[cfg(feature = "ed25519")]
-            KeyPairInner::Ed25519(ref inner) => Some(inner.secret().to_bytes().to_vec()),
-            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
-            KeyPairInner::Rsa(_) => None,
+            KeyPairInner::Dilithium(ref parent) => {
+                log::debug!(target: "libp2p-identity", "🛡️ Deriving Dilithium child keypair {}", index);
+                let parent_secret = zeroize::Zeroizing::new(parent.secret().to_bytes());
+                let mut seed = zeroize::Zeroizing::new([0u8; 32]);
+                hkdf::Hkdf::<sha2::Sha256>::new(Some(DERIVE_CHILD_SALT), parent_secret.as_ref())
+                    .expand(&index.to_be_bytes(), seed.as_mut())
+                    .expect("seed.len() == 32");
+
+                Ok(Keypair {
+                    keypair: KeyPairInner::Dilithium(dilithium::Keypair::from_seed(
+                        parent.level(),
+                        &seed,
+                    )),
+                    previous_key_fingerprint: None,
+                })
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(DeriveError::new(self.key_type())),
+        }
+    }
+
+    /// Deterministically derive the [`Keypair`] this [`Keypair`] signs `protocol` with.
+    ///
+    /// The parent's secret key is fed through HKDF with the protocol label to produce
+    /// the seed of the subkey, so one stored key yields a stable, independent signing
+    /// key per protocol. The subkey has the same [`DilithiumLevel`] as its parent.
+    ///
+    /// Only Dilithium keypairs can be derived from; other key types return a [`DeriveError`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use libp2p_identity as identity;
+    /// let master = identity::Keypair::generate_dilithium();
+    ///
+    /// let gossip = master.derive_subkey("/meshsub/1.1.0").unwrap();
+    /// assert_eq!(gossip.public(), master.derive_subkey("/meshsub/1.1.0").unwrap().public());
+    /// assert_ne!(gossip.public(), master.derive_subkey("/ipfs/kad/1.0.0").unwrap().public());
+    /// ```
+    #[cfg(feature = "dilithium")]
+    pub fn derive_subkey(&self, protocol: &str) -> Result<Keypair, DeriveError> {
+        match self.keypair {
+            KeyPairInner::Dilithium(ref parent) => {
+                log::debug!(target: "libp2p-identity", "🛡️ Deriving Dilithium subkey for {}", protocol);
+                let parent_secret = zeroize::Zeroizing::new(parent.secret().to_bytes());
+                let mut seed = zeroize::Zeroizing::new([0u8; 32]);
+                hkdf::Hkdf::<sha2::Sha256>::new(Some(DERIVE_SUBKEY_SALT), parent_secret.as_ref())
+                    .expand(protocol.as_bytes(), seed.as_mut())
+                    .expect("seed.len() == 32");
+
+                Ok(Keypair {
+                    keypair: KeyPairInner::Dilithium(dilithium::Keypair::from_seed(
+                        parent.level(),
+                        &seed,
+                    )),
+                    previous_key_fingerprint: None,
+                })
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(DeriveError::new(self.key_type())),
+        }
+    }
+
+    /// Return the secret key of the [`Keypair`].
+    #[allow(dead_code)]
+    pub fn secret(&self) -> Option<Vec<u8>> {
+        match self.keypair {
+            #[cfg(feature = "ed25519")]
+            KeyPairInner::Ed25519(ref inner) => Some(inner.secret().to_bytes().to_vec()),
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            KeyPairInner::Rsa(_) => None,
             #[cfg(feature = "secp256k1")]
             KeyPairInner::Secp256k1(ref inner) => Some(inner.secret().to_bytes().to_vec()),
             #[cfg(feature = "ecdsa")]
-            KeyPairInner::Ecdsa(ref inner) => Some(
-                inner
-                    .secret()
-                    .to_bytes()
-                    .try_into()
-                    .expect("Ecdsa's private key should be 32 bytes"),
-            ),
+            KeyPairInner::Ecdsa(ref inner) => Some(inner.secret().to_bytes()),
             #[cfg(feature = "dilithium")]
-            KeyPairInner::Dilithium(ref inner) => Some(inner.secret.to_bytes().to_vec()),
+            KeyPairInner::Dilithium(ref inner) => Some(inner.secret().to_bytes()),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(ref inner) => Some(inner.secret_to_bytes()),
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(ref inner) => Some(inner.secret().to_bytes()),
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(ref inner) => Some(inner.secret().to_bytes()),
         }
     }
 }
@@ -514,6 +1885,7 @@ impl From<ecdsa::Keypair> for Keypair {
     fn from(kp: ecdsa::Keypair) -> Self {
         Keypair {
             keypair: KeyPairInner::Ecdsa(kp),
+            previous_key_fingerprint: None,
         }
     }
 }
@@ -523,6 +1895,7 @@ impl From<ed25519::Keypair> for Keypair {
     fn from(kp: ed25519::Keypair) -> Self {
         Keypair {
             keypair: KeyPairInner::Ed25519(kp),
+            previous_key_fingerprint: None,
         }
     }
 }
@@ -532,6 +1905,37 @@ impl From<secp256k1::Keypair> for Keypair {
     fn from(kp: secp256k1::Keypair) -> Self {
         Keypair {
             keypair: KeyPairInner::Secp256k1(kp),
+            previous_key_fingerprint: None,
+        }
+    }
+}
+
+#[cfg(all(feature = "dilithium", feature = "ed25519"))]
+impl From<hybrid::Keypair> for Keypair {
+    fn from(kp: hybrid::Keypair) -> Self {
+        Keypair {
+            keypair: KeyPairInner::HybridDilithiumEd25519(kp),
+            previous_key_fingerprint: None,
+        }
+    }
+}
+
+#[cfg(feature = "sphincs-plus")]
+impl From<sphincs_plus::Keypair> for Keypair {
+    fn from(kp: sphincs_plus::Keypair) -> Self {
+        Keypair {
+            keypair: KeyPairInner::SphincsPlus(kp),
+            previous_key_fingerprint: None,
+        }
+    }
+}
+
+#[cfg(feature = "falcon")]
+impl From<falcon::Keypair> for Keypair {
+    fn from(kp: falcon::Keypair) -> Self {
+        Keypair {
+            keypair: KeyPairInner::Falcon(kp),
+            previous_key_fingerprint: None,
         }
     }
 }
@@ -541,10 +1945,49 @@ impl From<rsa::Keypair> for Keypair {
     fn from(kp: rsa::Keypair) -> Self {
         Keypair {
             keypair: KeyPairInner::Rsa(kp),
+            previous_key_fingerprint: None,
         }
     }
 }
 
+/// Keypairs are equal if their secret keys are, compared in constant time with
+/// [`Keypair::ct_eq`], and they supersede the same key.
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
+))]
+impl PartialEq for Keypair {
+    fn eq(&self, other: &Self) -> bool {
+        bool::from(self.ct_eq(other))
+            && self.previous_key_fingerprint == other.previous_key_fingerprint
+    }
+}
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
+))]
+impl Eq for Keypair {}
+
+impl TryFrom<&[u8]> for Keypair {
+    type Error = DecodingError;
+
+    /// Decode a protobuf encoded keypair, see [`Keypair::from_protobuf_encoding`].
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Keypair::from_protobuf_encoding(bytes)
+    }
+}
+
 #[cfg(feature = "ed25519")]
 impl TryInto<ed25519::Keypair> for Keypair {
     type Error = OtherVariantError;
@@ -560,6 +2003,16 @@ impl TryInto<ed25519::Keypair> for Keypair {
             KeyPairInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
             #[cfg(feature = "dilithium")]
             KeyPairInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
         }
     }
 }
@@ -579,6 +2032,16 @@ impl TryInto<ecdsa::Keypair> for Keypair {
             KeyPairInner::Secp256k1(_) => Err(OtherVariantError::new(crate::KeyType::Secp256k1)),
             #[cfg(feature = "dilithium")]
             KeyPairInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
         }
     }
 }
@@ -598,6 +2061,16 @@ impl TryInto<secp256k1::Keypair> for Keypair {
             KeyPairInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
             #[cfg(feature = "dilithium")]
             KeyPairInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
         }
     }
 }
@@ -617,11 +2090,265 @@ impl TryInto<rsa::Keypair> for Keypair {
             KeyPairInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
             #[cfg(feature = "dilithium")]
             KeyPairInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
+        }
+    }
+}
+
+#[cfg(feature = "dilithium")]
+impl TryInto<dilithium::Keypair> for Keypair {
+    type Error = OtherVariantError;
+
+    fn try_into(self) -> Result<dilithium::Keypair, Self::Error> {
+        match self.keypair {
+            KeyPairInner::Dilithium(inner) => Ok(inner),
+            #[cfg(feature = "ed25519")]
+            KeyPairInner::Ed25519(_) => Err(OtherVariantError::new(crate::KeyType::Ed25519)),
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            KeyPairInner::Rsa(_) => Err(OtherVariantError::new(crate::KeyType::RSA)),
+            #[cfg(feature = "secp256k1")]
+            KeyPairInner::Secp256k1(_) => Err(OtherVariantError::new(crate::KeyType::Secp256k1)),
+            #[cfg(feature = "ecdsa")]
+            KeyPairInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
+        }
+    }
+}
+
+#[cfg(feature = "sphincs-plus")]
+impl TryInto<sphincs_plus::Keypair> for Keypair {
+    type Error = OtherVariantError;
+
+    fn try_into(self) -> Result<sphincs_plus::Keypair, Self::Error> {
+        match self.keypair {
+            KeyPairInner::SphincsPlus(inner) => Ok(inner),
+            #[cfg(feature = "ed25519")]
+            KeyPairInner::Ed25519(_) => Err(OtherVariantError::new(crate::KeyType::Ed25519)),
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            KeyPairInner::Rsa(_) => Err(OtherVariantError::new(crate::KeyType::RSA)),
+            #[cfg(feature = "secp256k1")]
+            KeyPairInner::Secp256k1(_) => Err(OtherVariantError::new(crate::KeyType::Secp256k1)),
+            #[cfg(feature = "ecdsa")]
+            KeyPairInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
+            #[cfg(feature = "dilithium")]
+            KeyPairInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "falcon")]
+            KeyPairInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
+        }
+    }
+}
+
+#[cfg(feature = "falcon")]
+impl TryInto<falcon::Keypair> for Keypair {
+    type Error = OtherVariantError;
+
+    fn try_into(self) -> Result<falcon::Keypair, Self::Error> {
+        match self.keypair {
+            KeyPairInner::Falcon(inner) => Ok(inner),
+            #[cfg(feature = "ed25519")]
+            KeyPairInner::Ed25519(_) => Err(OtherVariantError::new(crate::KeyType::Ed25519)),
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            KeyPairInner::Rsa(_) => Err(OtherVariantError::new(crate::KeyType::RSA)),
+            #[cfg(feature = "secp256k1")]
+            KeyPairInner::Secp256k1(_) => Err(OtherVariantError::new(crate::KeyType::Secp256k1)),
+            #[cfg(feature = "ecdsa")]
+            KeyPairInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
+            #[cfg(feature = "dilithium")]
+            KeyPairInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            KeyPairInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            KeyPairInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+        }
+    }
+}
+
+/// Signs a message that is fed in chunks, created by [`Keypair::signer`].
+///
+/// The concatenated chunks are signed as [`Keypair::sign`] signs them. With the
+/// `dilithium-internals` feature, Dilithium keys hash each chunk as it arrives, so the
+/// message is never held in memory. Otherwise the whole message is buffered until
+/// [`Signer::finalize`].
+#[derive(Debug, Clone)]
+pub struct Signer {
+    inner: SignerInner,
+}
+
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+enum SignerInner {
+    #[cfg(feature = "dilithium")]
+    Dilithium(dilithium::Signer),
+    Buffered {
+        keypair: Keypair,
+        msg: Vec<u8>,
+    },
+}
+
+impl Signer {
+    /// Feed the next chunk of the message.
+    pub fn update(&mut self, chunk: &[u8]) {
+        match self.inner {
+            #[cfg(feature = "dilithium")]
+            SignerInner::Dilithium(ref mut signer) => signer.update(chunk),
+            SignerInner::Buffered { ref mut msg, .. } => msg.extend_from_slice(chunk),
+        }
+    }
+
+    /// Sign the message fed so far.
+    pub fn finalize(self) -> Result<Vec<u8>, SigningError> {
+        match self.inner {
+            #[cfg(feature = "dilithium")]
+            SignerInner::Dilithium(signer) => {
+                log::trace!(target: "libp2p-identity", "🛡️ Signing streamed message with Dilithium (Post-Quantum)");
+                Ok(signer.finalize())
+            }
+            SignerInner::Buffered { keypair, msg } => keypair.sign(&msg),
+        }
+    }
+}
+
+/// Verifies a signature over a message that is fed in chunks, created by
+/// [`PublicKey::verifier`].
+///
+/// Like [`Signer`], only Dilithium keys with the `dilithium-internals` feature verify
+/// without buffering the message.
+#[derive(Debug, Clone)]
+pub struct Verifier {
+    inner: VerifierInner,
+}
+
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+enum VerifierInner {
+    #[cfg(feature = "dilithium")]
+    Dilithium(dilithium::Verifier),
+    Buffered {
+        public: PublicKey,
+        msg: Vec<u8>,
+    },
+}
+
+impl Verifier {
+    /// Feed the next chunk of the message.
+    pub fn update(&mut self, chunk: &[u8]) {
+        match self.inner {
+            #[cfg(feature = "dilithium")]
+            VerifierInner::Dilithium(ref mut verifier) => verifier.update(chunk),
+            VerifierInner::Buffered { ref mut msg, .. } => msg.extend_from_slice(chunk),
+        }
+    }
+
+    /// Verify `sig` over the message fed so far.
+    #[must_use]
+    pub fn finalize(self, sig: &[u8]) -> bool {
+        match self.inner {
+            #[cfg(feature = "dilithium")]
+            VerifierInner::Dilithium(verifier) => {
+                log::trace!(target: "libp2p-identity", "🛡️ Verifying streamed message with Dilithium (Post-Quantum)");
+                verifier.finalize(sig)
+            }
+            VerifierInner::Buffered { public, msg } => public.verify(&msg, sig),
+        }
+    }
+}
+
+/// A public key with its verification state precomputed, created by
+/// [`PublicKey::into_prepared`].
+///
+/// Only Dilithium keys with the `dilithium-internals` feature have state worth caching:
+/// the expanded matrix `A` and the key hash. Other keys verify exactly like
+/// [`PublicKey::verify`].
+#[derive(Debug, Clone)]
+pub struct PreparedPublicKey {
+    inner: PreparedInner,
+}
+
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+enum PreparedInner {
+    #[cfg(feature = "dilithium")]
+    Dilithium(dilithium::PreparedPublicKey),
+    Plain(PublicKey),
+}
+
+impl PreparedPublicKey {
+    /// Verify a signature on a message, with the same result as [`PublicKey::verify`].
+    #[must_use]
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
+        match self.inner {
+            #[cfg(feature = "dilithium")]
+            PreparedInner::Dilithium(ref pk) => {
+                log::trace!(target: "libp2p-identity", "🛡️ Verifying signature with prepared Dilithium key (Post-Quantum)");
+                pk.verify(msg, sig)
+            }
+            PreparedInner::Plain(ref pk) => pk.verify(msg, sig),
+        }
+    }
+
+    /// The public key this state was prepared from.
+    pub fn public(&self) -> PublicKey {
+        match self.inner {
+            #[cfg(feature = "dilithium")]
+            PreparedInner::Dilithium(ref pk) => PublicKey {
+                publickey: PublicKeyInner::Dilithium(pk.public().clone()),
+            },
+            PreparedInner::Plain(ref pk) => pk.clone(),
+        }
+    }
+}
+
+/// A decoded key blob, returned by [`Keypair::try_from_protobuf_with_kind`].
+#[derive(Debug, Clone)]
+pub enum KeypairOrPublic {
+    /// The blob held a full keypair.
+    Keypair(Keypair),
+    /// The blob held only a public key.
+    Public(PublicKey),
+}
+
+impl KeypairOrPublic {
+    /// Whether the blob held a private key.
+    pub fn has_secret(&self) -> bool {
+        matches!(self, KeypairOrPublic::Keypair(_))
+    }
+
+    /// The public key, whichever kind of blob was decoded.
+    pub fn public(&self) -> PublicKey {
+        match self {
+            KeypairOrPublic::Keypair(keypair) => keypair.public(),
+            KeypairOrPublic::Public(public) => public.clone(),
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub(crate) enum PublicKeyInner {
     /// A public Ed25519 key.
     #[cfg(feature = "ed25519")]
@@ -635,16 +2362,71 @@ pub(crate) enum PublicKeyInner {
     /// A public ECDSA key.
     #[cfg(feature = "ecdsa")]
     Ecdsa(ecdsa::PublicKey),
+    /// A public Dilithium key.
     #[cfg(feature = "dilithium")]
-    Dilithium(ml_dsa_87::PublicKey),
+    Dilithium(dilithium::PublicKey),
+    /// A public hybrid Dilithium and Ed25519 key.
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    HybridDilithiumEd25519(hybrid::PublicKey),
+    /// A public SPHINCS+ key.
+    #[cfg(feature = "sphincs-plus")]
+    SphincsPlus(sphincs_plus::PublicKey),
+    #[cfg(feature = "falcon")]
+    Falcon(falcon::PublicKey),
 }
 
 /// The public key of a node's identity keypair.
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+///
+/// The derived [`PartialEq`] stops at the first differing byte; use
+/// [`PublicKey::ct_eq`] where the timing of a comparison must not leak.
+///
+/// Public keys are ordered by the protobuf tag of their [`KeyType`], then by the key
+/// bytes of that type, so the order is stable across encode round-trips and releases.
+/// Comparing never encodes or copies the keys.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct PublicKey {
     pub(crate) publickey: PublicKeyInner,
 }
 
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PublicKey {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let by_type = self
+            .key_type()
+            .to_protobuf_tag()
+            .cmp(&other.key_type().to_protobuf_tag());
+
+        match (&self.publickey, &other.publickey) {
+            #[cfg(feature = "ed25519")]
+            (PublicKeyInner::Ed25519(a), PublicKeyInner::Ed25519(b)) => a.cmp(b),
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            (PublicKeyInner::Rsa(a), PublicKeyInner::Rsa(b)) => a.cmp(b),
+            #[cfg(feature = "secp256k1")]
+            (PublicKeyInner::Secp256k1(a), PublicKeyInner::Secp256k1(b)) => a.cmp(b),
+            #[cfg(feature = "ecdsa")]
+            (PublicKeyInner::Ecdsa(a), PublicKeyInner::Ecdsa(b)) => a.cmp(b),
+            #[cfg(feature = "dilithium")]
+            (PublicKeyInner::Dilithium(a), PublicKeyInner::Dilithium(b)) => a.cmp(b),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            (
+                PublicKeyInner::HybridDilithiumEd25519(a),
+                PublicKeyInner::HybridDilithiumEd25519(b),
+            ) => a.cmp(b),
+            #[cfg(feature = "sphincs-plus")]
+            (PublicKeyInner::SphincsPlus(a), PublicKeyInner::SphincsPlus(b)) => a.cmp(b),
+            #[cfg(feature = "falcon")]
+            (PublicKeyInner::Falcon(a), PublicKeyInner::Falcon(b)) => a.cmp(b),
+            #[allow(unreachable_patterns)]
+            _ => by_type,
+        }
+    }
+}
+
 impl PublicKey {
     /// Verify a signature for a message using this public key, i.e. check
     /// that the signature has been produced by the corresponding
@@ -654,7 +2436,10 @@ impl PublicKey {
     #[allow(unused_variables)]
     /// Verify a signature for a message using the public key.
     pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
-        match &self.publickey {
+        #[cfg(feature = "metrics")]
+        let _timer =
+            crate::metrics::Timer::start(self.key_type(), crate::metrics::CryptoOperation::Verify);
+        match self.publickey {
             #[cfg(feature = "ed25519")]
             PublicKeyInner::Ed25519(ref pk) => {
                 log::trace!(target: "libp2p-identity", "🔑 Verifying signature with Ed25519");
@@ -678,38 +2463,421 @@ impl PublicKey {
             #[cfg(feature = "dilithium")]
             PublicKeyInner::Dilithium(ref pk) => {
                 log::trace!(target: "libp2p-identity", "🛡️ Verifying signature with Dilithium (Post-Quantum)");
-                pk.verify(msg, sig, None)
+                pk.verify(msg, sig)
+            }
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(ref pk) => {
+                log::trace!(target: "libp2p-identity", "🛡️🔑 Verifying signature with hybrid Dilithium+Ed25519");
+                pk.verify(msg, sig)
+            }
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(ref pk) => {
+                log::trace!(target: "libp2p-identity", "🌳 Verifying signature with SPHINCS+ (Post-Quantum)");
+                pk.verify(msg, sig)
+            }
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(ref pk) => {
+                log::trace!(target: "libp2p-identity", "🦅 Verifying signature with Falcon (Post-Quantum)");
+                pk.verify(msg, sig)
             }
         }
     }
 
-    #[cfg(feature = "ed25519")]
-    pub fn try_into_ed25519(self) -> Result<ed25519::PublicKey, OtherVariantError> {
-        self.try_into()
+    /// Verify a signature like [`PublicKey::verify`] on hot paths where the caller has
+    /// already checked that `sig` is a signature of this key type.
+    ///
+    /// Dilithium keys are verified without copying the public key and without
+    /// logging; other key types are verified exactly as by `verify`. A signature of the
+    /// wrong length is never read out of bounds, it simply fails to verify, without the
+    /// diagnosis of [`PublicKey::verify_detailed`].
+    #[must_use]
+    pub fn verify_unchecked(&self, msg: &[u8], sig: &[u8]) -> bool {
+        match self.publickey {
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(ref pk) => pk.verify_unchecked(msg, sig),
+            #[allow(unreachable_patterns)]
+            _ => self.verify(msg, sig),
+        }
     }
 
+    /// Recover the Secp256k1 public key behind a signature and recovery id produced by
+    /// [`Keypair::sign_recoverable`].
+    ///
+    /// Recovery only fails for malformed input; a signature over another message
+    /// recovers to another key, so compare the result against the expected key or
+    /// [`PeerId`](crate::PeerId).
     #[cfg(feature = "secp256k1")]
-    pub fn try_into_secp256k1(self) -> Result<secp256k1::PublicKey, OtherVariantError> {
-        self.try_into()
-    }
-
-    #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
-    pub fn try_into_rsa(self) -> Result<rsa::PublicKey, OtherVariantError> {
-        self.try_into()
+    pub fn recover_from_signature(
+        msg: &[u8],
+        sig: &[u8],
+        recovery_id: u8,
+    ) -> Result<PublicKey, VerificationError> {
+        log::trace!(target: "libp2p-identity", "🔐 Recovering Secp256k1 public key from signature");
+        secp256k1::PublicKey::recover(msg, sig, recovery_id).map(|key| PublicKey {
+            publickey: PublicKeyInner::Secp256k1(key),
+        })
     }
 
-    #[cfg(feature = "ecdsa")]
-    pub fn try_into_ecdsa(self) -> Result<ecdsa::PublicKey, OtherVariantError> {
-        self.try_into()
-    }
+    /// Verify a blob produced by [`Keypair::sign_attached`], returning the message it
+    /// carries.
+    ///
+    /// A blob whose length prefix is malformed or longer than the rest of the blob is
+    /// reported as [`VerificationError::MalformedSignature`]; otherwise the result of
+    /// [`PublicKey::verify_detailed`] on the split signature and message is returned.
+    pub fn verify_attached(&self, blob: &[u8]) -> Result<Vec<u8>, VerificationError> {
+        let (sig_len, rest) = unsigned_varint::decode::usize(blob)
+            .map_err(|_| VerificationError::MalformedSignature)?;
+        if sig_len > rest.len() {
+            return Err(VerificationError::MalformedSignature);
+        }
+        let (sig, msg) = rest.split_at(sig_len);
 
-    #[cfg(feature = "dilithium")]
-    pub fn try_into_dilithium(self) -> Result<ml_dsa_87::PublicKey, OtherVariantError> {
-        self.try_into()
+        self.verify_detailed(msg, sig)?;
+        Ok(msg.to_vec())
     }
 
-    /// Encode the public key into a protobuf structure for storage or
-    /// exchange with other nodes.
+    /// Verify a signature like [`PublicKey::verify`], additionally rejecting malleable
+    /// ECDSA and Secp256k1 signatures whose `s` is not in low-s form.
+    ///
+    /// Signatures made by [`Keypair::sign`] are always low-s. The other key types have
+    /// no such malleability and are verified exactly as by [`PublicKey::verify`].
+    #[must_use]
+    pub fn verify_strict(&self, msg: &[u8], sig: &[u8]) -> bool {
+        match self.publickey {
+            #[cfg(feature = "secp256k1")]
+            PublicKeyInner::Secp256k1(ref pk) => {
+                log::trace!(target: "libp2p-identity", "🔐 Strictly verifying signature with Secp256k1");
+                pk.verify_strict(msg, sig)
+            }
+            #[cfg(feature = "ecdsa")]
+            PublicKeyInner::Ecdsa(ref pk) => {
+                log::trace!(target: "libp2p-identity", "🔒 Strictly verifying signature with ECDSA");
+                pk.verify_strict(msg, sig)
+            }
+            #[allow(unreachable_patterns)]
+            _ => self.verify(msg, sig),
+        }
+    }
+
+    /// Verify a signature made with [`Keypair::sign_typed`].
+    ///
+    /// A signature tagged with another key type is rejected with
+    /// [`VerificationError::KeyTypeMismatch`] before the signature bytes are looked
+    /// at. Otherwise this behaves like [`PublicKey::verify_detailed`].
+    pub fn verify_typed(&self, msg: &[u8], sig: &Signature) -> Result<(), VerificationError> {
+        let key_type = self.key_type();
+        if sig.key_type() != key_type {
+            return Err(VerificationError::KeyTypeMismatch {
+                expected: key_type,
+                actual: sig.key_type(),
+            });
+        }
+        self.verify_detailed(msg, sig.as_bytes())
+    }
+
+    /// Verify a signature made with [`Keypair::sign_with_context`] under the context `ctx`.
+    ///
+    /// With an empty context this is the same as [`PublicKey::verify`]. Contexts longer
+    /// than [`Keypair::MAX_CONTEXT_LEN`] never verify.
+    pub fn verify_with_context(&self, ctx: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+        if ctx.is_empty() {
+            return self.verify(msg, sig);
+        }
+        if ctx.len() > Keypair::MAX_CONTEXT_LEN {
+            return false;
+        }
+
+        match self.publickey {
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(ref pk) => pk.verify_with_context(ctx, msg, sig),
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(ref pk) => pk.verify_with_context(ctx, msg, sig),
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(ref pk) => pk.verify_with_context(ctx, msg, sig),
+            #[allow(unreachable_patterns)]
+            _ => context_framed(ctx, msg).is_ok_and(|framed| self.verify(&framed, sig)),
+        }
+    }
+
+    /// Verify a signature made with [`Keypair::sign_with_aad`] over `msg` and the
+    /// associated data `aad`.
+    ///
+    /// With empty AAD this is the same as [`PublicKey::verify`].
+    pub fn verify_with_aad(&self, aad: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+        if aad.is_empty() {
+            return self.verify(msg, sig);
+        }
+        self.verify_with_context(AAD_CONTEXT, &aad_framed(aad, msg), sig)
+    }
+
+    /// Like [`PublicKey::verify`], but rejects messages longer than `max_bytes` with
+    /// [`VerificationError::InputTooLarge`] before doing any cryptographic work.
+    ///
+    /// Verification hashes the whole message, so this bounds the cost a peer can impose
+    /// by sending oversized input.
+    pub fn verify_bounded(
+        &self,
+        msg: &[u8],
+        sig: &[u8],
+        max_bytes: usize,
+    ) -> Result<bool, VerificationError> {
+        if msg.len() > max_bytes {
+            return Err(VerificationError::InputTooLarge {
+                max: max_bytes,
+                actual: msg.len(),
+            });
+        }
+        Ok(self.verify(msg, sig))
+    }
+
+    /// The length in bytes of the signatures this key verifies, or `None` if they are
+    /// variable-length.
+    ///
+    /// Unlike [`KeyType::signature_len`], this takes the [`DilithiumLevel`] of
+    /// Dilithium and hybrid keys into account.
+    pub fn signature_len(&self) -> Option<usize> {
+        #[allow(unreachable_patterns)]
+        match &self.publickey {
+            #[cfg(feature = "ed25519")]
+            PublicKeyInner::Ed25519(_) => Some(64),
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(pk) => Some(pk.level().signature_len()),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(pk) => Some(pk.signature_len()),
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(_) => Some(sphincs_plus::SIGNATURE_LEN),
+            _ => None,
+        }
+    }
+
+    /// Like [`PublicKey::verify`], but reports why verification failed.
+    ///
+    /// This succeeds exactly when [`PublicKey::verify`] returns `true`. A signature of
+    /// the wrong length is reported as a [`VerificationError::KeyTypeMismatch`] if its
+    /// length is that of the fixed-length signatures of another key type, e.g. an
+    /// Ed25519 signature checked against a Dilithium key.
+    pub fn verify_detailed(&self, msg: &[u8], sig: &[u8]) -> Result<(), VerificationError> {
+        let key_type = self.key_type();
+        let mismatch_or = |err| match key_type_for_signature_len(key_type, sig.len()) {
+            Some(actual) => VerificationError::KeyTypeMismatch {
+                expected: key_type,
+                actual,
+            },
+            None => err,
+        };
+
+        if let Some(expected) = self.signature_len() {
+            if sig.len() != expected {
+                return Err(mismatch_or(VerificationError::WrongLength {
+                    expected,
+                    actual: sig.len(),
+                }));
+            }
+        }
+
+        #[allow(unreachable_patterns)]
+        let well_formed = match &self.publickey {
+            #[cfg(feature = "secp256k1")]
+            PublicKeyInner::Secp256k1(_) => secp256k1::PublicKey::is_well_formed_signature(sig),
+            #[cfg(feature = "ecdsa")]
+            PublicKeyInner::Ecdsa(_) => ecdsa::PublicKey::is_well_formed_signature(sig),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(pk) => pk.is_well_formed_signature(sig),
+            _ => true,
+        };
+        if !well_formed {
+            return Err(mismatch_or(VerificationError::MalformedSignature));
+        }
+
+        if self.verify(msg, sig) {
+            Ok(())
+        } else {
+            Err(VerificationError::InvalidSignature)
+        }
+    }
+
+    /// Verify a signature over the concatenation of `chunks`, e.g. a header and a body
+    /// held in separate buffers, without joining them.
+    ///
+    /// The result is identical to [`PublicKey::verify`] over the joined message. The
+    /// chunks are fed to a [`Verifier`], so only Dilithium keys with the
+    /// `dilithium-internals` feature avoid buffering them.
+    #[must_use]
+    #[cfg_attr(
+        not(any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "rsa",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )),
+        allow(unreachable_code, unused_mut, unused_variables)
+    )]
+    pub fn verify_chunks<'a>(
+        &self,
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+        sig: &[u8],
+    ) -> bool {
+        let mut verifier = self.verifier();
+        for chunk in chunks {
+            verifier.update(chunk);
+        }
+        verifier.finalize(sig)
+    }
+
+    /// Precompute the verification state of this key, for a key that verifies many
+    /// signatures, see [`PreparedPublicKey`].
+    pub fn into_prepared(self) -> PreparedPublicKey {
+        let inner = match self.publickey {
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(pk) => PreparedInner::Dilithium(pk.into_prepared()),
+            #[allow(unreachable_patterns)]
+            _ => PreparedInner::Plain(self),
+        };
+        PreparedPublicKey { inner }
+    }
+
+    /// Start verifying a signature over a message that is fed in chunks, see [`Verifier`].
+    pub fn verifier(&self) -> Verifier {
+        let inner = match self.publickey {
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(ref pk) => VerifierInner::Dilithium(pk.verifier()),
+            #[allow(unreachable_patterns)]
+            _ => VerifierInner::Buffered {
+                public: self.clone(),
+                msg: Vec::new(),
+            },
+        };
+        Verifier { inner }
+    }
+
+    /// Compare two public keys in constant time with respect to their key material.
+    ///
+    /// Keys of different types never compare equal; the key type itself is not secret.
+    pub fn ct_eq(&self, other: &PublicKey) -> subtle::Choice {
+        use subtle::ConstantTimeEq;
+
+        if core::mem::discriminant(&self.publickey) != core::mem::discriminant(&other.publickey) {
+            return subtle::Choice::from(0);
+        }
+
+        match (&self.publickey, &other.publickey) {
+            #[cfg(feature = "dilithium")]
+            (PublicKeyInner::Dilithium(a), PublicKeyInner::Dilithium(b)) => a.ct_eq(b),
+            #[allow(unreachable_patterns)]
+            _ => self.encode_protobuf().ct_eq(&other.encode_protobuf()),
+        }
+    }
+
+    /// Verify a batch of `(message, signature)` pairs against this public key,
+    /// returning one result per item in the same order.
+    ///
+    /// With the `dilithium-internals` feature, Dilithium keys reuse their decoded form
+    /// across the whole batch; otherwise each item is verified in turn.
+    pub fn verify_batch(&self, items: &[(&[u8], &[u8])]) -> Vec<bool> {
+        match self.publickey {
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(ref pk) => {
+                log::trace!(target: "libp2p-identity", "🛡️ Verifying batch of {} signatures with Dilithium (Post-Quantum)", items.len());
+                pk.verify_batch(items)
+            }
+            #[allow(unreachable_patterns)]
+            _ => items
+                .iter()
+                .map(|(msg, sig)| self.verify(msg, sig))
+                .collect(),
+        }
+    }
+
+    #[cfg(feature = "ed25519")]
+    pub fn try_into_ed25519(self) -> Result<ed25519::PublicKey, OtherVariantError> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "secp256k1")]
+    pub fn try_into_secp256k1(self) -> Result<secp256k1::PublicKey, OtherVariantError> {
+        self.try_into()
+    }
+
+    #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+    pub fn try_into_rsa(self) -> Result<rsa::PublicKey, OtherVariantError> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "ecdsa")]
+    pub fn try_into_ecdsa(self) -> Result<ecdsa::PublicKey, OtherVariantError> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "dilithium")]
+    pub fn try_into_dilithium(self) -> Result<dilithium::PublicKey, OtherVariantError> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "sphincs-plus")]
+    pub fn try_into_sphincs_plus(self) -> Result<sphincs_plus::PublicKey, OtherVariantError> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "falcon")]
+    pub fn try_into_falcon(self) -> Result<falcon::PublicKey, OtherVariantError> {
+        self.try_into()
+    }
+
+    /// Return the [`DilithiumLevel`] of this public key, or `None` if it is not a Dilithium key.
+    #[cfg(feature = "dilithium")]
+    pub fn dilithium_level(&self) -> Option<DilithiumLevel> {
+        match self.publickey {
+            PublicKeyInner::Dilithium(ref pk) => Some(pk.level()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Extract the `key_type` component of a hybrid public key as a standalone
+    /// [`PublicKey`], e.g. to advertise only the Dilithium half.
+    ///
+    /// The components of a hybrid signature sign a domain-separated message, so they do
+    /// not verify with [`PublicKey::verify`] under the extracted key; use
+    /// [`PublicKey::verify_hybrid_component`] to check one half of a hybrid signature.
+    ///
+    /// Returns `None` for non-hybrid keys and for key types the hybrid key does not contain.
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    pub fn extract_component(&self, key_type: KeyType) -> Option<PublicKey> {
+        match (&self.publickey, key_type) {
+            (PublicKeyInner::HybridDilithiumEd25519(pk), KeyType::Dilithium) => {
+                Some(pk.dilithium().clone().into())
+            }
+            (PublicKeyInner::HybridDilithiumEd25519(pk), KeyType::Ed25519) => {
+                Some(pk.ed25519().clone().into())
+            }
+            _ => None,
+        }
+    }
+
+    /// Verify only this key's component of a signature made by a
+    /// [`KeyType::HybridDilithiumEd25519`] keypair, for a Dilithium or Ed25519 key
+    /// obtained with [`PublicKey::extract_component`].
+    ///
+    /// This lets a peer that only trusts the post-quantum half check hybrid signatures
+    /// with the Dilithium key alone. Other key types return `false`.
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    pub fn verify_hybrid_component(&self, msg: &[u8], sig: &[u8]) -> bool {
+        match self.publickey {
+            PublicKeyInner::Dilithium(ref pk) => {
+                hybrid::PublicKey::verify_dilithium_component(pk, msg, sig)
+            }
+            PublicKeyInner::Ed25519(ref pk) => {
+                hybrid::PublicKey::verify_ed25519_component(pk, msg, sig)
+            }
+            _ => false,
+        }
+    }
+
+    /// Encode the public key into a protobuf structure for storage or
+    /// exchange with other nodes.
     pub fn encode_protobuf(&self) -> Vec<u8> {
         #[cfg(any(
             feature = "ecdsa",
@@ -717,6 +2885,8 @@ impl PublicKey {
             feature = "ed25519",
             feature = "rsa",
             feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon",
         ))]
         {
             use quick_protobuf::MessageWrite;
@@ -732,13 +2902,19 @@ impl PublicKey {
                 PublicKeyInner::Ecdsa(_) => "🔒",
                 #[cfg(feature = "dilithium")]
                 PublicKeyInner::Dilithium(_) => "🛡️",
+                #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+                PublicKeyInner::HybridDilithiumEd25519(_) => "🛡️🔑",
+                #[cfg(feature = "sphincs-plus")]
+                PublicKeyInner::SphincsPlus(_) => "🌳",
+                #[cfg(feature = "falcon")]
+                PublicKeyInner::Falcon(_) => "🦅",
             };
             log::trace!(target: "libp2p-identity", "{} Encoding public key to protobuf", emoji);
 
             let public_key = proto::PublicKey::from(self);
 
-            let mut buf = Vec::with_capacity(public_key.get_size());
-            let mut writer = Writer::new(&mut buf);
+            let mut buf = vec![0; public_key.get_size()];
+            let mut writer = Writer::new(BytesWriter::new(&mut buf));
             public_key
                 .write_message(&mut writer)
                 .expect("Encoding to succeed");
@@ -752,6 +2928,8 @@ impl PublicKey {
             feature = "ed25519",
             feature = "rsa",
             feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon",
         )))]
         unreachable!()
     }
@@ -766,9 +2944,12 @@ impl PublicKey {
             feature = "ed25519",
             feature = "rsa",
             feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon",
         ))]
         {
             use quick_protobuf::MessageRead;
+            let (key_type, _) = check_key_fields(bytes)?;
             let mut reader = BytesReader::from_bytes(bytes);
 
             let pubkey = proto::PublicKey::from_reader(&mut reader, bytes)
@@ -780,10 +2961,15 @@ impl PublicKey {
                 proto::KeyType::Secp256k1 => "🔐",
                 proto::KeyType::ECDSA => "🔒",
                 proto::KeyType::Dilithium => "🛡️",
+                proto::KeyType::HybridDilithiumEd25519 => "🛡️🔑",
+                proto::KeyType::SphincsPlus => "🌳",
+                proto::KeyType::Falcon => "🦅",
             };
             log::trace!(target: "libp2p-identity", "{} Decoding public key from protobuf", emoji);
 
-            pubkey.try_into()
+            pubkey
+                .try_into()
+                .map_err(|e: DecodingError| e.invalid_key_material(key_type))
         }
 
         #[cfg(not(any(
@@ -792,10 +2978,259 @@ impl PublicKey {
             feature = "ed25519",
             feature = "rsa",
             feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon",
         )))]
         unreachable!()
     }
 
+    /// The scheme-specific bytes of the public key, i.e. the `Data` field of
+    /// [`PublicKey::encode_protobuf`] without the key type tag.
+    ///
+    /// These bytes are not self-describing: decoding them with
+    /// [`PublicKey::from_raw_bytes`] requires knowing the key type. They are raw
+    /// Ed25519, compressed Secp256k1, Dilithium, SPHINCS+ and Falcon keys, X.509
+    /// `SubjectPublicKeyInfo` DER for RSA and ECDSA, and the length-prefixed components
+    /// of a hybrid key.
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon",
+    ))]
+    pub fn raw_bytes(&self) -> Vec<u8> {
+        proto::PublicKey::from(self).Data
+    }
+
+    /// Decode a public key of the given type from the bytes returned by
+    /// [`PublicKey::raw_bytes`].
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon",
+    ))]
+    pub fn from_raw_bytes(key_type: KeyType, bytes: &[u8]) -> Result<PublicKey, DecodingError> {
+        PublicKey::from_protobuf_data(key_type.to_protobuf_tag().into(), bytes)
+            .map_err(|e| e.invalid_key_material(key_type))
+    }
+
+    /// Encode the public key in the crate's compact format: a single byte holding the
+    /// protobuf tag of the key type, followed by [`PublicKey::raw_bytes`].
+    ///
+    /// This saves the field tags and length varints of [`PublicKey::encode_protobuf`],
+    /// but is not understood by other libp2p implementations; only use it when both
+    /// peers run this crate.
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon",
+    ))]
+    pub fn to_compact_bytes(&self) -> Vec<u8> {
+        let raw = self.raw_bytes();
+        let mut buf = Vec::with_capacity(1 + raw.len());
+        buf.push(self.key_type().to_protobuf_tag() as u8);
+        buf.extend_from_slice(&raw);
+        buf
+    }
+
+    /// Decode a public key encoded by [`PublicKey::to_compact_bytes`].
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon",
+    ))]
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<PublicKey, DecodingError> {
+        let (&tag, raw) = bytes
+            .split_first()
+            .ok_or_else(|| DecodingError::new("compact public key is empty".to_string()))?;
+        let key_type = KeyType::from_protobuf_tag(tag.into()).ok_or_else(|| {
+            DecodingError::new(alloc::format!("unknown compact public key type {tag}"))
+        })?;
+        PublicKey::from_raw_bytes(key_type, raw)
+    }
+
+    /// Decode many protobuf encoded public keys, e.g. when loading a peer store.
+    ///
+    /// Each blob is parsed in a single pass and its key material is read in place,
+    /// without the intermediate copy [`PublicKey::try_decode_protobuf`] makes. Failures
+    /// are reported per blob, with the same errors as [`PublicKey::try_decode_protobuf`].
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon",
+    ))]
+    pub fn decode_many(blobs: &[&[u8]]) -> Vec<Result<PublicKey, DecodingError>> {
+        log::trace!(target: "libp2p-identity", "Decoding {} public keys from protobuf", blobs.len());
+        blobs
+            .iter()
+            .map(|bytes| {
+                let (key_type, data) = check_key_fields(bytes)?;
+                PublicKey::from_protobuf_data(key_type.to_protobuf_tag().into(), data)
+                    .map_err(|e| e.invalid_key_material(key_type))
+            })
+            .collect()
+    }
+
+    /// Encode the public key in DER as a X.509 SubjectPublicKeyInfo structure, as
+    /// understood by TLS and X.509 tooling.
+    ///
+    /// The post-quantum key types have no standard SubjectPublicKeyInfo encoding yet
+    /// and return [`EncodingError::UnsupportedForPostQuantum`].
+    pub fn to_spki_der(&self) -> Result<Vec<u8>, EncodingError> {
+        match self.publickey {
+            #[cfg(feature = "ed25519")]
+            PublicKeyInner::Ed25519(ref pk) => Ok(pk.encode_der()),
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            PublicKeyInner::Rsa(ref pk) => Ok(pk.encode_x509()),
+            #[cfg(feature = "secp256k1")]
+            PublicKeyInner::Secp256k1(ref pk) => Ok(pk.encode_der()),
+            #[cfg(feature = "ecdsa")]
+            PublicKeyInner::Ecdsa(ref pk) => Ok(pk.encode_der()),
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(_) => {
+                Err(EncodingError::UnsupportedForPostQuantum(KeyType::Dilithium))
+            }
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(_) => Err(
+                EncodingError::UnsupportedForPostQuantum(KeyType::HybridDilithiumEd25519),
+            ),
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(_) => Err(EncodingError::UnsupportedForPostQuantum(
+                KeyType::SphincsPlus,
+            )),
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(_) => {
+                Err(EncodingError::UnsupportedForPostQuantum(KeyType::Falcon))
+            }
+        }
+    }
+
+    /// Decode a public key from a DER-encoded X.509 SubjectPublicKeyInfo structure,
+    /// as produced by [`PublicKey::to_spki_der`].
+    #[allow(unused_variables)]
+    pub fn from_spki_der(der: &[u8]) -> Result<PublicKey, DecodingError> {
+        #[cfg(feature = "ed25519")]
+        if let Ok(pk) = ed25519::PublicKey::try_decode_der(der) {
+            return Ok(pk.into());
+        }
+        #[cfg(feature = "secp256k1")]
+        if let Ok(pk) = secp256k1::PublicKey::try_decode_der(der) {
+            return Ok(pk.into());
+        }
+        #[cfg(feature = "ecdsa")]
+        if let Ok(pk) = ecdsa::PublicKey::try_decode_der(der) {
+            return Ok(pk.into());
+        }
+        #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+        if let Ok(pk) = rsa::PublicKey::try_decode_x509(der) {
+            return Ok(pk.into());
+        }
+
+        Err(DecodingError::new(
+            "unsupported or malformed X.509 SubjectPublicKeyInfo".to_string(),
+        ))
+    }
+
+    /// Encode the protobuf encoding of this public key as a multibase string in `base`.
+    #[cfg(feature = "multibase")]
+    pub fn to_multibase(&self, base: multibase::Base) -> String {
+        multibase::encode(base, self.encode_protobuf())
+    }
+
+    /// Encode this public key as a base58btc multibase string.
+    #[cfg(feature = "multibase")]
+    pub fn to_multibase_default(&self) -> String {
+        self.to_multibase(multibase::Base::Base58Btc)
+    }
+
+    /// Decode a public key from a multibase string produced by [`PublicKey::to_multibase`],
+    /// in any base.
+    #[cfg(feature = "multibase")]
+    pub fn from_multibase(s: &str) -> Result<PublicKey, DecodingError> {
+        let (_, bytes) =
+            multibase::decode(s).map_err(|e| DecodingError::failed_to_parse("multibase", e))?;
+        PublicKey::try_decode_protobuf(&bytes)
+    }
+
+    /// Encode this public key as a JSON Web Key.
+    ///
+    /// Ed25519 keys use the `OKP` key type of RFC 8037; Dilithium keys use the custom
+    /// `PQK` key type with the ML-DSA parameter set as `alg`. Other key types are
+    /// unsupported.
+    #[cfg(feature = "jwk")]
+    pub fn to_jwk(&self) -> Result<serde_json::Value, EncodingError> {
+        crate::jwk::encode(self)
+    }
+
+    /// Decode a public key from a JSON Web Key produced by [`PublicKey::to_jwk`].
+    ///
+    /// The `kty`, `crv` and `alg` members must be consistent with each other and with
+    /// the length of the key material.
+    #[cfg(feature = "jwk")]
+    pub fn from_jwk(jwk: &serde_json::Value) -> Result<PublicKey, DecodingError> {
+        crate::jwk::decode(jwk)
+    }
+
+    /// Encode the public key as a deterministic CBOR map of its protobuf key type tag
+    /// and raw key bytes, `{"key": bytes, "type": int}`, for IPLD and other content
+    /// addressed systems.
+    ///
+    /// The same key always encodes to the same bytes.
+    #[cfg(all(
+        feature = "cbor",
+        any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "rsa",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )
+    ))]
+    pub fn to_cbor(&self) -> Vec<u8> {
+        crate::cbor::encode(self)
+    }
+
+    /// Decode a public key from the CBOR produced by [`PublicKey::to_cbor`].
+    ///
+    /// Encodings other than the deterministic one, e.g. with the map keys reordered,
+    /// are rejected.
+    #[cfg(all(
+        feature = "cbor",
+        any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "rsa",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )
+    ))]
+    pub fn from_cbor(bytes: &[u8]) -> Result<PublicKey, DecodingError> {
+        crate::cbor::decode(bytes)
+    }
+
     /// Convert the [`PublicKey`] into the corresponding [`PeerId`].
     #[cfg(feature = "peerid")]
     pub fn to_peer_id(&self) -> crate::PeerId {
@@ -810,27 +3245,233 @@ impl PublicKey {
             PublicKeyInner::Ecdsa(_) => "🔒",
             #[cfg(feature = "dilithium")]
             PublicKeyInner::Dilithium(_) => "🛡️",
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(_) => "🛡️🔑",
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(_) => "🌳",
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(_) => "🦅",
         };
         log::trace!(target: "libp2p-identity", "{} Converting public key to PeerId", emoji);
 
         self.into()
     }
 
-    /// Return a [`KeyType`] of the [`PublicKey`].
-    /// Get the key type of the public key.
-    pub fn key_type(&self) -> KeyType {
-        let key_type = match &self.publickey {
-            #[cfg(feature = "ed25519")]
-            PublicKeyInner::Ed25519(_) => KeyType::Ed25519,
-            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
-            PublicKeyInner::Rsa(_) => KeyType::RSA,
-            #[cfg(feature = "secp256k1")]
-            PublicKeyInner::Secp256k1(_) => KeyType::Secp256k1,
-            #[cfg(feature = "ecdsa")]
-            PublicKeyInner::Ecdsa(_) => KeyType::Ecdsa,
-            #[cfg(feature = "dilithium")]
-            PublicKeyInner::Dilithium(_) => KeyType::Dilithium,
-        };
+    /// The `/p2p/<peer id>` multiaddr component identifying this key, ready to be
+    /// appended to a transport address, e.g. `/ip4/127.0.0.1/tcp/4001/p2p/Qm...`.
+    ///
+    /// [`peer_id_from_multiaddr_str`](crate::peer_id_from_multiaddr_str) parses it back.
+    #[cfg(feature = "peerid")]
+    pub fn to_p2p_multiaddr_component(&self) -> alloc::string::String {
+        alloc::format!("/p2p/{}", self.to_peer_id())
+    }
+
+    /// Convert the [`PublicKey`] into a [`PeerId`](crate::PeerId) using the multihash
+    /// `code`, e.g. `0x12` to hash small keys such as Ed25519 ones with SHA-256 instead
+    /// of inlining them.
+    ///
+    /// The libp2p spec only allows SHA-256 (`0x12`), and identity (`0x00`) for keys whose
+    /// protobuf encoding is at most 42 bytes. Other codes return
+    /// [`EncodingError::UnsupportedMultihash`]. [`PublicKey::to_peer_id`] keeps choosing
+    /// the code by key size.
+    #[cfg(feature = "peerid")]
+    pub fn to_peer_id_with_hash(&self, code: u64) -> Result<crate::PeerId, EncodingError> {
+        crate::PeerId::from_public_key_with_hash(self, code)
+            .ok_or(EncodingError::UnsupportedMultihash(code))
+    }
+
+    /// Write the bytes of the [`PeerId`](crate::PeerId) of this key, as returned by
+    /// `to_peer_id().to_bytes()`, into `out`, returning their length.
+    ///
+    /// Nothing is allocated for Ed25519, Secp256k1, Dilithium, SPHINCS+ and Falcon keys,
+    /// whose protobuf encoding is hashed straight from the key. The peer id takes at
+    /// most 44 bytes; a buffer too short to hold it is rejected with
+    /// [`EncodingError::BufferTooSmall`].
+    #[cfg(feature = "peerid")]
+    #[cfg_attr(
+        not(any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "rsa",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )),
+        allow(unreachable_code)
+    )]
+    pub fn write_peer_id_bytes(&self, out: &mut [u8]) -> Result<usize, EncodingError> {
+        let mut hasher = crate::PeerIdHasher::new();
+        match self.publickey {
+            #[cfg(feature = "ed25519")]
+            PublicKeyInner::Ed25519(ref key) => {
+                hash_protobuf_fields(&mut hasher, KeyType::Ed25519, &key.to_bytes())
+            }
+            #[cfg(feature = "secp256k1")]
+            PublicKeyInner::Secp256k1(ref key) => {
+                hash_protobuf_fields(&mut hasher, KeyType::Secp256k1, &key.to_bytes())
+            }
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(ref key) => {
+                hash_protobuf_fields(&mut hasher, KeyType::Dilithium, key.as_bytes())
+            }
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(ref key) => {
+                hash_protobuf_fields(&mut hasher, KeyType::SphincsPlus, &key.to_bytes())
+            }
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(ref key) => {
+                hash_protobuf_fields(&mut hasher, KeyType::Falcon, key.as_bytes())
+            }
+            #[allow(unreachable_patterns)]
+            _ => hasher.update(&self.encode_protobuf()),
+        }
+
+        hasher.finalize().write_bytes(out)
+    }
+
+    /// The multihash code [`PublicKey::to_peer_id`] uses: `0x00` (identity) if the
+    /// protobuf encoding is small enough to be inlined, as for Ed25519 keys, and `0x12`
+    /// (SHA-256) otherwise, as for Dilithium keys.
+    #[cfg(feature = "peerid")]
+    pub fn peer_id_multihash_code(&self) -> u64 {
+        crate::peer_id::multihash_code(self.encode_protobuf().len())
+    }
+
+    /// Check that `peer_id` is the [`PeerId`](crate::PeerId) of this public key.
+    ///
+    /// Keys too large to be inlined, such as Dilithium keys, are only present in
+    /// the peer id as a hash, so a key received alongside a claimed peer id has to
+    /// be checked against it by rehashing.
+    #[cfg(feature = "peerid")]
+    pub fn matches_peer_id(&self, peer_id: &crate::PeerId) -> bool {
+        crate::PeerId::from_public_key(self) == *peer_id
+    }
+
+    /// Check that `proof` was produced by [`Keypair::prove_identity`] for `challenge`
+    /// with the secret key of this public key, and that this key is the one behind
+    /// `peer_id`.
+    #[cfg(feature = "peerid")]
+    pub fn verify_identity_proof(
+        &self,
+        peer_id: &crate::PeerId,
+        challenge: &[u8],
+        proof: &crate::IdentityProof,
+    ) -> bool {
+        self.matches_peer_id(peer_id)
+            && self.verify_with_context(crate::identity_proof::CONTEXT, challenge, proof.as_bytes())
+    }
+
+    /// Number of hash bytes in a [`PublicKey::fingerprint`].
+    #[cfg(feature = "peerid")]
+    pub const DEFAULT_FINGERPRINT_LEN: usize = 16;
+
+    /// A short, stable fingerprint of this public key for display in logs, e.g.
+    /// `3f:a1:09:...`, made of the first [`DEFAULT_FINGERPRINT_LEN`](Self::DEFAULT_FINGERPRINT_LEN) bytes of the
+    /// SHA-256 of the protobuf encoding.
+    ///
+    /// Unlike the [`PeerId`](crate::PeerId), a fingerprint is not meant to identify
+    /// a node unambiguously.
+    #[cfg(feature = "peerid")]
+    pub fn fingerprint(&self) -> String {
+        self.fingerprint_with_len(Self::DEFAULT_FINGERPRINT_LEN)
+    }
+
+    /// Like [`PublicKey::fingerprint`], but made of the first `len` bytes of the hash.
+    ///
+    /// `len` is capped at 32, the length of a SHA-256 hash.
+    #[cfg(feature = "peerid")]
+    pub fn fingerprint_with_len(&self, len: usize) -> String {
+        use sha2::Digest as _;
+
+        let hash = sha2::Sha256::digest(self.encode_protobuf());
+        hash.iter()
+            .take(len)
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<Vec<_>>()
+            .join(":")
+    }
+
+    /// The default limit on the encoded length accepted by
+    /// [`PublicKey::read_length_prefixed`], enough for every supported key type,
+    /// including hybrid Dilithium+Ed25519 keys.
+    #[cfg(feature = "std")]
+    pub const DEFAULT_MAX_FRAMED_LEN: usize = 4096;
+
+    /// Write the protobuf encoding of the public key to `w`, prefixed with its length
+    /// as an unsigned varint.
+    #[cfg(feature = "std")]
+    pub fn write_length_prefixed<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        let encoded = self.encode_protobuf();
+        w.write_all(unsigned_varint::encode::usize(
+            encoded.len(),
+            &mut unsigned_varint::encode::usize_buffer(),
+        ))?;
+        w.write_all(&encoded)
+    }
+
+    /// Read a public key written by [`PublicKey::write_length_prefixed`] from `r`.
+    ///
+    /// Frames longer than [`DEFAULT_MAX_FRAMED_LEN`](Self::DEFAULT_MAX_FRAMED_LEN) are
+    /// rejected before their body is read.
+    #[cfg(feature = "std")]
+    pub fn read_length_prefixed<R: std::io::Read>(r: &mut R) -> Result<PublicKey, DecodingError> {
+        Self::read_length_prefixed_with_limit(r, Self::DEFAULT_MAX_FRAMED_LEN)
+    }
+
+    /// Like [`PublicKey::read_length_prefixed`], but rejects frames longer than `max_len`.
+    #[cfg(feature = "std")]
+    pub fn read_length_prefixed_with_limit<R: std::io::Read>(
+        r: &mut R,
+        max_len: usize,
+    ) -> Result<PublicKey, DecodingError> {
+        let len = unsigned_varint::io::read_usize(&mut *r)
+            .map_err(|e| DecodingError::failed_to_parse("public key length prefix", e))?;
+        if len > max_len {
+            return Err(DecodingError::new(format!(
+                "length-prefixed public key of {len} bytes exceeds the maximum of {max_len}"
+            )));
+        }
+
+        let mut encoded = vec![0; len];
+        r.read_exact(&mut encoded)
+            .map_err(|e| DecodingError::failed_to_parse("length-prefixed public key", e))?;
+        PublicKey::try_decode_protobuf(&encoded)
+    }
+
+    /// Return a [`KeyType`] of the [`PublicKey`].
+    /// Get the key type of the public key.
+    #[cfg_attr(
+        not(any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "rsa",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )),
+        allow(unreachable_code)
+    )]
+    pub fn key_type(&self) -> KeyType {
+        let key_type = match self.publickey {
+            #[cfg(feature = "ed25519")]
+            PublicKeyInner::Ed25519(_) => KeyType::Ed25519,
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            PublicKeyInner::Rsa(_) => KeyType::RSA,
+            #[cfg(feature = "secp256k1")]
+            PublicKeyInner::Secp256k1(_) => KeyType::Secp256k1,
+            #[cfg(feature = "ecdsa")]
+            PublicKeyInner::Ecdsa(_) => KeyType::Ecdsa,
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(_) => KeyType::Dilithium,
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(_) => KeyType::HybridDilithiumEd25519,
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(_) => KeyType::SphincsPlus,
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(_) => KeyType::Falcon,
+        };
 
         let emoji = match key_type {
             KeyType::Ed25519 => "🔑",
@@ -838,11 +3479,245 @@ impl PublicKey {
             KeyType::Secp256k1 => "🔐",
             KeyType::Ecdsa => "🔒",
             KeyType::Dilithium => "🛡️",
+            KeyType::HybridDilithiumEd25519 => "🛡️🔑",
+            KeyType::SphincsPlus => "🌳",
+            KeyType::Falcon => "🦅",
         };
 
         log::trace!(target: "libp2p-identity", "{} Public key type: {}", emoji, key_type);
         key_type
     }
+
+    /// The arcs of the algorithm OID that identifies this key in an X.509
+    /// `SubjectPublicKeyInfo`, e.g. `[1, 3, 101, 112]` for Ed25519.
+    ///
+    /// Dilithium keys return the ML-DSA OID of their level and SPHINCS+ keys that of
+    /// SLH-DSA-SHA2-256f. ECDSA and Secp256k1 keys share `id-ecPublicKey`, as the
+    /// curve is a parameter of the algorithm identifier. Falcon and hybrid keys have no
+    /// assigned OID and return `None`.
+    ///
+    /// For classical keys this is the OID that [`PublicKey::to_spki_der`] writes.
+    pub fn algorithm_oid(&self) -> Option<&'static [u32]> {
+        match self.publickey {
+            #[cfg(feature = "ed25519")]
+            PublicKeyInner::Ed25519(_) => Some(&[1, 3, 101, 112]),
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            PublicKeyInner::Rsa(_) => Some(&[1, 2, 840, 113549, 1, 1, 1]),
+            #[cfg(feature = "secp256k1")]
+            PublicKeyInner::Secp256k1(_) => Some(&[1, 2, 840, 10045, 2, 1]),
+            #[cfg(feature = "ecdsa")]
+            PublicKeyInner::Ecdsa(_) => Some(&[1, 2, 840, 10045, 2, 1]),
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(ref pk) => Some(match pk.level() {
+                DilithiumLevel::Level2 => &[2, 16, 840, 1, 101, 3, 4, 3, 17],
+                DilithiumLevel::Level3 => &[2, 16, 840, 1, 101, 3, 4, 3, 18],
+                DilithiumLevel::Level5 => &[2, 16, 840, 1, 101, 3, 4, 3, 19],
+            }),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(_) => None,
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(_) => Some(&[2, 16, 840, 1, 101, 3, 4, 3, 25]),
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(_) => None,
+        }
+    }
+}
+
+/// Verify `sig` against each of `keys` in turn, returning the index of the first key
+/// it is valid for.
+///
+/// Keys whose signatures have a fixed length other than that of `sig`, such as
+/// Ed25519 keys for a Dilithium signature, are skipped without running their backend.
+pub fn verify_against_any(keys: &[PublicKey], msg: &[u8], sig: &[u8]) -> Option<usize> {
+    keys.iter()
+        .position(|key| key.verify_detailed(msg, sig).is_ok())
+}
+
+/// Like [`verify_against_any`], but skips every key whose type differs from that of the
+/// [`Signature`].
+pub fn verify_typed_against_any(keys: &[PublicKey], msg: &[u8], sig: &Signature) -> Option<usize> {
+    keys.iter()
+        .position(|key| key.verify_typed(msg, sig).is_ok())
+}
+
+/// Check the `Type` and `Data` fields shared by `PublicKey` and `PrivateKey` messages,
+/// returning the key type and the last `Data` field, borrowed from `bytes`.
+///
+/// The generated readers silently fall back to RSA for a missing or unknown `Type`, so
+/// the fields are checked here first to report what is actually wrong with the input.
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon",
+))]
+fn check_key_fields(bytes: &[u8]) -> Result<(KeyType, &[u8]), DecodingError> {
+    let mut reader = BytesReader::from_bytes(bytes);
+    let mut tag = None;
+    let mut data = None;
+    while !reader.is_eof() {
+        match reader.next_tag(bytes) {
+            Ok(8) => {
+                tag = Some(
+                    reader
+                        .read_int32(bytes)
+                        .map_err(|_| DecodingError::TruncatedField { field: "Type" })?,
+                )
+            }
+            Ok(18) => {
+                data = Some(
+                    reader
+                        .read_bytes(bytes)
+                        .map_err(|_| DecodingError::TruncatedField { field: "Data" })?,
+                )
+            }
+            Ok(t) => reader
+                .read_unknown(bytes, t)
+                .map_err(|e| DecodingError::bad_protobuf("key bytes", e))?,
+            Err(e) => return Err(DecodingError::bad_protobuf("key bytes", e)),
+        }
+    }
+
+    let tag = tag.ok_or(DecodingError::MissingKeyType)?;
+    let key_type = KeyType::from_protobuf_tag(tag).ok_or(DecodingError::UnknownKeyType(tag))?;
+    let data = data.ok_or(DecodingError::TruncatedField { field: "Data" })?;
+
+    Ok((key_type, data))
+}
+
+/// What [`inspect_protobuf`] learns about an encoded key without decoding it.
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon",
+))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyInfo {
+    /// The key type named by the encoding.
+    pub key_type: KeyType,
+    /// Whether the encoding holds a private key rather than only a public key.
+    pub has_secret: bool,
+    /// The length of the encoded key material, without the protobuf framing.
+    pub approx_len: usize,
+}
+
+/// Read the key type of a protobuf encoded private or public key, and tell which of
+/// the two it is, e.g. to catalogue a key store.
+///
+/// Only the protobuf fields are parsed; the key material is not decoded or copied, so
+/// this also works for key types whose cargo feature is disabled. Private and public
+/// keys share the same protobuf layout and are told apart by the key material: its
+/// length for fixed-size keys, and whether the DER structure starts with a version
+/// `INTEGER` (private keys) or an algorithm identifier (`SubjectPublicKeyInfo`) for
+/// RSA and ECDSA. A blob that passes inspection may still fail to decode.
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon",
+))]
+pub fn inspect_protobuf(bytes: &[u8]) -> Result<KeyInfo, DecodingError> {
+    let (key_type, data) = check_key_fields(bytes)?;
+
+    let has_secret = match public_key_len_matches(key_type, data) {
+        Some(matches) => !matches,
+        None => der_starts_with_integer(data),
+    };
+
+    Ok(KeyInfo {
+        key_type,
+        has_secret,
+        approx_len: data.len(),
+    })
+}
+
+/// Whether `data` has the length of a raw public key of `key_type`, or `None` for RSA
+/// and ECDSA, whose DER encoded public keys vary in length.
+///
+/// For the hybrid type, the Dilithium component must have the length of one of the
+/// levels and be followed by the length-prefixed Ed25519 key.
+#[cfg(any(
+    feature = "peerid",
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon",
+))]
+fn public_key_len_matches(key_type: KeyType, data: &[u8]) -> Option<bool> {
+    match key_type {
+        KeyType::HybridDilithiumEd25519 => Some(unsigned_varint::decode::usize(data).is_ok_and(
+            |(len, rest)| {
+                KeyType::Dilithium.public_key_lens().contains(&len) && rest.len() == len + 1 + 32
+            },
+        )),
+        KeyType::RSA | KeyType::Ecdsa => None,
+        KeyType::Ed25519
+        | KeyType::Secp256k1
+        | KeyType::Dilithium
+        | KeyType::SphincsPlus
+        | KeyType::Falcon => Some(key_type.public_key_lens().contains(&data.len())),
+    }
+}
+
+/// Derive the [`PeerId`](crate::PeerId) of a public key from its raw bytes, as returned
+/// by [`PublicKey::raw_bytes`], without decoding it into a [`PublicKey`].
+///
+/// The protobuf encoding is hashed as it is produced, so the result equals
+/// [`PublicKey::to_peer_id`] of the decoded key. Only the length of `raw` is checked
+/// against `key_type`, returning [`EncodingError::InvalidKeyLength`] on a mismatch;
+/// the key material itself is not validated, and RSA and ECDSA keys, whose DER
+/// encodings vary in length, are not checked at all. This works for every key type,
+/// whether or not its cargo feature is enabled.
+#[cfg(feature = "peerid")]
+pub fn peer_id_from_public_key_bytes(
+    key_type: KeyType,
+    raw: &[u8],
+) -> Result<crate::PeerId, EncodingError> {
+    if public_key_len_matches(key_type, raw) == Some(false) {
+        return Err(EncodingError::InvalidKeyLength {
+            key_type,
+            len: raw.len(),
+        });
+    }
+
+    let mut hasher = crate::PeerIdHasher::new();
+    hash_protobuf_fields(&mut hasher, key_type, raw);
+    Ok(hasher.finalize())
+}
+
+/// Whether `der` is a DER `SEQUENCE` whose first element is an `INTEGER`.
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon",
+))]
+fn der_starts_with_integer(der: &[u8]) -> bool {
+    const SEQUENCE: u8 = 0x30;
+    const INTEGER: u8 = 0x02;
+
+    let header_len = match der {
+        [SEQUENCE, len, ..] if *len < 0x80 => 2,
+        [SEQUENCE, len, ..] => 2 + usize::from(len & 0x7f),
+        _ => return false,
+    };
+    der.get(header_len) == Some(&INTEGER)
 }
 
 #[cfg(any(
@@ -851,69 +3726,120 @@ impl PublicKey {
     feature = "ed25519",
     feature = "rsa",
     feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon",
 ))]
 impl TryFrom<proto::PublicKey> for PublicKey {
     type Error = DecodingError;
 
     fn try_from(pubkey: proto::PublicKey) -> Result<Self, Self::Error> {
-        match pubkey.Type {
+        PublicKey::from_protobuf_data(pubkey.Type, &pubkey.Data)
+    }
+}
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon",
+))]
+impl PublicKey {
+    /// Decode the `Data` field of a protobuf `PublicKey` of the given type.
+    fn from_protobuf_data(key_type: proto::KeyType, data: &[u8]) -> Result<Self, DecodingError> {
+        match key_type {
             #[cfg(feature = "ed25519")]
-            proto::KeyType::Ed25519 => Ok(ed25519::PublicKey::try_from_bytes(&pubkey.Data).map(
-                |kp| PublicKey {
-                    publickey: PublicKeyInner::Ed25519(kp),
-                },
-            )?),
+            proto::KeyType::Ed25519 => {
+                Ok(
+                    ed25519::PublicKey::try_from_bytes(data).map(|kp| PublicKey {
+                        publickey: PublicKeyInner::Ed25519(kp),
+                    })?,
+                )
+            }
             #[cfg(not(feature = "ed25519"))]
             proto::KeyType::Ed25519 => {
                 log::debug!("support for ed25519 was disabled at compile-time");
-                Err(DecodingError::missing_feature("ed25519"))
+                Err(DecodingError::disabled_key_type(KeyType::Ed25519))
             }
             #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
             proto::KeyType::RSA => {
-                Ok(
-                    rsa::PublicKey::try_decode_x509(&pubkey.Data).map(|kp| PublicKey {
-                        publickey: PublicKeyInner::Rsa(kp),
-                    })?,
-                )
+                Ok(rsa::PublicKey::try_decode_x509(data).map(|kp| PublicKey {
+                    publickey: PublicKeyInner::Rsa(kp),
+                })?)
             }
             #[cfg(any(not(feature = "rsa"), target_arch = "wasm32"))]
             proto::KeyType::RSA => {
                 log::debug!("support for RSA was disabled at compile-time");
-                Err(DecodingError::missing_feature("rsa"))
+                Err(DecodingError::disabled_key_type(KeyType::RSA))
             }
             #[cfg(feature = "secp256k1")]
-            proto::KeyType::Secp256k1 => Ok(secp256k1::PublicKey::try_from_bytes(&pubkey.Data)
-                .map(|kp| PublicKey {
+            proto::KeyType::Secp256k1 => Ok(secp256k1::PublicKey::try_from_bytes(data).map(
+                |kp| PublicKey {
                     publickey: PublicKeyInner::Secp256k1(kp),
-                })?),
+                },
+            )?),
             #[cfg(not(feature = "secp256k1"))]
             proto::KeyType::Secp256k1 => {
                 log::debug!("support for secp256k1 was disabled at compile-time");
-                Err(DecodingError::missing_feature("secp256k1"))
+                Err(DecodingError::disabled_key_type(KeyType::Secp256k1))
             }
             #[cfg(feature = "ecdsa")]
-            proto::KeyType::ECDSA => Ok(ecdsa::PublicKey::try_decode_der(&pubkey.Data).map(
-                |kp| PublicKey {
+            proto::KeyType::ECDSA => {
+                Ok(ecdsa::PublicKey::try_decode_der(data).map(|kp| PublicKey {
                     publickey: PublicKeyInner::Ecdsa(kp),
-                },
-            )?),
+                })?)
+            }
             #[cfg(not(feature = "ecdsa"))]
             proto::KeyType::ECDSA => {
                 log::debug!("support for ECDSA was disabled at compile-time");
-                Err(DecodingError::missing_feature("ecdsa"))
+                Err(DecodingError::disabled_key_type(KeyType::Ecdsa))
             }
             #[cfg(feature = "dilithium")]
-            proto::KeyType::Dilithium => Ok(ml_dsa_87::PublicKey::from_bytes(&pubkey.Data)
-                .map(|kp| PublicKey {
+            proto::KeyType::Dilithium => Ok(dilithium::PublicKey::try_from_bytes(data).map(
+                |kp| PublicKey {
                     publickey: PublicKeyInner::Dilithium(kp),
-                })
-                .map_err(|e| {
-                    DecodingError::new(format!("Unable to decode dilithium pubkey protobuf: {}", e))
-                })?),
+                },
+            )?),
             #[cfg(not(feature = "dilithium"))]
             proto::KeyType::Dilithium => {
                 log::debug!("support for Dilithium was disabled at compile-time");
-                Err(DecodingError::missing_feature("dilithium"))
+                Err(DecodingError::disabled_key_type(KeyType::Dilithium))
+            }
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            proto::KeyType::HybridDilithiumEd25519 => Ok(hybrid::PublicKey::try_from_bytes(data)
+                .map(|kp| PublicKey {
+                publickey: PublicKeyInner::HybridDilithiumEd25519(kp),
+            })?),
+            #[cfg(not(all(feature = "dilithium", feature = "ed25519")))]
+            proto::KeyType::HybridDilithiumEd25519 => {
+                log::debug!("support for hybrid Dilithium+Ed25519 was disabled at compile-time");
+                Err(DecodingError::disabled_key_type(
+                    KeyType::HybridDilithiumEd25519,
+                ))
+            }
+            #[cfg(feature = "sphincs-plus")]
+            proto::KeyType::SphincsPlus => Ok(sphincs_plus::PublicKey::try_from_bytes(data).map(
+                |kp| PublicKey {
+                    publickey: PublicKeyInner::SphincsPlus(kp),
+                },
+            )?),
+            #[cfg(feature = "falcon")]
+            proto::KeyType::Falcon => {
+                Ok(falcon::PublicKey::try_from_bytes(data).map(|kp| PublicKey {
+                    publickey: PublicKeyInner::Falcon(kp),
+                })?)
+            }
+            #[cfg(not(feature = "sphincs-plus"))]
+            proto::KeyType::SphincsPlus => {
+                log::debug!("support for SPHINCS+ was disabled at compile-time");
+                Err(DecodingError::disabled_key_type(KeyType::SphincsPlus))
+            }
+            #[cfg(not(feature = "falcon"))]
+            proto::KeyType::Falcon => {
+                log::debug!("support for Falcon was disabled at compile-time");
+                Err(DecodingError::disabled_key_type(KeyType::Falcon))
             }
         }
     }
@@ -934,6 +3860,16 @@ impl TryInto<ed25519::PublicKey> for PublicKey {
             PublicKeyInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
             #[cfg(feature = "dilithium")]
             PublicKeyInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
         }
     }
 }
@@ -953,6 +3889,16 @@ impl TryInto<ecdsa::PublicKey> for PublicKey {
             PublicKeyInner::Secp256k1(_) => Err(OtherVariantError::new(crate::KeyType::Secp256k1)),
             #[cfg(feature = "dilithium")]
             PublicKeyInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
         }
     }
 }
@@ -972,6 +3918,16 @@ impl TryInto<secp256k1::PublicKey> for PublicKey {
             PublicKeyInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
             #[cfg(feature = "dilithium")]
             PublicKeyInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
         }
     }
 }
@@ -991,15 +3947,25 @@ impl TryInto<rsa::PublicKey> for PublicKey {
             PublicKeyInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
             #[cfg(feature = "dilithium")]
             PublicKeyInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
         }
     }
 }
 
-#[cfg(all(feature = "dilithium"))]
-impl TryInto<ml_dsa_87::PublicKey> for PublicKey {
+#[cfg(feature = "dilithium")]
+impl TryInto<dilithium::PublicKey> for PublicKey {
     type Error = OtherVariantError;
 
-    fn try_into(self) -> Result<ml_dsa_87::PublicKey, Self::Error> {
+    fn try_into(self) -> Result<dilithium::PublicKey, Self::Error> {
         match self.publickey {
             #[cfg(feature = "rsa")]
             PublicKeyInner::Rsa(inner) => Err(OtherVariantError::new(crate::KeyType::RSA)),
@@ -1011,6 +3977,72 @@ impl TryInto<ml_dsa_87::PublicKey> for PublicKey {
             PublicKeyInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
             #[cfg(feature = "dilithium")]
             PublicKeyInner::Dilithium(inner) => Ok(inner),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
+        }
+    }
+}
+
+#[cfg(feature = "sphincs-plus")]
+impl TryInto<sphincs_plus::PublicKey> for PublicKey {
+    type Error = OtherVariantError;
+
+    fn try_into(self) -> Result<sphincs_plus::PublicKey, Self::Error> {
+        match self.publickey {
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            PublicKeyInner::Rsa(_) => Err(OtherVariantError::new(crate::KeyType::RSA)),
+            #[cfg(feature = "ed25519")]
+            PublicKeyInner::Ed25519(_) => Err(OtherVariantError::new(crate::KeyType::Ed25519)),
+            #[cfg(feature = "secp256k1")]
+            PublicKeyInner::Secp256k1(_) => Err(OtherVariantError::new(crate::KeyType::Secp256k1)),
+            #[cfg(feature = "ecdsa")]
+            PublicKeyInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            PublicKeyInner::SphincsPlus(inner) => Ok(inner),
+            #[cfg(feature = "falcon")]
+            PublicKeyInner::Falcon(_) => Err(OtherVariantError::new(crate::KeyType::Falcon)),
+        }
+    }
+}
+
+#[cfg(feature = "falcon")]
+impl TryInto<falcon::PublicKey> for PublicKey {
+    type Error = OtherVariantError;
+
+    fn try_into(self) -> Result<falcon::PublicKey, Self::Error> {
+        match self.publickey {
+            #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
+            PublicKeyInner::Rsa(_) => Err(OtherVariantError::new(crate::KeyType::RSA)),
+            #[cfg(feature = "ed25519")]
+            PublicKeyInner::Ed25519(_) => Err(OtherVariantError::new(crate::KeyType::Ed25519)),
+            #[cfg(feature = "secp256k1")]
+            PublicKeyInner::Secp256k1(_) => Err(OtherVariantError::new(crate::KeyType::Secp256k1)),
+            #[cfg(feature = "ecdsa")]
+            PublicKeyInner::Ecdsa(_) => Err(OtherVariantError::new(crate::KeyType::Ecdsa)),
+            #[cfg(feature = "dilithium")]
+            PublicKeyInner::Dilithium(_) => Err(OtherVariantError::new(crate::KeyType::Dilithium)),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            PublicKeyInner::HybridDilithiumEd25519(_) => Err(OtherVariantError::new(
+                crate::KeyType::HybridDilithiumEd25519,
+            )),
+            #[cfg(feature = "sphincs-plus")]
+            PublicKeyInner::SphincsPlus(_) => {
+                Err(OtherVariantError::new(crate::KeyType::SphincsPlus))
+            }
+            PublicKeyInner::Falcon(inner) => Ok(inner),
         }
     }
 }
@@ -1052,21 +4084,258 @@ impl From<rsa::PublicKey> for PublicKey {
 }
 
 #[cfg(feature = "dilithium")]
-impl From<ml_dsa_87::PublicKey> for PublicKey {
-    fn from(key: ml_dsa_87::PublicKey) -> Self {
+impl From<dilithium::PublicKey> for PublicKey {
+    fn from(key: dilithium::PublicKey) -> Self {
         PublicKey {
-            publickey: PublicKeyInner::Dilithium(key.clone()),
+            publickey: PublicKeyInner::Dilithium(key),
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[cfg(all(feature = "dilithium", feature = "ed25519"))]
+impl From<hybrid::PublicKey> for PublicKey {
+    fn from(key: hybrid::PublicKey) -> Self {
+        PublicKey {
+            publickey: PublicKeyInner::HybridDilithiumEd25519(key),
+        }
+    }
+}
 
-    #[test]
-    #[cfg(feature = "ed25519")]
-    #[cfg(feature = "peerid")]
+#[cfg(feature = "sphincs-plus")]
+impl From<sphincs_plus::PublicKey> for PublicKey {
+    fn from(key: sphincs_plus::PublicKey) -> Self {
+        PublicKey {
+            publickey: PublicKeyInner::SphincsPlus(key),
+        }
+    }
+}
+
+#[cfg(feature = "falcon")]
+impl From<falcon::PublicKey> for PublicKey {
+    fn from(key: falcon::PublicKey) -> Self {
+        PublicKey {
+            publickey: PublicKeyInner::Falcon(key),
+        }
+    }
+}
+
+#[cfg(feature = "dilithium")]
+impl From<ml_dsa_87::PublicKey> for PublicKey {
+    fn from(key: ml_dsa_87::PublicKey) -> Self {
+        PublicKey {
+            publickey: PublicKeyInner::Dilithium(key.into()),
+        }
+    }
+}
+
+impl From<&Keypair> for PublicKey {
+    fn from(keypair: &Keypair) -> Self {
+        keypair.public()
+    }
+}
+
+impl TryFrom<&[u8]> for PublicKey {
+    type Error = DecodingError;
+
+    /// Decode a protobuf encoded public key, see [`PublicKey::try_decode_protobuf`].
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        PublicKey::try_decode_protobuf(bytes)
+    }
+}
+
+/// Feed the protobuf encoding of a public key of type `key_type` with the given `Data`
+/// field to `hasher`, without building it in memory.
+#[cfg(feature = "peerid")]
+fn hash_protobuf_fields(hasher: &mut crate::PeerIdHasher, key_type: KeyType, data: &[u8]) {
+    let mut tag_buf = unsigned_varint::encode::u64_buffer();
+    let mut len_buf = unsigned_varint::encode::usize_buffer();
+
+    hasher.update(&[0x08]);
+    hasher.update(unsigned_varint::encode::u64(
+        key_type.to_protobuf_tag() as u64,
+        &mut tag_buf,
+    ));
+    hasher.update(&[0x12]);
+    hasher.update(unsigned_varint::encode::usize(data.len(), &mut len_buf));
+    hasher.update(data);
+}
+
+/// Prepended, with the context, to the message signed by
+/// [`Keypair::sign_with_context`] for key types without a native context field.
+const CONTEXT_DOMAIN_SEPARATION: &[u8] = b"libp2p-pqc-context";
+
+/// Frame `msg` as `CONTEXT_DOMAIN_SEPARATION || len(ctx) || ctx || msg`, rejecting
+/// contexts whose length does not fit the single length byte.
+fn context_framed(ctx: &[u8], msg: &[u8]) -> Result<Vec<u8>, SigningError> {
+    let ctx_len = u8::try_from(ctx.len()).map_err(|_| SigningError::ContextTooLong(ctx.len()))?;
+
+    let mut buf = Vec::with_capacity(CONTEXT_DOMAIN_SEPARATION.len() + 1 + ctx.len() + msg.len());
+    buf.extend_from_slice(CONTEXT_DOMAIN_SEPARATION);
+    buf.push(ctx_len);
+    buf.extend_from_slice(ctx);
+    buf.extend_from_slice(msg);
+    Ok(buf)
+}
+
+/// The context under which [`Keypair::sign_with_aad`] signs the framed message.
+const AAD_CONTEXT: &[u8] = b"libp2p-pqc-aad";
+
+/// Frame `msg` as `varint(len(aad)) || aad || msg`, so that moving bytes between the
+/// AAD and the message changes what is signed.
+fn aad_framed(aad: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut len_buf = unsigned_varint::encode::usize_buffer();
+    let len = unsigned_varint::encode::usize(aad.len(), &mut len_buf);
+
+    let mut buf = Vec::with_capacity(len.len() + aad.len() + msg.len());
+    buf.extend_from_slice(len);
+    buf.extend_from_slice(aad);
+    buf.extend_from_slice(msg);
+    buf
+}
+
+/// The key type other than `own` whose fixed-length signatures are `len` bytes long, if any.
+fn key_type_for_signature_len(own: KeyType, len: usize) -> Option<KeyType> {
+    let candidates = [
+        KeyType::Ed25519,
+        KeyType::Dilithium,
+        KeyType::HybridDilithiumEd25519,
+        KeyType::SphincsPlus,
+    ]
+    .map(|key_type| (key_type, key_type.signature_lens().contains(&len)));
+
+    candidates
+        .into_iter()
+        .find(|&(key_type, matches)| matches && key_type != own)
+        .map(|(key_type, _)| key_type)
+}
+
+#[cfg(all(
+    feature = "serde",
+    any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
+    )
+))]
+mod serde_impls {
+    //! Serde support for [`Keypair`] and [`PublicKey`] through their protobuf encoding,
+    //! written as base64 for human-readable formats and as raw bytes otherwise.
+
+    use super::{Keypair, PublicKey};
+    use crate::error::DecodingError;
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use core::fmt;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use zeroize::Zeroizing;
+
+    fn serialize_protobuf<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&Zeroizing::new(STANDARD.encode(bytes)))
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+
+    fn deserialize_protobuf<'de, D, T>(
+        deserializer: D,
+        what: &'static str,
+        decode: fn(&[u8]) -> Result<T, DecodingError>,
+    ) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ProtobufVisitor<T> {
+            what: &'static str,
+            decode: fn(&[u8]) -> Result<T, DecodingError>,
+        }
+
+        impl<T> de::Visitor<'_> for ProtobufVisitor<T> {
+            type Value = T;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a protobuf encoded {}", self.what)
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                (self.decode)(v).map_err(E::custom)
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let bytes = Zeroizing::new(STANDARD.decode(v).map_err(E::custom)?);
+                (self.decode)(&bytes).map_err(E::custom)
+            }
+        }
+
+        let visitor = ProtobufVisitor { what, decode };
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(visitor)
+        } else {
+            deserializer.deserialize_bytes(visitor)
+        }
+    }
+
+    impl Serialize for PublicKey {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serialize_protobuf(&self.encode_protobuf(), serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for PublicKey {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize_protobuf(deserializer, "public key", PublicKey::try_decode_protobuf)
+        }
+    }
+
+    impl Serialize for Keypair {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            use serde::ser::Error as _;
+
+            let bytes = self
+                .to_protobuf_encoding_zeroizing()
+                .map_err(S::Error::custom)?;
+            serialize_protobuf(&bytes, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Keypair {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize_protobuf(deserializer, "keypair", Keypair::from_protobuf_encoding)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "ed25519")]
+    #[cfg(feature = "peerid")]
     fn keypair_protobuf_roundtrip_ed25519() {
         let priv_key = Keypair::from_protobuf_encoding(&hex_literal::hex!(
             "080112407e0830617c4a7de83925dfb2694556b12936c477a0e1feb2e148ec9da60fee7d1ed1e8fae2c4a144b8be8fd4b47bf3d3b34b871c3cacf6010f0e42d474fce27e"
@@ -1138,7 +4407,77 @@ mod tests {
         );
     }
 
-    #[cfg(feature = "peerid")]
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn cloned_dilithium_keypair_is_independent() {
+        let keypair = Keypair::generate_dilithium();
+        let public = keypair.public();
+        let clone = keypair.clone();
+        assert_eq!(clone, keypair);
+
+        let msg = b"hello world";
+        let sig = keypair.sign(msg).unwrap();
+        drop(keypair);
+
+        let clone_sig = clone.sign(msg).unwrap();
+        assert!(public.verify(msg, &sig));
+        assert!(public.verify(msg, &clone_sig));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn keypair_equality() {
+        let keypair = Keypair::generate_dilithium();
+        let decoded =
+            Keypair::from_protobuf_encoding(&keypair.to_protobuf_encoding().unwrap()).unwrap();
+        assert_eq!(decoded, keypair);
+        assert!(bool::from(decoded.ct_eq(&keypair)));
+
+        let level2 = Keypair::generate_dilithium_level(DilithiumLevel::Level2);
+        assert_ne!(Keypair::generate_dilithium(), keypair);
+        assert_ne!(level2, keypair);
+
+        let mut successor = keypair.clone();
+        successor.set_supersedes(b"previous key fingerprint");
+        assert!(bool::from(successor.ct_eq(&keypair)));
+        assert_ne!(successor, keypair);
+
+        #[cfg(feature = "ed25519")]
+        assert_ne!(Keypair::generate_ed25519(), keypair);
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn hex_roundtrip() {
+        let keypair = Keypair::generate_dilithium();
+        let hex = keypair.to_hex().unwrap();
+
+        assert_eq!(Keypair::from_hex(&hex).unwrap(), keypair);
+        assert_eq!(
+            Keypair::from_hex(&format!("  0x{}\n", hex.as_str())).unwrap(),
+            keypair
+        );
+        assert!(Keypair::from_hex(&hex[1..]).is_err());
+        assert!(Keypair::from_hex(&hex.replace('a', "g")).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn zeroizing_protobuf_encoding() {
+        let keypair = Keypair::generate_dilithium();
+
+        let encoded: zeroize::Zeroizing<Vec<u8>> =
+            keypair.to_protobuf_encoding_zeroizing().unwrap();
+        assert_eq!(*encoded, keypair.to_protobuf_encoding().unwrap());
+
+        let decoded = Keypair::from_protobuf_encoding(&encoded).unwrap();
+        assert_eq!(keypair.secret(), decoded.secret());
+    }
+
+    #[cfg(all(
+        feature = "peerid",
+        any(feature = "ed25519", feature = "ecdsa", feature = "secp256k1")
+    ))]
     fn roundtrip_protobuf_encoding(keypair: &Keypair, public_key: &PublicKey, tpe: KeyType) {
         assert_eq!(&keypair.public(), public_key);
 
@@ -1162,6 +4501,56 @@ mod tests {
         assert_eq!(keypair.key_type(), tpe)
     }
 
+    #[test]
+    #[cfg(all(
+        feature = "dilithium",
+        not(any(
+            feature = "ed25519",
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "rsa"
+        ))
+    ))]
+    fn classical_protobuf_is_rejected_as_disabled() {
+        use quick_protobuf::MessageWrite;
+
+        fn encode(message: impl MessageWrite) -> Vec<u8> {
+            let mut buf = vec![0; message.get_size()];
+            message
+                .write_message(&mut Writer::new(BytesWriter::new(&mut buf)))
+                .unwrap();
+            buf
+        }
+
+        for tpe in [
+            proto::KeyType::Ed25519,
+            proto::KeyType::RSA,
+            proto::KeyType::Secp256k1,
+            proto::KeyType::ECDSA,
+        ] {
+            let key_type = KeyType::from_protobuf_tag(tpe as i32).unwrap();
+
+            let private = encode(proto::PrivateKey {
+                Type: tpe,
+                Data: vec![1; 32],
+                PreviousKeyFingerprint: None,
+            });
+            assert!(matches!(
+                Keypair::from_protobuf_encoding(&private),
+                Err(DecodingError::DisabledKeyType(t)) if t == key_type
+            ));
+
+            let public = encode(proto::PublicKey {
+                Type: tpe,
+                Data: vec![2; 33],
+            });
+            assert!(matches!(
+                PublicKey::try_decode_protobuf(&public),
+                Err(DecodingError::DisabledKeyType(t)) if t == key_type
+            ));
+        }
+    }
+
     #[test]
     // NOTE: not sure what other feature is supposed to be here, the original didn't specify
     #[cfg(all(feature = "secp256k1", feature = "peerid"))]
@@ -1240,6 +4629,272 @@ mod tests {
         assert_eq!(converted_pubkey.key_type(), KeyType::Ecdsa)
     }
 
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn previous_key_fingerprint_roundtrip() {
+        let previous = Keypair::generate_dilithium();
+        let mut keypair = Keypair::generate_dilithium();
+        assert_eq!(keypair.previous_key_fingerprint(), None);
+
+        let fp = previous.public().to_peer_id().to_bytes();
+        keypair.set_supersedes(&fp);
+        assert_eq!(keypair.previous_key_fingerprint(), Some(fp.as_slice()));
+
+        let decoded =
+            Keypair::from_protobuf_encoding(&keypair.to_protobuf_encoding().unwrap()).unwrap();
+        assert_eq!(decoded.previous_key_fingerprint(), Some(fp.as_slice()));
+        assert_eq!(decoded.public(), keypair.public());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn decode_keypair_without_previous_key_fingerprint() {
+        let data = dilithium::Keypair::generate(DilithiumLevel::default()).to_bytes();
+        let keypair = Keypair::dilithium_from_bytes(&data).unwrap();
+
+        // `Type` and `Data` only, as written before the field was introduced.
+        let mut old_format = vec![0x08, proto::KeyType::Dilithium as u8, 0x12];
+        let mut len_buf = unsigned_varint::encode::usize_buffer();
+        old_format.extend_from_slice(unsigned_varint::encode::usize(data.len(), &mut len_buf));
+        old_format.extend_from_slice(&data);
+
+        let decoded = Keypair::from_protobuf_encoding(&old_format).unwrap();
+        assert_eq!(decoded.previous_key_fingerprint(), None);
+        assert_eq!(decoded.public(), keypair.public());
+
+        // Without a fingerprint the encoding is unchanged.
+        assert_eq!(keypair.to_protobuf_encoding().unwrap(), old_format);
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn keypair_protobuf_roundtrip_dilithium_levels() {
+        for level in [
+            DilithiumLevel::Level2,
+            DilithiumLevel::Level3,
+            DilithiumLevel::Level5,
+        ] {
+            let keypair = Keypair::generate_dilithium_level(level);
+            assert_eq!(keypair.key_type(), KeyType::Dilithium);
+            assert_eq!(keypair.dilithium_level(), Some(level));
+
+            let decoded_keypair =
+                Keypair::from_protobuf_encoding(&keypair.to_protobuf_encoding().unwrap()).unwrap();
+            assert_eq!(decoded_keypair.dilithium_level(), Some(level));
+            assert_eq!(keypair.secret(), decoded_keypair.secret());
+
+            let decoded_public =
+                PublicKey::try_decode_protobuf(&keypair.public().encode_protobuf()).unwrap();
+            assert_eq!(decoded_public.dilithium_level(), Some(level));
+            assert_eq!(keypair.public(), decoded_public);
+
+            let msg = b"hello world";
+            let sig = decoded_keypair.sign(msg).unwrap();
+            assert!(decoded_public.verify(msg, &sig));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "dilithium-internals"))]
+    fn dilithium_secret_bytes_roundtrip() {
+        for level in [
+            DilithiumLevel::Level2,
+            DilithiumLevel::Level3,
+            DilithiumLevel::Level5,
+        ] {
+            let original = Keypair::generate_dilithium_level(level);
+            let secret = original.secret_to_bytes().unwrap();
+            assert_eq!(secret.len(), level.secret_key_len());
+
+            let restored = Keypair::from_secret_bytes(KeyType::Dilithium, &secret).unwrap();
+            assert_eq!(restored.public(), original.public());
+
+            let msg = b"signed by the restored keypair";
+            let sig = restored.sign(msg).unwrap();
+            assert!(original.public().verify(msg, &sig));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", not(feature = "dilithium-internals")))]
+    fn dilithium_secret_bytes_need_internals() {
+        let secret = Keypair::generate_dilithium().secret_to_bytes().unwrap();
+
+        assert!(Keypair::from_secret_bytes(KeyType::Dilithium, &secret).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn dilithium_secret_bytes_wrong_length() {
+        let secret = Keypair::generate_dilithium().secret_to_bytes().unwrap();
+
+        assert!(Keypair::from_secret_bytes(KeyType::Dilithium, &secret[1..]).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ed25519", feature = "rand"))]
+    fn ed25519_secret_bytes_roundtrip() {
+        let original = Keypair::generate_ed25519();
+        let secret = original.secret_to_bytes().unwrap();
+
+        let restored = Keypair::from_secret_bytes(KeyType::Ed25519, &secret).unwrap();
+
+        let msg = b"signed by the restored keypair";
+        let sig = restored.sign(msg).unwrap();
+        assert!(original.public().verify(msg, &sig));
+    }
+
+    #[test]
+    #[cfg(all(feature = "secp256k1", feature = "rand"))]
+    fn secp256k1_secret_bytes_roundtrip() {
+        let original = Keypair::generate_secp256k1();
+        let secret = original.secret_to_bytes().unwrap();
+
+        let restored = Keypair::from_secret_bytes(KeyType::Secp256k1, &secret).unwrap();
+
+        let msg = b"signed by the restored keypair";
+        let sig = restored.sign(msg).unwrap();
+        assert!(original.public().verify(msg, &sig));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn verify_batch_reports_each_item() {
+        let keypair = Keypair::generate_dilithium();
+        let other = Keypair::generate_dilithium();
+        let good = keypair.sign(b"good").unwrap();
+        let foreign = other.sign(b"good").unwrap();
+
+        let results = keypair.public().verify_batch(&[
+            (b"good", &good),
+            (b"good", &foreign),
+            (b"bad", &good),
+        ]);
+
+        assert_eq!(results, vec![true, false, false]);
+        assert!(keypair.public().verify_batch(&[]).is_empty());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ed25519", feature = "rand"))]
+    fn verify_batch_ed25519() {
+        let keypair = Keypair::generate_ed25519();
+        let sig = keypair.sign(b"good").unwrap();
+
+        let results = keypair
+            .public()
+            .verify_batch(&[(b"good", &sig), (b"bad", &sig)]);
+
+        assert_eq!(results, vec![true, false]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "pem", feature = "dilithium"))]
+    fn dilithium_pem_roundtrip() {
+        let keypair = Keypair::generate_dilithium();
+        let pem = keypair.to_pem().unwrap();
+
+        assert!(pem.starts_with("-----BEGIN LIBP2P PRIVATE KEY-----\nKey-Type: Dilithium\n"));
+
+        let decoded = Keypair::from_pem(&pem.replace('\n', "\r\n")).unwrap();
+        assert_eq!(decoded.public(), keypair.public());
+    }
+
+    #[test]
+    #[cfg(all(feature = "pem", feature = "ed25519", feature = "rand"))]
+    fn ed25519_pem_roundtrip() {
+        let keypair = Keypair::generate_ed25519();
+        let pem = keypair.to_pem().unwrap();
+
+        let decoded = Keypair::from_pem(&format!("{}   \n", *pem)).unwrap();
+        assert_eq!(decoded.public(), keypair.public());
+    }
+
+    #[test]
+    #[cfg(all(feature = "identity-file", feature = "dilithium"))]
+    fn dilithium_identity_file_roundtrip() {
+        let keypair = Keypair::generate_dilithium();
+        let file = keypair.to_identity_file().unwrap();
+
+        let decoded = Keypair::from_identity_file(&file).unwrap();
+        assert_eq!(decoded.public(), keypair.public());
+
+        let mut corrupted = file.clone();
+        corrupted[100] ^= 1;
+        assert!(Keypair::from_identity_file(&corrupted).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "pem", feature = "dilithium", feature = "ed25519"))]
+    fn pem_key_type_mismatch_is_rejected() {
+        let pem = Keypair::generate_dilithium()
+            .to_pem()
+            .unwrap()
+            .replace("Key-Type: Dilithium", "Key-Type: Ed25519");
+
+        assert!(Keypair::from_pem(&pem).is_err());
+    }
+
+    #[test]
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
+    ))]
+    fn ct_eq_agrees_with_eq() {
+        #[allow(unused_mut)]
+        let mut keys: Vec<PublicKey> = Vec::new();
+        #[cfg(feature = "dilithium")]
+        keys.extend([
+            Keypair::generate_dilithium().public(),
+            Keypair::generate_dilithium().public(),
+            Keypair::generate_dilithium_level(DilithiumLevel::Level2).public(),
+        ]);
+        #[cfg(all(feature = "ed25519", feature = "rand"))]
+        keys.extend([
+            Keypair::generate_ed25519().public(),
+            Keypair::generate_ed25519().public(),
+        ]);
+        #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+        keys.extend([
+            Keypair::generate_hybrid_dilithium_ed25519().public(),
+            Keypair::generate_hybrid_dilithium_ed25519().public(),
+        ]);
+        #[cfg(all(feature = "secp256k1", feature = "rand"))]
+        keys.extend([
+            Keypair::generate_secp256k1().public(),
+            Keypair::generate_secp256k1().public(),
+        ]);
+        #[cfg(all(feature = "ecdsa", feature = "rand"))]
+        keys.extend([
+            Keypair::generate_ecdsa().public(),
+            Keypair::generate_ecdsa().public(),
+        ]);
+
+        for a in &keys {
+            for b in &keys {
+                assert_eq!(bool::from(a.ct_eq(b)), a == b);
+            }
+            assert!(bool::from(a.ct_eq(&a.clone())));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn generate_dilithium_uses_default_level() {
+        let keypair = Keypair::generate_dilithium();
+
+        assert_eq!(keypair.dilithium_level(), Some(DilithiumLevel::Level5));
+        assert_eq!(
+            keypair.public().dilithium_level(),
+            Some(DilithiumLevel::Level5)
+        );
+    }
+
     #[test]
     #[cfg(all(feature = "dilithium", feature = "rand"))]
     fn test_publickey_from_dilithium_public_key() {
@@ -1261,7 +4916,7 @@ mod tests {
         let bytes = original_keypair.dilithium_to_bytes();
 
         // Convert back from bytes
-        let roundtrip_keypair = Keypair::dilithium_from_bytes(bytes.clone()).unwrap();
+        let roundtrip_keypair = Keypair::dilithium_from_bytes(&bytes).unwrap();
 
         // Verify that the roundtrip keypair produces the same bytes
         let roundtrip_bytes = roundtrip_keypair.dilithium_to_bytes();
@@ -1289,10 +4944,1979 @@ mod tests {
         assert!(roundtrip_keypair.public().verify(message, &signature2));
     }
 
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn dilithium_from_bytes_rejects_malformed_layout() {
+        let bytes = Keypair::generate_dilithium().dilithium_to_bytes();
+
+        assert!(Keypair::dilithium_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Keypair::dilithium_from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+
+        // Secret and public key swapped.
+        let secret_len = DilithiumLevel::default().secret_key_len();
+        let swapped = [&bytes[secret_len..], &bytes[..secret_len]].concat();
+        assert!(Keypair::dilithium_from_bytes(&swapped).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "peerid"))]
+    fn test_dilithium_derive_child() {
+        let parent = Keypair::generate_dilithium();
+
+        let child = parent.derive_child(7).unwrap();
+        let same_child = parent.derive_child(7).unwrap();
+        let sibling = parent.derive_child(8).unwrap();
+
+        assert_eq!(child.public(), same_child.public());
+        assert_eq!(child.secret(), same_child.secret());
+        assert_ne!(child.public().to_peer_id(), sibling.public().to_peer_id());
+        assert_ne!(child.public().to_peer_id(), parent.public().to_peer_id());
+
+        let other_parent = Keypair::generate_dilithium();
+        assert_ne!(
+            child.public(),
+            other_parent.derive_child(7).unwrap().public()
+        );
+
+        let message = b"signed by a child key";
+        let signature = child.sign(message).unwrap();
+        assert!(same_child.public().verify(message, &signature));
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn test_derive_child_unsupported_key_type() {
+        let keypair = Keypair::generate_ed25519();
+
+        assert!(keypair.derive_child(0).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn test_dilithium_derive_subkey() {
+        let master = Keypair::generate_dilithium_level(DilithiumLevel::Level3);
+
+        let gossip = master.derive_subkey("/meshsub/1.1.0").unwrap();
+        let same_gossip = master.derive_subkey("/meshsub/1.1.0").unwrap();
+        let kad = master.derive_subkey("/ipfs/kad/1.0.0").unwrap();
+
+        assert_eq!(gossip.public(), same_gossip.public());
+        assert_eq!(gossip.secret(), same_gossip.secret());
+        assert_ne!(gossip.public(), kad.public());
+        assert_ne!(gossip.public(), master.public());
+        assert_eq!(gossip.dilithium_level(), Some(DilithiumLevel::Level3));
+
+        // Subkeys are domain separated from child keys and from other masters.
+        assert_ne!(
+            master.derive_subkey("").unwrap().public(),
+            master.derive_child(0).unwrap().public()
+        );
+        let other_master = Keypair::generate_dilithium_level(DilithiumLevel::Level3);
+        assert_ne!(
+            gossip.public(),
+            other_master
+                .derive_subkey("/meshsub/1.1.0")
+                .unwrap()
+                .public()
+        );
+
+        // A subkey signs for itself, not for its master or siblings.
+        let message = b"signed by a gossip subkey";
+        let signature = gossip.sign(message).unwrap();
+        assert!(same_gossip.public().verify(message, &signature));
+        assert!(!kad.public().verify(message, &signature));
+        assert!(!master.public().verify(message, &signature));
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn test_derive_subkey_unsupported_key_type() {
+        assert!(Keypair::generate_ed25519()
+            .derive_subkey("/meshsub/1.1.0")
+            .is_err());
+    }
+
     #[test]
     #[cfg(feature = "ecdsa")]
     fn test_secret_from_ecdsa_private_key() {
         let keypair = Keypair::generate_ecdsa();
         assert!(keypair.derive_secret(b"domain separator!").is_some())
     }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519", feature = "peerid"))]
+    fn hybrid_protobuf_roundtrip() {
+        let keypair = Keypair::generate_hybrid_dilithium_ed25519();
+        assert_eq!(keypair.key_type(), KeyType::HybridDilithiumEd25519);
+
+        let decoded_keypair =
+            Keypair::from_protobuf_encoding(&keypair.to_protobuf_encoding().unwrap()).unwrap();
+        let decoded_public =
+            PublicKey::try_decode_protobuf(&keypair.public().encode_protobuf()).unwrap();
+        assert_eq!(decoded_keypair.public(), keypair.public());
+        assert_eq!(decoded_public, keypair.public());
+        assert_eq!(decoded_public.to_peer_id(), keypair.public().to_peer_id());
+
+        let restored = Keypair::from_secret_bytes(
+            KeyType::HybridDilithiumEd25519,
+            &keypair.secret_to_bytes().unwrap(),
+        );
+        #[cfg(feature = "dilithium-internals")]
+        assert_eq!(restored.unwrap().public(), keypair.public());
+        #[cfg(not(feature = "dilithium-internals"))]
+        assert!(restored.is_err());
+
+        let msg = b"hello world";
+        let sig = decoded_keypair.sign(msg).unwrap();
+        assert!(decoded_public.verify(msg, &sig));
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn hybrid_extract_component() {
+        let hybrid = hybrid::Keypair::generate();
+        let public = PublicKey::from(hybrid.public());
+
+        assert_eq!(
+            public.extract_component(KeyType::Dilithium),
+            Some(PublicKey::from(hybrid.dilithium().public().clone()))
+        );
+        assert_eq!(
+            public.extract_component(KeyType::Ed25519),
+            Some(PublicKey::from(hybrid.ed25519().public()))
+        );
+        assert_eq!(public.extract_component(KeyType::SphincsPlus), None);
+        assert_eq!(
+            public.extract_component(KeyType::HybridDilithiumEd25519),
+            None
+        );
+
+        let dilithium = Keypair::generate_dilithium().public();
+        assert_eq!(dilithium.extract_component(KeyType::Dilithium), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn hybrid_component_verifies_with_extracted_key() {
+        let keypair = Keypair::generate_hybrid_dilithium_ed25519();
+        let public = keypair.public();
+        let msg = b"post-quantum only";
+        let sig = keypair.sign(msg).unwrap();
+
+        let dilithium = public.extract_component(KeyType::Dilithium).unwrap();
+        let ed25519 = public.extract_component(KeyType::Ed25519).unwrap();
+        assert!(dilithium.verify_hybrid_component(msg, &sig));
+        assert!(ed25519.verify_hybrid_component(msg, &sig));
+        assert!(!dilithium.verify_hybrid_component(b"other", &sig));
+        assert!(!dilithium.verify_hybrid_component(msg, &sig[1..]));
+
+        // The halves sign a domain-separated message, never `msg` itself.
+        assert!(!dilithium.verify(msg, &sig));
+        assert!(!public.verify_hybrid_component(msg, &sig));
+
+        let other = Keypair::generate_dilithium().public();
+        assert!(!other.verify_hybrid_component(msg, &sig));
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn hybrid_signature_requires_both_components() {
+        let keypair = Keypair::generate_hybrid_dilithium_ed25519();
+        let msg = b"hello world";
+        let sig = keypair.sign(msg).unwrap();
+        assert!(keypair.public().verify(msg, &sig));
+
+        // The Dilithium signature comes first, the Ed25519 signature last.
+        let dilithium_at = 8;
+        let ed25519_at = sig.len() - 1;
+        for at in [dilithium_at, ed25519_at] {
+            let mut corrupted = sig.clone();
+            corrupted[at] ^= 1;
+            assert!(!keypair.public().verify(msg, &corrupted));
+        }
+
+        assert!(!keypair.public().verify(msg, &sig[..sig.len() - 1]));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn public_key_matches_its_peer_id() {
+        let keypair = Keypair::generate_dilithium();
+        let other = Keypair::generate_dilithium();
+        let peer_id = keypair.public().to_peer_id();
+
+        assert!(keypair.public().matches_peer_id(&peer_id));
+        assert!(!other.public().matches_peer_id(&peer_id));
+        assert!(!keypair.public().matches_peer_id(&crate::PeerId::random()));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn dilithium_peer_id_is_hashed() {
+        let public = Keypair::generate_dilithium().public();
+
+        assert_eq!(public.peer_id_multihash_code(), 0x12);
+        assert_eq!(
+            public.to_peer_id().as_ref().code(),
+            public.peer_id_multihash_code()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "ed25519", feature = "peerid"))]
+    fn ed25519_peer_id_is_inlined() {
+        let public = Keypair::generate_ed25519().public();
+
+        assert_eq!(public.peer_id_multihash_code(), 0x00);
+        assert_eq!(
+            public.to_peer_id().as_ref().code(),
+            public.peer_id_multihash_code()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn public_key_does_not_match_peer_id_of_other_key_type() {
+        let dilithium = Keypair::generate_dilithium().public();
+        let ed25519 = Keypair::generate_ed25519().public();
+
+        assert!(!dilithium.matches_peer_id(&ed25519.to_peer_id()));
+        assert!(!ed25519.matches_peer_id(&dilithium.to_peer_id()));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn generate_dilithium_batch_keys_are_distinct() {
+        let keypairs = Keypair::generate_dilithium_batch(16);
+        assert_eq!(keypairs.len(), 16);
+
+        let peer_ids = keypairs
+            .iter()
+            .map(|kp| kp.public().to_peer_id())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(peer_ids.len(), keypairs.len());
+
+        assert!(Keypair::generate_dilithium_batch(0).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn verify_detailed_reports_failure_reason() {
+        let keypair = Keypair::generate_dilithium();
+        let public = keypair.public();
+        let msg = b"hello";
+        let sig = keypair.sign(msg).unwrap();
+
+        assert_eq!(public.verify_detailed(msg, &sig), Ok(()));
+        assert_eq!(
+            public.verify_detailed(b"other", &sig),
+            Err(VerificationError::InvalidSignature)
+        );
+        assert_eq!(
+            public.verify_detailed(msg, &sig[..sig.len() - 1]),
+            Err(VerificationError::WrongLength {
+                expected: sig.len(),
+                actual: sig.len() - 1
+            })
+        );
+        assert_eq!(
+            public.verify_detailed(msg, &[0; 64]),
+            Err(VerificationError::KeyTypeMismatch {
+                expected: KeyType::Dilithium,
+                actual: KeyType::Ed25519
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn verify_detailed_reports_malformed_and_mismatched_signatures() {
+        let msg = b"hello";
+        let hybrid = Keypair::generate_hybrid_dilithium_ed25519();
+        let dilithium = Keypair::generate_dilithium();
+        let ed25519 = Keypair::generate_ed25519();
+
+        let mut malformed = hybrid.sign(msg).unwrap();
+        malformed[0] ^= 0xff;
+        assert_eq!(
+            hybrid.public().verify_detailed(msg, &malformed),
+            Err(VerificationError::MalformedSignature)
+        );
+        assert!(!hybrid.public().verify(msg, &malformed));
+
+        assert_eq!(
+            hybrid
+                .public()
+                .verify_detailed(msg, &dilithium.sign(msg).unwrap()),
+            Err(VerificationError::KeyTypeMismatch {
+                expected: KeyType::HybridDilithiumEd25519,
+                actual: KeyType::Dilithium
+            })
+        );
+        assert_eq!(
+            ed25519
+                .public()
+                .verify_detailed(msg, &hybrid.sign(msg).unwrap()),
+            Err(VerificationError::KeyTypeMismatch {
+                expected: KeyType::Ed25519,
+                actual: KeyType::HybridDilithiumEd25519
+            })
+        );
+    }
+
+    #[cfg(all(any(feature = "ed25519", feature = "secp256k1"), feature = "rand"))]
+    fn oid_to_string(arcs: &[u32]) -> String {
+        arcs.iter()
+            .map(|arc| arc.to_string())
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    #[test]
+    #[cfg(all(feature = "ed25519", feature = "rand"))]
+    fn ed25519_spki_der_roundtrip() {
+        let public = Keypair::generate_ed25519().public();
+        let der = public.to_spki_der().unwrap();
+
+        let spki = spki::SubjectPublicKeyInfoRef::try_from(der.as_slice()).unwrap();
+        assert_eq!(spki.algorithm.oid.to_string(), "1.3.101.112");
+        assert_eq!(
+            oid_to_string(public.algorithm_oid().unwrap()),
+            spki.algorithm.oid.to_string()
+        );
+        assert!(spki.algorithm.parameters.is_none());
+        assert_eq!(
+            spki.subject_public_key.raw_bytes(),
+            public.clone().try_into_ed25519().unwrap().to_bytes()
+        );
+
+        assert_eq!(PublicKey::from_spki_der(&der).unwrap(), public);
+    }
+
+    #[test]
+    #[cfg(all(feature = "secp256k1", feature = "rand"))]
+    fn secp256k1_spki_der_roundtrip() {
+        let public = Keypair::generate_secp256k1().public();
+        let der = public.to_spki_der().unwrap();
+
+        let spki = spki::SubjectPublicKeyInfoRef::try_from(der.as_slice()).unwrap();
+        assert_eq!(spki.algorithm.oid.to_string(), "1.2.840.10045.2.1");
+        assert_eq!(
+            oid_to_string(public.algorithm_oid().unwrap()),
+            spki.algorithm.oid.to_string()
+        );
+        assert_eq!(
+            spki.algorithm.parameters_oid().unwrap().to_string(),
+            "1.3.132.0.10"
+        );
+
+        assert_eq!(PublicKey::from_spki_der(&der).unwrap(), public);
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn spki_der_is_unsupported_for_post_quantum_keys() {
+        let public = Keypair::generate_dilithium().public();
+
+        assert!(matches!(
+            public.to_spki_der(),
+            Err(EncodingError::UnsupportedForPostQuantum(KeyType::Dilithium))
+        ));
+        assert!(PublicKey::from_spki_der(&public.encode_protobuf()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn fingerprint_is_stable_and_distinct() {
+        let public = Keypair::generate_dilithium().public();
+        let other = Keypair::generate_dilithium().public();
+
+        let fingerprint = public.fingerprint();
+        assert_eq!(fingerprint, public.fingerprint());
+        assert_ne!(fingerprint, other.fingerprint());
+
+        assert_eq!(
+            fingerprint.split(':').count(),
+            PublicKey::DEFAULT_FINGERPRINT_LEN
+        );
+        assert!(fingerprint
+            .split(':')
+            .all(|group| group.len() == 2 && group.chars().all(|c| c.is_ascii_hexdigit())));
+
+        assert!(fingerprint.starts_with(&public.fingerprint_with_len(4)));
+        assert_eq!(public.fingerprint_with_len(4).len(), 4 * 3 - 1);
+        assert_eq!(
+            public.fingerprint_with_len(64),
+            public.fingerprint_with_len(32)
+        );
+        assert_eq!(public.fingerprint_with_len(0), "");
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn generate_dilithium_with_seeded_rng_is_deterministic() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let peer_id = |seed| {
+            Keypair::generate_dilithium_with_rng(&mut ChaCha20Rng::seed_from_u64(seed))
+                .public()
+                .to_peer_id()
+        };
+
+        assert_eq!(peer_id(42), peer_id(42));
+        assert_ne!(peer_id(42), peer_id(43));
+    }
+
+    #[test]
+    #[cfg(all(feature = "ed25519", feature = "rand"))]
+    fn generate_ed25519_with_seeded_rng_is_deterministic() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let peer_id = |seed| {
+            Keypair::generate_ed25519_with_rng(&mut ChaCha20Rng::seed_from_u64(seed))
+                .public()
+                .to_peer_id()
+        };
+
+        assert_eq!(peer_id(42), peer_id(42));
+        assert_ne!(peer_id(42), peer_id(43));
+    }
+
+    #[test]
+    fn protobuf_tags_roundtrip() {
+        let key_types = [
+            KeyType::RSA,
+            KeyType::Ed25519,
+            KeyType::Secp256k1,
+            KeyType::Ecdsa,
+            KeyType::Dilithium,
+            KeyType::HybridDilithiumEd25519,
+            KeyType::SphincsPlus,
+            KeyType::Falcon,
+        ];
+
+        for key_type in key_types {
+            let tag = key_type.to_protobuf_tag();
+            assert_eq!(KeyType::from_protobuf_tag(tag), Some(key_type));
+            #[cfg(any(
+                feature = "ecdsa",
+                feature = "secp256k1",
+                feature = "ed25519",
+                feature = "rsa",
+                feature = "dilithium",
+                feature = "sphincs-plus",
+                feature = "falcon"
+            ))]
+            assert_eq!(proto::KeyType::from(tag) as i32, tag);
+        }
+        assert_eq!(KeyType::Dilithium.to_protobuf_tag(), 4);
+
+        assert_eq!(KeyType::from_protobuf_tag(-1), None);
+        assert_eq!(KeyType::from_protobuf_tag(8), None);
+    }
+
+    #[test]
+    fn enabled_variants_follow_features() {
+        let enabled = KeyType::enabled_variants();
+
+        assert_eq!(
+            enabled.contains(&KeyType::Dilithium),
+            cfg!(feature = "dilithium")
+        );
+        assert_eq!(
+            enabled.contains(&KeyType::Ed25519),
+            cfg!(feature = "ed25519")
+        );
+        assert_eq!(
+            enabled.contains(&KeyType::Secp256k1),
+            cfg!(feature = "secp256k1")
+        );
+        assert_eq!(enabled.contains(&KeyType::Ecdsa), cfg!(feature = "ecdsa"));
+        assert_eq!(
+            enabled.contains(&KeyType::RSA),
+            cfg!(all(feature = "rsa", not(target_arch = "wasm32")))
+        );
+        assert_eq!(
+            enabled.contains(&KeyType::HybridDilithiumEd25519),
+            cfg!(all(feature = "dilithium", feature = "ed25519"))
+        );
+        assert!(enabled
+            .windows(2)
+            .all(|w| w[0].to_protobuf_tag() < w[1].to_protobuf_tag()));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn length_prefixed_roundtrip() {
+        use std::io::Cursor;
+
+        let public_key = Keypair::generate_dilithium().public();
+        let other = Keypair::generate_dilithium().public();
+
+        let mut buf = Vec::new();
+        public_key.write_length_prefixed(&mut buf).unwrap();
+        other.write_length_prefixed(&mut buf).unwrap();
+
+        let mut cursor = Cursor::new(&buf);
+        assert_eq!(
+            PublicKey::read_length_prefixed(&mut cursor).unwrap(),
+            public_key
+        );
+        assert_eq!(PublicKey::read_length_prefixed(&mut cursor).unwrap(), other);
+        assert!(PublicKey::read_length_prefixed(&mut cursor).is_err());
+
+        // Truncated body.
+        let mut cursor = Cursor::new(&buf[..100]);
+        assert!(PublicKey::read_length_prefixed(&mut cursor).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn length_prefixed_rejects_oversized_frames() {
+        use std::io::Cursor;
+
+        let public_key = Keypair::generate_dilithium().public();
+        let mut buf = Vec::new();
+        public_key.write_length_prefixed(&mut buf).unwrap();
+
+        let err =
+            PublicKey::read_length_prefixed_with_limit(&mut Cursor::new(&buf), 1024).unwrap_err();
+        assert!(err.to_string().contains("exceeds the maximum of 1024"));
+
+        // A frame claiming to be huge is rejected without reading its body.
+        let mut huge = Vec::new();
+        huge.extend_from_slice(unsigned_varint::encode::usize(
+            usize::MAX,
+            &mut unsigned_varint::encode::usize_buffer(),
+        ));
+        assert!(PublicKey::read_length_prefixed(&mut Cursor::new(&huge)).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn public_key_order_is_stable_across_round_trips() {
+        let mut keys = (0..8)
+            .map(|_| Keypair::generate_dilithium().public())
+            .chain([Keypair::generate_dilithium_level(DilithiumLevel::Level2).public()])
+            .collect::<Vec<_>>();
+        keys.sort();
+
+        let mut decoded = keys
+            .iter()
+            .rev()
+            .map(|key| PublicKey::try_decode_protobuf(&key.encode_protobuf()).unwrap())
+            .collect::<Vec<_>>();
+        decoded.sort();
+        assert_eq!(decoded, keys);
+
+        for pair in keys.windows(2) {
+            assert!(pair[0].encode_protobuf() < pair[1].encode_protobuf());
+        }
+        assert_eq!(keys[0].cmp(&keys[0].clone()), core::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn public_keys_sort_by_key_type_tag() {
+        let dilithium = Keypair::generate_dilithium().public();
+        let ed25519 = Keypair::generate_ed25519().public();
+        let hybrid = Keypair::generate_hybrid_dilithium_ed25519().public();
+
+        let mut keys = vec![hybrid.clone(), dilithium.clone(), ed25519.clone()];
+        keys.sort();
+        assert_eq!(keys, [ed25519, dilithium, hybrid]);
+
+        let map = keys
+            .iter()
+            .map(|key| (key.clone(), key.key_type()))
+            .collect::<std::collections::BTreeMap<_, _>>();
+        assert_eq!(
+            map.values().copied().collect::<Vec<_>>(),
+            [
+                KeyType::Ed25519,
+                KeyType::Dilithium,
+                KeyType::HybridDilithiumEd25519
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn verify_against_any_finds_the_signing_key() {
+        let signer = Keypair::generate_dilithium();
+        let keys = [
+            Keypair::generate_ed25519().public(),
+            Keypair::generate_dilithium().public(),
+            Keypair::generate_ed25519().public(),
+            signer.public(),
+            Keypair::generate_dilithium_level(DilithiumLevel::Level2).public(),
+        ];
+        let msg = b"hello";
+
+        let sig = signer.sign(msg).unwrap();
+        assert_eq!(verify_against_any(&keys, msg, &sig), Some(3));
+        assert_eq!(verify_against_any(&keys, b"other", &sig), None);
+        assert_eq!(verify_against_any(&keys[..3], msg, &sig), None);
+        assert_eq!(verify_against_any(&[], msg, &sig), None);
+
+        let sig = signer.sign_typed(msg).unwrap();
+        assert_eq!(verify_typed_against_any(&keys, msg, &sig), Some(3));
+
+        let ed25519_sig = Keypair::generate_ed25519().sign(msg).unwrap();
+        assert_eq!(verify_against_any(&keys, msg, &ed25519_sig), None);
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn max_signature_overhead_matches_framed_dilithium_signature() {
+        let sig = Keypair::generate_dilithium().sign_typed(b"hello").unwrap();
+        let tag = [sig.key_type().to_protobuf_tag() as u8];
+        let mut len_buf = unsigned_varint::encode::usize_buffer();
+        let len_prefix = unsigned_varint::encode::usize(sig.len(), &mut len_buf);
+
+        assert_eq!(
+            KeyType::Dilithium.max_signature_overhead(),
+            tag.len() + len_prefix.len() + sig.len()
+        );
+    }
+
+    #[test]
+    fn max_signature_overhead_covers_signature_len() {
+        let key_types = [
+            KeyType::RSA,
+            KeyType::Ed25519,
+            KeyType::Secp256k1,
+            KeyType::Ecdsa,
+            KeyType::Dilithium,
+            KeyType::HybridDilithiumEd25519,
+            KeyType::SphincsPlus,
+            KeyType::Falcon,
+        ];
+
+        for key_type in key_types {
+            let overhead = key_type.max_signature_overhead();
+            assert!(
+                overhead > key_type.signature_len().unwrap_or(0) + 1,
+                "{key_type}"
+            );
+        }
+        assert_eq!(KeyType::Ed25519.max_signature_overhead(), 1 + 1 + 64);
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn secret_bytes_into_matches_dilithium_to_bytes() {
+        let keypair = Keypair::generate_dilithium();
+        let expected = keypair.dilithium_to_bytes();
+
+        // A pre-sized buffer is written in place, without reallocating.
+        let mut out = zeroize::Zeroizing::new(Vec::with_capacity(expected.len()));
+        out.extend_from_slice(b"stale");
+        let ptr = out.as_ptr();
+        keypair.secret_bytes_into(&mut out);
+        assert_eq!(*out, expected);
+        assert_eq!(out.as_ptr(), ptr);
+
+        // A buffer that is too small is replaced.
+        let mut out = zeroize::Zeroizing::new(Vec::new());
+        keypair.secret_bytes_into(&mut out);
+        assert_eq!(*out, expected);
+        assert_eq!(out.capacity(), expected.len());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn decode_keypair_or_public_only_blob() {
+        let keypair = Keypair::generate_dilithium();
+
+        let full =
+            Keypair::try_from_protobuf_with_kind(&keypair.to_protobuf_encoding().unwrap()).unwrap();
+        assert!(full.has_secret());
+        assert_eq!(full.public(), keypair.public());
+        assert!(matches!(full, KeypairOrPublic::Keypair(ref k) if *k == keypair));
+
+        let public_only =
+            Keypair::try_from_protobuf_with_kind(&keypair.public().encode_protobuf()).unwrap();
+        assert!(!public_only.has_secret());
+        assert_eq!(public_only.public(), keypair.public());
+
+        assert!(Keypair::try_from_protobuf_with_kind(b"garbage").is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ed25519", feature = "rand"))]
+    fn decode_ed25519_keypair_or_public_only_blob() {
+        let keypair = Keypair::generate_ed25519();
+
+        let full =
+            Keypair::try_from_protobuf_with_kind(&keypair.to_protobuf_encoding().unwrap()).unwrap();
+        assert!(full.has_secret());
+
+        let public_only =
+            Keypair::try_from_protobuf_with_kind(&keypair.public().encode_protobuf()).unwrap();
+        assert!(!public_only.has_secret());
+        assert_eq!(public_only.public(), keypair.public());
+    }
+
+    #[test]
+    #[cfg(feature = "secp256k1")]
+    fn verify_strict_rejects_high_s_secp256k1() {
+        let keypair = Keypair::generate_secp256k1();
+        let msg = b"hello";
+        let sig = keypair.sign(msg).unwrap();
+        assert!(keypair.public().verify_strict(msg, &sig));
+
+        let mut high = libsecp256k1::Signature::parse_der(&sig).unwrap();
+        high.s = -high.s;
+        let high = high.serialize_der().as_ref().to_vec();
+        assert!(keypair.public().verify(msg, &high));
+        assert!(!keypair.public().verify_strict(msg, &high));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn verify_strict_matches_verify_for_dilithium() {
+        let keypair = Keypair::generate_dilithium();
+        let sig = keypair.sign(b"hello").unwrap();
+
+        assert!(keypair.public().verify_strict(b"hello", &sig));
+        assert!(!keypair.public().verify_strict(b"other", &sig));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn generate_dispatches_on_key_type() {
+        let key_types = [
+            KeyType::RSA,
+            KeyType::Ed25519,
+            KeyType::Secp256k1,
+            KeyType::Ecdsa,
+            KeyType::Dilithium,
+            KeyType::HybridDilithiumEd25519,
+            KeyType::SphincsPlus,
+            KeyType::Falcon,
+        ];
+
+        for key_type in key_types {
+            let enabled = match key_type {
+                KeyType::RSA => false,
+                KeyType::Ed25519 => cfg!(feature = "ed25519"),
+                KeyType::Secp256k1 => cfg!(feature = "secp256k1"),
+                KeyType::Ecdsa => cfg!(feature = "ecdsa"),
+                KeyType::Dilithium => cfg!(feature = "dilithium"),
+                KeyType::HybridDilithiumEd25519 => {
+                    cfg!(all(feature = "dilithium", feature = "ed25519"))
+                }
+                KeyType::SphincsPlus => cfg!(feature = "sphincs-plus"),
+                KeyType::Falcon => cfg!(feature = "falcon"),
+            };
+
+            match Keypair::generate(key_type) {
+                Ok(keypair) => {
+                    assert!(enabled, "{key_type}");
+                    assert_eq!(keypair.key_type(), key_type);
+                    let sig = keypair.sign(b"hello").unwrap();
+                    assert!(keypair.public().verify(b"hello", &sig));
+                }
+                Err(err) => {
+                    assert!(!enabled, "{key_type}");
+                    assert!(
+                        matches!(err, SigningError::UnsupportedKeyType(t) if t == key_type),
+                        "{key_type}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "ed25519", feature = "rand", feature = "peerid"))]
+    fn ed25519_peer_id_can_be_forced_to_sha256() {
+        let public = Keypair::generate_ed25519().public();
+        let inlined = public.to_peer_id();
+
+        let hashed = public.to_peer_id_with_hash(0x12).unwrap();
+        assert_ne!(hashed, inlined);
+        assert_eq!(hashed.to_bytes()[0], 0x12);
+        assert_eq!(
+            crate::PeerId::from_bytes(&hashed.to_bytes()).unwrap(),
+            hashed
+        );
+        assert_eq!(public.to_peer_id_with_hash(0x00).unwrap(), inlined);
+
+        // SHA-512 is not allowed for peer ids.
+        assert!(matches!(
+            public.to_peer_id_with_hash(0x13),
+            Err(EncodingError::UnsupportedMultihash(0x13))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn dilithium_peer_id_cannot_be_inlined() {
+        let public = Keypair::generate_dilithium().public();
+
+        assert_eq!(
+            public.to_peer_id_with_hash(0x12).unwrap(),
+            public.to_peer_id()
+        );
+        assert!(matches!(
+            public.to_peer_id_with_hash(0x00),
+            Err(EncodingError::UnsupportedMultihash(0x00))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn protobuf_decode_errors_name_the_problem() {
+        fn decode_both(bytes: &[u8]) -> [DecodingError; 2] {
+            [
+                Keypair::from_protobuf_encoding(bytes).unwrap_err(),
+                PublicKey::try_decode_protobuf(bytes).unwrap_err(),
+            ]
+        }
+
+        for err in decode_both(&[18, 1, 0]) {
+            assert!(matches!(err, DecodingError::MissingKeyType));
+        }
+        for err in decode_both(&[8, 42, 18, 1, 0]) {
+            assert!(matches!(err, DecodingError::UnknownKeyType(42)));
+        }
+        for err in decode_both(&[8]) {
+            assert!(matches!(
+                err,
+                DecodingError::TruncatedField { field: "Type" }
+            ));
+        }
+        for err in decode_both(&[8, 4, 18, 32, 0]) {
+            assert!(matches!(
+                err,
+                DecodingError::TruncatedField { field: "Data" }
+            ));
+        }
+        for err in decode_both(&[8, 4]) {
+            assert!(matches!(
+                err,
+                DecodingError::TruncatedField { field: "Data" }
+            ));
+        }
+        for err in decode_both(&[8, 4, 18, 1, 0]) {
+            assert!(matches!(
+                err,
+                DecodingError::InvalidKeyMaterial {
+                    key_type: KeyType::Dilithium,
+                    ..
+                }
+            ));
+            assert!(err.to_string().contains("Dilithium"));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn sign_many_matches_individual_signatures() {
+        let msgs: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; i as usize * 10]).collect();
+        let msgs: Vec<&[u8]> = msgs.iter().map(Vec::as_slice).collect();
+
+        for keypair in [Keypair::generate_dilithium(), Keypair::generate_ed25519()] {
+            let sigs = keypair.sign_many(&msgs).unwrap();
+
+            assert_eq!(sigs.len(), msgs.len());
+            for (msg, sig) in msgs.iter().zip(&sigs) {
+                assert!(keypair.public().verify(msg, sig));
+            }
+            assert!(keypair.sign_many(&[]).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn algorithm_oid() {
+        #[cfg(feature = "ed25519")]
+        assert_eq!(
+            Keypair::generate_ed25519().public().algorithm_oid(),
+            Some(&[1, 3, 101, 112][..])
+        );
+
+        for (level, arc) in [
+            (DilithiumLevel::Level2, 17),
+            (DilithiumLevel::Level3, 18),
+            (DilithiumLevel::Level5, 19),
+        ] {
+            let public = Keypair::generate_dilithium_level(level).public();
+            assert_eq!(
+                public.algorithm_oid(),
+                Some(&[2, 16, 840, 1, 101, 3, 4, 3, arc][..])
+            );
+        }
+
+        #[cfg(feature = "ed25519")]
+        assert_eq!(
+            Keypair::generate_hybrid_dilithium_ed25519()
+                .public()
+                .algorithm_oid(),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn validate_rejects_corrupted_dilithium_secret() {
+        let keypair = Keypair::generate_dilithium();
+        assert_eq!(keypair.validate(), Ok(()));
+
+        // The first byte of the packed `s1` vector, after rho, key and tr.
+        let mut bytes = keypair.dilithium_to_bytes();
+        bytes[128] = 0xff;
+        let corrupted = Keypair::dilithium_from_bytes(&bytes).unwrap();
+        assert_eq!(
+            corrupted.validate(),
+            Err(ValidationError::SecretCoefficientOutOfRange)
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "ed25519", feature = "peerid"))]
+    fn import_legacy_ed25519_keypair() {
+        // RFC 8032, section 7.1, test 1: secret key followed by public key.
+        let legacy = hex_literal::hex!(
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+
+        let keypair = Keypair::from_legacy_ed25519_bytes(&legacy).unwrap();
+        assert_eq!(
+            keypair.public().to_peer_id().to_base58(),
+            "12D3KooWQK1wnefoLrcVHbbnf5tLzbopUd3K3bFAoJpA7YJgL5pV"
+        );
+
+        let mut mismatched = legacy;
+        mismatched[63] ^= 1;
+        assert!(Keypair::from_legacy_ed25519_bytes(&mismatched).is_err());
+        assert!(Keypair::from_legacy_ed25519_bytes(&legacy[..32]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn debug_output_hides_key_material() {
+        let keypair = Keypair::generate_dilithium();
+        let debug = format!("{keypair:?}");
+
+        assert!(debug.contains("Dilithium"), "{debug}");
+        #[cfg(feature = "peerid")]
+        assert!(debug.contains(&keypair.public().fingerprint()), "{debug}");
+
+        let secret = keypair.dilithium_to_bytes();
+        for window in secret.windows(4).step_by(64) {
+            let hex: String = window.iter().map(|b| format!("{b:02x}")).collect();
+            assert!(!debug.contains(&hex), "{debug}");
+        }
+        assert!(debug.len() < 128, "{debug}");
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "peerid"))]
+    fn identity_proof() {
+        let keypair = Keypair::generate_dilithium();
+        let public = keypair.public();
+        let peer_id = public.to_peer_id();
+        let challenge = b"nonce from the verifier";
+
+        let proof = keypair.prove_identity(challenge).unwrap();
+        assert!(public.verify_identity_proof(&peer_id, challenge, &proof));
+
+        assert!(!public.verify_identity_proof(&peer_id, b"another nonce", &proof));
+        assert!(!public.verify(challenge, proof.as_bytes()));
+
+        let other = Keypair::generate_dilithium().public().to_peer_id();
+        assert!(!public.verify_identity_proof(&other, challenge, &proof));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn attached_signature_roundtrip_and_tampering() {
+        let keypair = Keypair::generate_dilithium();
+        let public = keypair.public();
+        let msg = b"message travelling with its signature";
+
+        let blob = keypair.sign_attached(msg).unwrap();
+        assert_eq!(public.verify_attached(&blob).unwrap(), msg);
+        assert!(public
+            .verify_attached(&keypair.sign_attached(&[]).unwrap())
+            .unwrap()
+            .is_empty());
+
+        let mut tampered = blob.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            public.verify_attached(&tampered),
+            Err(VerificationError::InvalidSignature)
+        );
+
+        let mut tampered = blob.clone();
+        tampered[10] ^= 1;
+        assert_eq!(
+            public.verify_attached(&tampered),
+            Err(VerificationError::InvalidSignature)
+        );
+
+        assert_eq!(
+            public.verify_attached(&blob[..100]),
+            Err(VerificationError::MalformedSignature)
+        );
+        assert_eq!(
+            public.verify_attached(&[0xff]),
+            Err(VerificationError::MalformedSignature)
+        );
+
+        let other = Keypair::generate_dilithium().public();
+        assert!(other.verify_attached(&blob).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn deterministic_and_hedged_dilithium_signing() {
+        let keypair = Keypair::generate_dilithium();
+        let public = keypair.public();
+        let msg = b"reproducible";
+
+        let deterministic = keypair.sign_deterministic(msg).unwrap();
+        assert_eq!(keypair.sign_deterministic(msg).unwrap(), deterministic);
+        assert!(public.verify(msg, &deterministic));
+
+        let hedged = keypair.sign(msg).unwrap();
+        assert_ne!(hedged, deterministic);
+        assert_ne!(keypair.sign(msg).unwrap(), hedged);
+        assert!(public.verify(msg, &hedged));
+
+        // The backend draws the randomness, so the RNG passed in is not used.
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let with_rng = keypair.sign_with_rng(msg, &mut rng).unwrap();
+        assert_ne!(with_rng, hedged);
+        assert!(public.verify(msg, &with_rng));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn verify_unchecked_rejects_short_signature() {
+        let keypair = Keypair::generate_dilithium();
+        let public = keypair.public();
+        let sig = keypair.sign(b"msg").unwrap();
+
+        assert!(public.verify_unchecked(b"msg", &sig));
+        assert!(!public.verify_unchecked(b"msg", &sig[..64]));
+        assert!(!public.verify_unchecked(b"msg", &[]));
+    }
+
+    #[test]
+    #[cfg(feature = "bip39")]
+    fn dilithium_from_mnemonic() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        let a = Keypair::dilithium_from_mnemonic(phrase, "").unwrap();
+        let b = Keypair::dilithium_from_mnemonic(phrase, "").unwrap();
+        let salted = Keypair::dilithium_from_mnemonic(phrase, "TREZOR").unwrap();
+
+        assert_eq!(a.public(), b.public());
+        assert_ne!(a.public(), salted.public());
+        // Pinned so that changes to the seed derivation are caught.
+        assert_eq!(
+            a.public().to_peer_id().to_base58(),
+            "QmSKpF35PxutxvZPecU9rhW1cTHZdCuuN7PAAWj7mNdsKf"
+        );
+
+        let bad_checksum = phrase.replace("about", "abandon");
+        assert!(Keypair::dilithium_from_mnemonic(&bad_checksum, "").is_err());
+        assert!(Keypair::dilithium_from_mnemonic("abandon abandon abandon", "").is_err());
+        assert!(Keypair::dilithium_from_mnemonic(&phrase.replace("about", "libp2p"), "").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn decode_many_reports_each_blob() {
+        let dilithium = Keypair::generate_dilithium().public().encode_protobuf();
+        let level2 = Keypair::generate_dilithium_level(DilithiumLevel::Level2)
+            .public()
+            .encode_protobuf();
+        let blobs: Vec<&[u8]> = vec![
+            &dilithium,
+            b"not a key",
+            &dilithium[..2],
+            &level2,
+            &[0x08, 0x63, 0x12, 0x00],
+            &dilithium[..dilithium.len() - 1],
+        ];
+
+        let decoded = PublicKey::decode_many(&blobs);
+
+        assert_eq!(decoded.len(), blobs.len());
+        for (blob, result) in blobs.iter().zip(&decoded) {
+            match (result, PublicKey::try_decode_protobuf(blob)) {
+                (Ok(key), Ok(expected)) => assert_eq!(*key, expected),
+                (Err(e), Err(expected)) => assert_eq!(e.to_string(), expected.to_string()),
+                (result, expected) => panic!("{result:?} != {expected:?}"),
+            }
+        }
+        assert!(decoded[0].is_ok());
+        assert!(decoded[3].is_ok());
+        assert!(matches!(decoded[4], Err(DecodingError::UnknownKeyType(99))));
+        assert_eq!(decoded.iter().filter(|r| r.is_err()).count(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "secp256k1")]
+    fn secp256k1_recoverable_signature() {
+        let keypair = Keypair::generate_secp256k1();
+        let msg = b"recover me";
+
+        let (sig, recovery_id) = keypair.sign_recoverable(msg).unwrap();
+
+        assert_eq!(sig.len(), 64);
+        assert_eq!(
+            PublicKey::recover_from_signature(msg, &sig, recovery_id).unwrap(),
+            keypair.public()
+        );
+        assert_ne!(
+            PublicKey::recover_from_signature(b"tampered", &sig, recovery_id).ok(),
+            Some(keypair.public())
+        );
+        assert_eq!(
+            PublicKey::recover_from_signature(msg, &sig[..63], recovery_id),
+            Err(VerificationError::MalformedSignature)
+        );
+        assert_eq!(
+            PublicKey::recover_from_signature(msg, &sig, 4),
+            Err(VerificationError::MalformedSignature)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn sign_recoverable_rejects_other_key_types() {
+        assert!(matches!(
+            Keypair::generate_dilithium().sign_recoverable(b"msg"),
+            Err(SigningError::UnsupportedKeyType(KeyType::Dilithium))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn aad_is_bound_to_the_signature() {
+        let keypair = Keypair::generate_dilithium();
+        let public = keypair.public();
+        let msg = b"payload";
+
+        let sig = keypair
+            .sign_with_aad(b"topic:blocks ts:1700000000", msg)
+            .unwrap();
+
+        assert!(public.verify_with_aad(b"topic:blocks ts:1700000000", msg, &sig));
+        assert!(!public.verify_with_aad(b"topic:blocks ts:1700000001", msg, &sig));
+        assert!(!public.verify_with_aad(b"", msg, &sig));
+        assert!(!public.verify(msg, &sig));
+        assert!(!public.verify_with_context(b"topic:blocks ts:1700000000", msg, &sig));
+
+        // The boundary between AAD and message is part of what is signed.
+        let sig = keypair.sign_with_aad(b"ab", b"c").unwrap();
+        assert!(!public.verify_with_aad(b"a", b"bc", &sig));
+
+        assert!(public.verify(msg, &keypair.sign_with_aad(b"", msg).unwrap()));
+        assert!(public.verify_with_aad(b"", msg, &keypair.sign(msg).unwrap()));
+    }
+
+    #[test]
+    #[cfg(feature = "peerid")]
+    fn write_peer_id_bytes_matches_to_peer_id() {
+        let keys = [
+            #[cfg(feature = "dilithium")]
+            Keypair::generate_dilithium().public(),
+            #[cfg(feature = "dilithium")]
+            Keypair::generate_dilithium_level(DilithiumLevel::Level2).public(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_ed25519().public(),
+            #[cfg(feature = "secp256k1")]
+            Keypair::generate_secp256k1().public(),
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            Keypair::generate_hybrid_dilithium_ed25519().public(),
+        ];
+
+        for key in keys {
+            let expected = key.to_peer_id().to_bytes();
+            let mut out = [0xff; 64];
+
+            let len = key.write_peer_id_bytes(&mut out).unwrap();
+
+            assert_eq!(&out[..len], &expected[..]);
+            assert!(matches!(
+                key.write_peer_id_bytes(&mut out[..len - 1]),
+                Err(EncodingError::BufferTooSmall { needed, available })
+                    if needed == len && available == len - 1
+            ));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn failing_rng_is_reported_as_entropy_failure() {
+        struct FailingRng;
+
+        impl RngCore for FailingRng {
+            fn next_u32(&mut self) -> u32 {
+                unreachable!("only try_fill_bytes is called")
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                unreachable!("only try_fill_bytes is called")
+            }
+
+            fn fill_bytes(&mut self, _: &mut [u8]) {
+                unreachable!("only try_fill_bytes is called")
+            }
+
+            fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), rand::Error> {
+                Err(rand::Error::new("entropy source unavailable"))
+            }
+        }
+
+        impl CryptoRng for FailingRng {}
+
+        assert_eq!(
+            Keypair::try_generate_dilithium_with_rng(&mut FailingRng).unwrap_err(),
+            GenerationError::EntropyFailure
+        );
+        assert_eq!(
+            Keypair::try_generate_dilithium().unwrap().key_type(),
+            KeyType::Dilithium
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn verify_bounded_rejects_oversized_messages() {
+        let keypair = Keypair::generate_dilithium();
+        let public = keypair.public();
+        let msg = vec![7u8; 1024];
+        let sig = keypair.sign(&msg).unwrap();
+
+        assert_eq!(public.verify_bounded(&msg, &sig, 1024), Ok(true));
+        assert_eq!(public.verify_bounded(&msg[..1000], &sig, 1024), Ok(false));
+        assert_eq!(
+            public.verify_bounded(&msg, &sig, 1023),
+            Err(VerificationError::InputTooLarge {
+                max: 1023,
+                actual: 1024
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn raw_bytes_roundtrip() {
+        let keys = [
+            Keypair::generate_dilithium().public(),
+            Keypair::generate_dilithium_level(DilithiumLevel::Level2).public(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_ed25519().public(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_hybrid_dilithium_ed25519().public(),
+        ];
+
+        for key in keys {
+            let raw = key.raw_bytes();
+
+            assert!(key.encode_protobuf().ends_with(&raw));
+            assert_eq!(
+                PublicKey::from_raw_bytes(key.key_type(), &raw).unwrap(),
+                key
+            );
+        }
+
+        let raw = Keypair::generate_dilithium().public().raw_bytes();
+        assert!(PublicKey::from_raw_bytes(KeyType::Dilithium, &raw[1..]).is_err());
+        assert!(PublicKey::from_raw_bytes(KeyType::Ed25519, &raw).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn compact_bytes_roundtrip() {
+        let keys = [
+            Keypair::generate_dilithium().public(),
+            Keypair::generate_dilithium_level(DilithiumLevel::Level2).public(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_ed25519().public(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_hybrid_dilithium_ed25519().public(),
+        ];
+
+        for key in keys {
+            let compact = key.to_compact_bytes();
+
+            assert_eq!(compact.len(), 1 + key.raw_bytes().len());
+            assert_eq!(PublicKey::from_compact_bytes(&compact).unwrap(), key);
+        }
+
+        let key = Keypair::generate_dilithium().public();
+        assert!(key.to_compact_bytes().len() < key.encode_protobuf().len());
+
+        assert!(PublicKey::from_compact_bytes(&[]).is_err());
+        assert!(PublicKey::from_compact_bytes(&[0xff, 1, 2, 3]).is_err());
+        let compact = key.to_compact_bytes();
+        assert!(PublicKey::from_compact_bytes(&compact[..compact.len() - 1]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn has_public_key_matches_own_key_only() {
+        let keypair = Keypair::generate_dilithium();
+        let other = Keypair::generate_dilithium();
+
+        assert!(keypair.has_public_key(&keypair.public()));
+        assert!(!keypair.has_public_key(&other.public()));
+
+        let imported =
+            Keypair::from_protobuf_encoding(&keypair.to_protobuf_encoding().unwrap()).unwrap();
+        assert!(imported.has_public_key(&keypair.public()));
+
+        #[cfg(feature = "ed25519")]
+        assert!(!keypair.has_public_key(&Keypair::generate_ed25519().public()));
+    }
+
+    #[test]
+    #[cfg(all(feature = "encrypted-storage", feature = "dilithium"))]
+    fn encrypted_bytes_roundtrip() {
+        use crate::encrypted_storage::tests::TEST_PARAMS;
+
+        let keypair = Keypair::generate_dilithium();
+        let encrypted = keypair
+            .to_encrypted_bytes_with_params(b"correct horse", TEST_PARAMS)
+            .unwrap();
+
+        let decrypted = Keypair::from_encrypted_bytes(&encrypted, b"correct horse").unwrap();
+        assert_eq!(decrypted.public(), keypair.public());
+        assert!(bool::from(decrypted.ct_eq(&keypair)));
+
+        let again = keypair
+            .to_encrypted_bytes_with_params(b"correct horse", TEST_PARAMS)
+            .unwrap();
+        assert_ne!(again, encrypted, "salt and nonce must be fresh");
+    }
+
+    #[test]
+    #[cfg(all(feature = "encrypted-storage", feature = "dilithium"))]
+    fn encrypted_bytes_wrong_passphrase() {
+        use crate::encrypted_storage::tests::TEST_PARAMS;
+
+        let encrypted = Keypair::generate_dilithium()
+            .to_encrypted_bytes_with_params(b"correct horse", TEST_PARAMS)
+            .unwrap();
+
+        assert!(matches!(
+            Keypair::from_encrypted_bytes(&encrypted, b"battery staple"),
+            Err(DecodingError::AuthenticationFailed)
+        ));
+        assert!(matches!(
+            Keypair::from_encrypted_bytes(&encrypted, b""),
+            Err(DecodingError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    #[cfg(all(feature = "encrypted-storage", feature = "dilithium"))]
+    fn encrypted_bytes_default_params() {
+        let keypair = Keypair::generate_dilithium();
+        let encrypted = keypair.to_encrypted_bytes(b"correct horse").unwrap();
+
+        let decrypted = Keypair::from_encrypted_bytes(&encrypted, b"correct horse").unwrap();
+        assert_eq!(decrypted.public(), keypair.public());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn prepared_public_key_matches_verify() {
+        let keys = [
+            Keypair::generate_dilithium(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_ed25519(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_hybrid_dilithium_ed25519(),
+        ];
+
+        for keypair in keys {
+            let public = keypair.public();
+            let prepared = public.clone().into_prepared();
+            assert_eq!(prepared.public(), public);
+
+            let msg = b"prepared verification";
+            let sig = keypair.sign(msg).unwrap();
+            let mut tampered = sig.clone();
+            tampered[0] ^= 1;
+
+            for (m, s) in [
+                (&msg[..], &sig[..]),
+                (b"other message", &sig[..]),
+                (&msg[..], &tampered[..]),
+                (&msg[..], &[]),
+            ] {
+                assert_eq!(prepared.verify(m, s), public.verify(m, s));
+            }
+            assert!(prepared.verify(msg, &sig));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn inspect_protobuf_tells_keypairs_from_public_keys() {
+        let keypairs = [
+            Keypair::generate_dilithium(),
+            Keypair::generate_dilithium_level(DilithiumLevel::Level2),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_ed25519(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_hybrid_dilithium_ed25519(),
+            #[cfg(feature = "secp256k1")]
+            Keypair::generate_secp256k1(),
+            #[cfg(feature = "ecdsa")]
+            Keypair::generate_ecdsa(),
+            #[cfg(feature = "sphincs-plus")]
+            Keypair::generate_sphincs_plus(),
+            #[cfg(feature = "falcon")]
+            Keypair::generate_falcon(),
+        ];
+
+        for keypair in keypairs {
+            let private = keypair.to_protobuf_encoding().unwrap();
+            let public = keypair.public().encode_protobuf();
+
+            let private_info = inspect_protobuf(&private).unwrap();
+            let public_info = inspect_protobuf(&public).unwrap();
+
+            assert_eq!(private_info.key_type, keypair.key_type());
+            assert_eq!(public_info.key_type, keypair.key_type());
+            assert!(private_info.has_secret, "{:?}", keypair.key_type());
+            assert!(!public_info.has_secret, "{:?}", keypair.key_type());
+            assert_eq!(public_info.approx_len, keypair.public().raw_bytes().len());
+        }
+
+        assert!(matches!(
+            inspect_protobuf(&[]),
+            Err(DecodingError::MissingKeyType)
+        ));
+    }
+
+    #[test]
+    #[cfg(all(feature = "cbor", feature = "dilithium"))]
+    fn cbor_roundtrip_is_deterministic() {
+        let keys = [
+            Keypair::generate_dilithium().public(),
+            Keypair::generate_dilithium_level(DilithiumLevel::Level2).public(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_ed25519().public(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_hybrid_dilithium_ed25519().public(),
+        ];
+
+        for key in keys {
+            let cbor = key.to_cbor();
+
+            assert_eq!(key.to_cbor(), cbor);
+            let decoded = PublicKey::from_cbor(&cbor).unwrap();
+            assert_eq!(decoded, key);
+            assert_eq!(decoded.to_cbor(), cbor);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn verify_chunks_matches_joined_message() {
+        let keys = [
+            Keypair::generate_dilithium(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_ed25519(),
+        ];
+
+        for keypair in keys {
+            let public = keypair.public();
+            let sig = keypair.sign(b"Hello, world").unwrap();
+
+            assert!(public.verify_chunks([&b"Hello, "[..], b"world"], &sig));
+            assert!(public.verify_chunks(vec![&b"Hello, world"[..]], &sig));
+            assert!(public.verify_chunks([&b""[..], b"Hello", b", ", b"", b"world"], &sig));
+            assert!(!public.verify_chunks([&b"Hello, "[..], b"World"], &sig));
+            assert!(!public.verify_chunks([&b"Hello, "[..]], &sig));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn scheme_accessors_match_key_type() {
+        let keypair = Keypair::generate_dilithium();
+        let inner = keypair.as_dilithium().expect("a Dilithium keypair");
+        assert_eq!(
+            inner.public(),
+            &keypair.public().try_into_dilithium().unwrap()
+        );
+        assert_eq!(
+            keypair.clone().try_into_dilithium().unwrap().public(),
+            inner.public()
+        );
+
+        #[cfg(feature = "ed25519")]
+        {
+            assert!(keypair.as_ed25519().is_none());
+            assert!(keypair.as_hybrid_dilithium_ed25519().is_none());
+
+            let ed25519 = Keypair::generate_ed25519();
+            assert!(ed25519.as_dilithium().is_none());
+            assert!(ed25519.as_ed25519().is_some());
+            assert_eq!(
+                ed25519
+                    .clone()
+                    .try_into_dilithium()
+                    .unwrap_err()
+                    .to_string(),
+                OtherVariantError::new(KeyType::Ed25519).to_string()
+            );
+
+            let hybrid = Keypair::generate_hybrid_dilithium_ed25519();
+            assert!(hybrid.as_hybrid_dilithium_ed25519().is_some());
+            assert!(hybrid.as_dilithium().is_none());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn peer_id_from_public_key_bytes_matches_public_key() {
+        let keys = [
+            Keypair::generate_dilithium().public(),
+            Keypair::generate_dilithium_level(DilithiumLevel::Level2).public(),
+            Keypair::generate_dilithium_level(DilithiumLevel::Level3).public(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_ed25519().public(),
+            #[cfg(feature = "ed25519")]
+            Keypair::generate_hybrid_dilithium_ed25519().public(),
+            #[cfg(feature = "secp256k1")]
+            Keypair::generate_secp256k1().public(),
+        ];
+
+        for key in keys {
+            let raw = key.raw_bytes();
+            let expected = PublicKey::from_raw_bytes(key.key_type(), &raw)
+                .unwrap()
+                .to_peer_id();
+
+            assert_eq!(
+                crate::peer_id_from_public_key_bytes(key.key_type(), &raw).unwrap(),
+                expected
+            );
+            assert!(matches!(
+                crate::peer_id_from_public_key_bytes(key.key_type(), &raw[1..]),
+                Err(EncodingError::InvalidKeyLength { key_type, len })
+                    if key_type == key.key_type() && len == raw.len() - 1
+            ));
+        }
+
+        // Key types need not be compiled in to derive their PeerIds.
+        assert!(crate::peer_id_from_public_key_bytes(KeyType::SphincsPlus, &[7; 64]).is_ok());
+        assert!(crate::peer_id_from_public_key_bytes(KeyType::Ed25519, &[7; 33]).is_err());
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "async", feature = "dilithium"))]
+    async fn sign_async_signs_like_sign() {
+        let keypair = Keypair::generate_dilithium();
+        let msg = b"signed off the executor".to_vec();
+
+        let sig = keypair.sign_async(msg.clone()).await.unwrap();
+
+        assert!(keypair.public().verify(&msg, &sig));
+    }
+
+    #[test]
+    fn dilithium_costs_more_than_ed25519() {
+        assert_eq!(KeyType::Ed25519.relative_sign_cost(), 1);
+        assert_eq!(KeyType::Ed25519.relative_verify_cost(), 1);
+        assert!(KeyType::Dilithium.relative_sign_cost() > KeyType::Ed25519.relative_sign_cost());
+        assert!(
+            KeyType::Dilithium.relative_verify_cost() > KeyType::Ed25519.relative_verify_cost()
+        );
+        assert!(
+            KeyType::HybridDilithiumEd25519.relative_verify_cost()
+                >= KeyType::Dilithium.relative_verify_cost()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn verify_typed_rejects_mismatched_key_type() {
+        let dilithium = Keypair::generate_dilithium();
+        let ed25519 = Keypair::generate_ed25519();
+        let msg = b"hello";
+
+        let sig = dilithium.sign_typed(msg).unwrap();
+        assert_eq!(sig.key_type(), KeyType::Dilithium);
+        assert_eq!(dilithium.public().verify_typed(msg, &sig), Ok(()));
+        assert!(dilithium.public().verify(msg, sig.as_bytes()));
+        assert_eq!(
+            dilithium.public().verify_typed(b"other", &sig),
+            Err(VerificationError::InvalidSignature)
+        );
+
+        let sig = ed25519.sign_typed(msg).unwrap();
+        assert_eq!(
+            dilithium.public().verify_typed(msg, &sig),
+            Err(VerificationError::KeyTypeMismatch {
+                expected: KeyType::Dilithium,
+                actual: KeyType::Ed25519,
+            })
+        );
+
+        // The tag is checked even if the bytes would verify.
+        let mislabelled = Signature::new(KeyType::Dilithium, sig.into_bytes());
+        assert!(ed25519.public().verify(msg, mislabelled.as_bytes()));
+        assert_eq!(
+            ed25519.public().verify_typed(msg, &mislabelled),
+            Err(VerificationError::KeyTypeMismatch {
+                expected: KeyType::Ed25519,
+                actual: KeyType::Dilithium,
+            })
+        );
+    }
+
+    #[test]
+    fn key_type_from_str() {
+        let key_types = [
+            KeyType::RSA,
+            KeyType::Ed25519,
+            KeyType::Secp256k1,
+            KeyType::Ecdsa,
+            KeyType::Dilithium,
+            KeyType::HybridDilithiumEd25519,
+            KeyType::SphincsPlus,
+            KeyType::Falcon,
+        ];
+
+        for key_type in key_types {
+            let name = key_type.to_string();
+            assert_eq!(name.parse::<KeyType>(), Ok(key_type));
+            assert_eq!(name.to_lowercase().parse::<KeyType>(), Ok(key_type));
+            assert_eq!(name.to_uppercase().parse::<KeyType>(), Ok(key_type));
+        }
+
+        assert_eq!("ML-DSA".parse::<KeyType>(), Ok(KeyType::Dilithium));
+        assert_eq!("slh-dsa".parse::<KeyType>(), Ok(KeyType::SphincsPlus));
+        assert_eq!("sphincs+".parse::<KeyType>(), Ok(KeyType::SphincsPlus));
+        assert_eq!("FN-DSA".parse::<KeyType>(), Ok(KeyType::Falcon));
+        assert_eq!(
+            "hybrid-dilithium-ed25519".parse::<KeyType>(),
+            Ok(KeyType::HybridDilithiumEd25519)
+        );
+
+        assert!("".parse::<KeyType>().is_err());
+        assert!(" dilithium".parse::<KeyType>().is_err());
+        assert!("dsa".parse::<KeyType>().is_err());
+    }
+
+    #[test]
+    fn security_category_of_every_key_type() {
+        use crate::SecurityCategory;
+
+        let key_types = [
+            KeyType::RSA,
+            KeyType::Ed25519,
+            KeyType::Secp256k1,
+            KeyType::Ecdsa,
+            KeyType::Dilithium,
+            KeyType::HybridDilithiumEd25519,
+            KeyType::SphincsPlus,
+            KeyType::Falcon,
+        ];
+
+        for key_type in key_types {
+            // Fails to compile when a key type is added without being listed above.
+            let expected = match key_type {
+                KeyType::RSA | KeyType::Ed25519 | KeyType::Secp256k1 | KeyType::Ecdsa => {
+                    SecurityCategory::Classical
+                }
+                KeyType::Dilithium | KeyType::SphincsPlus | KeyType::Falcon => {
+                    SecurityCategory::PostQuantum
+                }
+                KeyType::HybridDilithiumEd25519 => SecurityCategory::Hybrid,
+            };
+            assert_eq!(key_type.security_category(), expected, "{key_type}");
+            assert_eq!(
+                key_type.is_post_quantum(),
+                expected != SecurityCategory::Classical,
+                "{key_type}"
+            );
+        }
+    }
+
+    #[cfg(any(feature = "dilithium", all(feature = "ed25519", feature = "rand")))]
+    fn assert_streaming_matches_one_shot(keypair: &Keypair) {
+        let msg = (0..1 << 20).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let expected = keypair.sign(&msg).unwrap();
+
+        for chunk_size in [1, 7, 136, 4096, 65537, msg.len()] {
+            let mut signer = keypair.signer();
+            let mut verifier = keypair.public().verifier();
+            for chunk in msg.chunks(chunk_size) {
+                signer.update(chunk);
+                verifier.update(chunk);
+            }
+            let sig = signer.finalize().unwrap();
+
+            assert!(keypair.public().verify(&msg, &sig));
+            assert!(verifier.finalize(&sig));
+        }
+
+        let mut verifier = keypair.public().verifier();
+        verifier.update(&msg[1..]);
+        assert!(!verifier.finalize(&expected));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn dilithium_streaming_matches_one_shot() {
+        assert_streaming_matches_one_shot(&Keypair::generate_dilithium());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ed25519", feature = "rand"))]
+    fn buffered_streaming_matches_one_shot() {
+        assert_streaming_matches_one_shot(&Keypair::generate_ed25519());
+        #[cfg(feature = "dilithium")]
+        assert_streaming_matches_one_shot(&Keypair::generate_hybrid_dilithium_ed25519());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn dilithium_from_seed_is_deterministic() {
+        let a = Keypair::dilithium_from_seed(&[7; 32]);
+        let b = Keypair::dilithium_from_seed(&[7; 32]);
+        let c = Keypair::dilithium_from_seed(&[8; 32]);
+
+        assert_eq!(a.public().to_peer_id(), b.public().to_peer_id());
+        assert_ne!(a.public().to_peer_id(), c.public().to_peer_id());
+        assert_eq!(a.dilithium_level(), Some(DilithiumLevel::default()));
+
+        // Pinned so that changes to the seed expansion are caught.
+        assert_eq!(
+            a.public().to_peer_id().to_base58(),
+            "QmbNi7rFPGFYsW4wPURfjrGvMpZN9qyL3ZuJ4AcV5j3uRg"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn empty_message_can_be_signed() {
+        for level in [
+            DilithiumLevel::Level2,
+            DilithiumLevel::Level3,
+            DilithiumLevel::Level5,
+        ] {
+            let keypair = Keypair::generate_dilithium_level(level);
+            let sig = keypair.sign(&[]).unwrap();
+
+            assert!(keypair.public().verify(&[], &sig));
+            assert!(!keypair.public().verify(&[0], &sig));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn key_type_lengths_match_dilithium_artifacts() {
+        // The lengths depend on the level, which the key type does not carry.
+        assert_eq!(KeyType::Dilithium.public_key_len(), None);
+        assert_eq!(KeyType::Dilithium.signature_len(), None);
+
+        let levels = DilithiumLevel::ALL;
+        assert_eq!(
+            KeyType::Dilithium.public_key_lens(),
+            levels.map(DilithiumLevel::public_key_len)
+        );
+        assert_eq!(
+            KeyType::Dilithium.signature_lens(),
+            levels.map(DilithiumLevel::signature_len)
+        );
+
+        for level in levels {
+            let keypair = Keypair::generate_dilithium_level(level);
+            let public = keypair.public();
+            let sig = keypair.sign(b"hello").unwrap();
+
+            assert_eq!(public.raw_bytes().len(), level.public_key_len());
+            assert_eq!(public.signature_len(), Some(sig.len()));
+            assert!(KeyType::Dilithium.signature_lens().contains(&sig.len()));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+    fn key_type_lengths_match_ed25519_and_hybrid_artifacts() {
+        let keypair = Keypair::generate_ed25519();
+        let proto = proto::PublicKey::from(&keypair.public());
+        assert_eq!(keypair.key_type().public_key_len(), Some(proto.Data.len()));
+        assert_eq!(
+            keypair.key_type().signature_len(),
+            Some(keypair.sign(b"hello").unwrap().len())
+        );
+
+        assert_eq!(KeyType::HybridDilithiumEd25519.public_key_len(), None);
+        assert_eq!(KeyType::HybridDilithiumEd25519.signature_len(), None);
+        for level in DilithiumLevel::ALL {
+            let keypair = Keypair::from(hybrid::Keypair::from_components(
+                dilithium::Keypair::generate(level),
+                ed25519::Keypair::generate(),
+            ));
+            let proto = proto::PublicKey::from(&keypair.public());
+            let sig = keypair.sign(b"hello").unwrap();
+
+            assert!(KeyType::HybridDilithiumEd25519
+                .public_key_lens()
+                .contains(&proto.Data.len()));
+            assert!(KeyType::HybridDilithiumEd25519
+                .signature_lens()
+                .contains(&sig.len()));
+            assert_eq!(keypair.public().signature_len(), Some(sig.len()));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "secp256k1", feature = "rand"))]
+    fn key_type_lengths_match_secp256k1_artifacts() {
+        let keypair = Keypair::generate_secp256k1();
+        let proto = proto::PublicKey::from(&keypair.public());
+
+        assert_eq!(keypair.key_type().public_key_len(), Some(proto.Data.len()));
+        assert_eq!(keypair.key_type().signature_len(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn truncated_dilithium_public_key_is_rejected() {
+        use quick_protobuf::MessageWrite;
+
+        let mut proto = proto::PublicKey::from(&Keypair::generate_dilithium().public());
+        proto.Data.pop();
+
+        let mut buf = Vec::with_capacity(proto.get_size());
+        proto.write_message(&mut Writer::new(&mut buf)).unwrap();
+
+        assert!(PublicKey::try_decode_protobuf(&buf).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "multibase", feature = "dilithium"))]
+    fn dilithium_multibase_roundtrip() {
+        let public = Keypair::generate_dilithium().public();
+
+        for base in [multibase::Base::Base58Btc, multibase::Base::Base64Url] {
+            let encoded = public.to_multibase(base);
+            assert_eq!(encoded.chars().next(), Some(base.code()));
+            assert_eq!(PublicKey::from_multibase(&encoded).unwrap(), public);
+        }
+
+        assert_eq!(
+            public.to_multibase_default(),
+            public.to_multibase(multibase::Base::Base58Btc)
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "multibase", feature = "dilithium"))]
+    fn multibase_with_corrupted_prefix_is_rejected() {
+        let encoded = Keypair::generate_dilithium()
+            .public()
+            .to_multibase_default();
+
+        // `!` is not a multibase code.
+        assert!(PublicKey::from_multibase(&format!("!{}", &encoded[1..])).is_err());
+        // Base58btc data read as base64url decodes to garbage.
+        assert!(PublicKey::from_multibase(&format!("u{}", &encoded[1..])).is_err());
+        assert!(PublicKey::from_multibase("").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn conversion_traits_dilithium() {
+        let keypair = Keypair::generate_dilithium();
+        let public_key = PublicKey::from(&keypair);
+        assert_eq!(public_key, keypair.public());
+
+        let encoded = public_key.encode_protobuf();
+        assert_eq!(PublicKey::try_from(encoded.as_slice()).unwrap(), public_key);
+
+        let encoded = keypair.to_protobuf_encoding().unwrap();
+        let decoded = Keypair::try_from(encoded.as_slice()).unwrap();
+        assert_eq!(decoded.public(), public_key);
+
+        assert!(PublicKey::try_from(&encoded[..4]).is_err());
+        assert!(Keypair::try_from(&[][..]).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ed25519", feature = "rand"))]
+    fn conversion_traits_ed25519() {
+        let keypair = Keypair::generate_ed25519();
+        let public_key: PublicKey = (&keypair).into();
+        assert_eq!(public_key, keypair.public());
+
+        let encoded = public_key.encode_protobuf();
+        let decoded: PublicKey = encoded.as_slice().try_into().unwrap();
+        assert_eq!(decoded, public_key);
+
+        let encoded = keypair.to_protobuf_encoding().unwrap();
+        let decoded: Keypair = encoded.as_slice().try_into().unwrap();
+        assert_eq!(decoded.public(), public_key);
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn sign_with_context_dilithium() {
+        let keypair = Keypair::generate_dilithium();
+        let public_key = keypair.public();
+        let msg = b"hello";
+
+        let sig = keypair.sign_with_context(b"protocol-a", msg).unwrap();
+        assert!(public_key.verify_with_context(b"protocol-a", msg, &sig));
+        assert!(!public_key.verify_with_context(b"protocol-b", msg, &sig));
+        assert!(!public_key.verify(msg, &sig));
+
+        let plain = keypair.sign(msg).unwrap();
+        assert!(public_key.verify(msg, &keypair.sign_with_context(b"", msg).unwrap()));
+        assert!(public_key.verify_with_context(b"", msg, &plain));
+
+        let long = [0u8; Keypair::MAX_CONTEXT_LEN + 1];
+        assert!(matches!(
+            keypair.sign_with_context(&long, msg),
+            Err(SigningError::ContextTooLong(256))
+        ));
+        assert!(!public_key.verify_with_context(&long, msg, &sig));
+    }
+
+    #[test]
+    #[cfg(all(feature = "ed25519", feature = "rand"))]
+    fn sign_with_context_ed25519() {
+        let keypair = Keypair::generate_ed25519();
+        let public_key = keypair.public();
+        let msg = b"hello";
+
+        let sig = keypair.sign_with_context(b"protocol-a", msg).unwrap();
+        assert!(public_key.verify_with_context(b"protocol-a", msg, &sig));
+        assert!(!public_key.verify_with_context(b"protocol-b", msg, &sig));
+        assert!(!public_key.verify(msg, &sig));
+
+        let plain = keypair.sign(msg).unwrap();
+        assert_eq!(keypair.sign_with_context(b"", msg).unwrap(), plain);
+        assert!(public_key.verify_with_context(b"", msg, &plain));
+
+        let long = [0u8; Keypair::MAX_CONTEXT_LEN + 1];
+        assert!(matches!(
+            keypair.sign_with_context(&long, msg),
+            Err(SigningError::ContextTooLong(256))
+        ));
+    }
 }
@@ -32,45 +32,138 @@
 //! binary representation of the specific key type
 //! (e.g. [ed25519 binary format](https://datatracker.ietf.org/doc/html/rfc8032#section-5.1.5)).
 //! All key types have functions to enable conversion to/from their binary representations.
+//!
+//! Without the default `std` feature the crate is `no_std` and only requires `alloc`.
+//! Key types whose backends need the standard library enable `std` themselves, so
+//! only Ed25519 keys and, with the `dilithium-verify` feature, Dilithium public keys
+//! are available in `no_std` builds.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+extern crate alloc;
+
 #[cfg(any(
     feature = "ecdsa",
     feature = "secp256k1",
     feature = "ed25519",
     feature = "rsa",
-    feature = "dilithium"
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
 ))]
 mod proto {
     #![allow(unreachable_pub)]
     include!("generated/mod.rs");
-    pub(crate) use self::keys_proto::*;
+    pub(crate) use self::{envelope_proto::*, keys_proto::*};
 }
 
+#[cfg(feature = "dilithium-verify")]
+pub mod dilithium;
+
 #[cfg(feature = "ecdsa")]
 pub mod ecdsa;
 
 #[cfg(feature = "ed25519")]
 pub mod ed25519;
 
+#[cfg(feature = "falcon")]
+pub mod falcon;
+
+#[cfg(all(feature = "dilithium", feature = "ed25519"))]
+pub mod hybrid;
+
+#[cfg(feature = "kyber")]
+pub mod kem;
+
 #[cfg(all(feature = "rsa", not(target_arch = "wasm32")))]
 pub mod rsa;
 
 #[cfg(feature = "secp256k1")]
 pub mod secp256k1;
 
+#[cfg(feature = "sphincs-plus")]
+pub mod sphincs_plus;
+
+#[cfg(feature = "rand")]
+mod builder;
+#[cfg(all(
+    feature = "cbor",
+    any(
+        feature = "ecdsa",
+        feature = "secp256k1",
+        feature = "ed25519",
+        feature = "rsa",
+        feature = "dilithium",
+        feature = "sphincs-plus",
+        feature = "falcon"
+    )
+))]
+mod cbor;
+#[cfg(feature = "compression")]
+mod compression;
+#[cfg(feature = "encrypted-storage")]
+mod encrypted_storage;
 mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fragment;
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
+))]
+mod hex;
+#[cfg(feature = "identity-file")]
+mod identity_file;
+#[cfg(feature = "peerid")]
+mod identity_proof;
+#[cfg(feature = "jwk")]
+mod jwk;
 mod keypair;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
+))]
+mod multisig;
 #[cfg(feature = "peerid")]
 mod peer_id;
+#[cfg(feature = "pem")]
+mod pem;
+mod signature;
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
+))]
+mod signed_envelope;
+mod signer_provider;
+#[cfg(feature = "verification-cache")]
+mod verification_cache;
 
 #[cfg(any(
     feature = "ecdsa",
     feature = "secp256k1",
     feature = "ed25519",
     feature = "rsa",
-    feature = "dilithium"
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
 ))]
 impl zeroize::Zeroize for proto::PrivateKey {
     fn zeroize(&mut self) {
@@ -83,7 +176,9 @@ impl zeroize::Zeroize for proto::PrivateKey {
     feature = "secp256k1",
     feature = "ed25519",
     feature = "rsa",
-    feature = "dilithium"
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
 ))]
 impl From<&PublicKey> for proto::PublicKey {
     fn from(key: &PublicKey) -> Self {
@@ -111,36 +206,392 @@ impl From<&PublicKey> for proto::PublicKey {
             #[cfg(feature = "dilithium")]
             keypair::PublicKeyInner::Dilithium(key) => proto::PublicKey {
                 Type: proto::KeyType::Dilithium,
+                Data: key.to_bytes(),
+            },
+            #[cfg(all(feature = "dilithium", feature = "ed25519"))]
+            keypair::PublicKeyInner::HybridDilithiumEd25519(key) => proto::PublicKey {
+                Type: proto::KeyType::HybridDilithiumEd25519,
+                Data: key.to_bytes(),
+            },
+            #[cfg(feature = "sphincs-plus")]
+            keypair::PublicKeyInner::SphincsPlus(key) => proto::PublicKey {
+                Type: proto::KeyType::SphincsPlus,
                 Data: key.to_bytes().to_vec(),
             },
+            #[cfg(feature = "falcon")]
+            keypair::PublicKeyInner::Falcon(key) => proto::PublicKey {
+                Type: proto::KeyType::Falcon,
+                Data: key.to_bytes(),
+            },
         }
     }
 }
 
-pub use error::{DecodingError, OtherVariantError, SigningError};
-pub use keypair::{Keypair, PublicKey};
+#[cfg(feature = "rand")]
+pub use builder::KeypairBuilder;
+#[cfg(feature = "compression")]
+pub use compression::{compress_signature, decompress_signature, MAX_DECOMPRESSED_SIGNATURE_LEN};
+#[cfg(feature = "dilithium-verify")]
+pub use dilithium::DilithiumLevel;
+pub use error::{
+    BackendError, DecodingError, DeriveError, EncodingError, GenerationError, MalformedKeyError,
+    OtherVariantError, ParseKeyTypeError, SigningError, ValidationError, VerificationError,
+};
+pub use fragment::{fragment_signature, reassemble_signature, SignatureFragment};
 #[cfg(feature = "peerid")]
-pub use peer_id::{ParseError, PeerId};
+pub use identity_proof::IdentityProof;
+#[cfg(feature = "peerid")]
+pub use keypair::peer_id_from_public_key_bytes;
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
+))]
+pub use keypair::{inspect_protobuf, KeyInfo};
+pub use keypair::{
+    verify_against_any, verify_typed_against_any, Keypair, KeypairOrPublic, PreparedPublicKey,
+    PublicKey, Signer, Verifier,
+};
+#[cfg(feature = "metrics")]
+pub use metrics::{CryptoMetrics, CryptoOperation, OperationMetrics};
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
+))]
+pub use multisig::MultiSig;
+#[cfg(feature = "peerid")]
+pub use peer_id::{peer_id_from_multiaddr_str, ParseError, PeerId, PeerIdHasher};
+pub use signature::Signature;
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "rsa",
+    feature = "dilithium",
+    feature = "sphincs-plus",
+    feature = "falcon"
+))]
+pub use signed_envelope::{EnvelopeError, SignedEnvelope};
+pub use signer_provider::{AsyncSigner, PublicKeyProvider};
+#[cfg(feature = "verification-cache")]
+pub use verification_cache::VerificationCache;
 
 /// The type of key a `KeyPair` is holding.
-#[derive(Debug, PartialEq, Eq)]
+///
+/// New key types may be added in minor releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(clippy::upper_case_acronyms)]
+#[non_exhaustive]
 pub enum KeyType {
     Ed25519,
     RSA,
     Secp256k1,
     Ecdsa,
+    /// Any [`DilithiumLevel`].
+    ///
+    /// The level is not carried here, so that `KeyType` stays a fieldless enum mirroring
+    /// the protobuf key types; it is reported by [`PublicKey::dilithium_level`] and
+    /// [`Keypair::dilithium_level`], and encoded in the key bytes.
     Dilithium,
+    /// Experimental: its protobuf tag is provisional, see [`KeyType::to_protobuf_tag`].
+    HybridDilithiumEd25519,
+    /// Experimental: its protobuf tag is provisional, see [`KeyType::to_protobuf_tag`].
+    SphincsPlus,
+    /// Experimental: its protobuf tag is provisional, see [`KeyType::to_protobuf_tag`].
+    Falcon,
+}
+
+impl KeyType {
+    /// The length in bytes of a signature produced by this key type, or `None` if
+    /// signatures are variable-length or, for Dilithium and the hybrid type, depend on
+    /// the [`DilithiumLevel`].
+    ///
+    /// [`KeyType::signature_lens`] lists the lengths of every level, and
+    /// [`PublicKey::signature_len`] gives the length for a given key.
+    pub const fn signature_len(&self) -> Option<usize> {
+        match self.signature_lens() {
+            [len] => Some(*len),
+            _ => None,
+        }
+    }
+
+    /// Every length in bytes a signature of this key type can have, from shortest to
+    /// longest: one per [`DilithiumLevel`] for Dilithium and the hybrid type, a single
+    /// length for the other fixed-length types, and none if signatures are
+    /// variable-length.
+    pub const fn signature_lens(&self) -> &'static [usize] {
+        match self {
+            KeyType::Ed25519 => &[64],
+            // ML-DSA-44, ML-DSA-65 and ML-DSA-87.
+            KeyType::Dilithium => &[2420, 3309, 4627],
+            // Both components plus their length prefixes.
+            KeyType::HybridDilithiumEd25519 => {
+                &[2 + 2420 + 1 + 64, 2 + 3309 + 1 + 64, 2 + 4627 + 1 + 64]
+            }
+            // SLH-DSA-SHA2-256f.
+            KeyType::SphincsPlus => &[49856],
+            // Falcon signatures are variable-length, even if FN-DSA pads them.
+            KeyType::RSA | KeyType::Secp256k1 | KeyType::Ecdsa | KeyType::Falcon => &[],
+        }
+    }
+
+    /// The largest number of bytes a signature of this key type adds to a message once
+    /// framed with a one-byte key type tag and a varint length prefix.
+    ///
+    /// Variable-length signatures are counted at their maximum size: 1024 bytes for RSA
+    /// (8192-bit moduli), 72 bytes for DER-encoded ECDSA and Secp256k1 and 1280 bytes
+    /// for Falcon-1024. Dilithium is counted at its largest level, ML-DSA-87.
+    pub const fn max_signature_overhead(&self) -> usize {
+        let max_len = match self {
+            KeyType::RSA => 1024,
+            KeyType::Secp256k1 | KeyType::Ecdsa => 72,
+            KeyType::Falcon => 1280,
+            KeyType::Ed25519
+            | KeyType::Dilithium
+            | KeyType::HybridDilithiumEd25519
+            | KeyType::SphincsPlus => match self.signature_lens() {
+                [.., max] => *max,
+                [] => unreachable!(),
+            },
+        };
+
+        1 + varint_len(max_len) + max_len
+    }
+
+    /// The approximate CPU cost of signing with this key type, relative to Ed25519
+    /// signing as `1`.
+    ///
+    /// The weights are the ratios of the short message signing times in
+    /// `BENCHMARKS.md`, rounded, e.g. 480μs for Dilithium against 15μs for Ed25519. They
+    /// are meant for budgeting CPU between key types, not for precise accounting.
+    /// Dilithium is weighted at the default [`DilithiumLevel`], and the hybrid type as
+    /// the sum of its halves. RSA, SPHINCS+ and Falcon are not benchmarked there; their
+    /// weights are rough estimates, RSA at 2048 bits.
+    pub const fn relative_sign_cost(&self) -> u32 {
+        match self {
+            KeyType::Ed25519 => 1,
+            KeyType::Secp256k1 => 5,
+            KeyType::Ecdsa => 6,
+            KeyType::RSA => 60,
+            KeyType::Dilithium => 32,
+            KeyType::HybridDilithiumEd25519 => 33,
+            KeyType::SphincsPlus => 3600,
+            KeyType::Falcon => 32,
+        }
+    }
+
+    /// The approximate CPU cost of verifying a signature of this key type, relative
+    /// to Ed25519 verification as `1`.
+    ///
+    /// Like [`KeyType::relative_sign_cost`], these are the rounded ratios of the
+    /// verification times in `BENCHMARKS.md`, e.g. 180μs for Dilithium against 50μs for
+    /// Ed25519, with rough estimates for RSA, SPHINCS+ and Falcon. Verification is much
+    /// cheaper than signing for the post-quantum schemes, so the two weights are on
+    /// separate scales and should not be compared.
+    pub const fn relative_verify_cost(&self) -> u32 {
+        match self {
+            KeyType::Ed25519 | KeyType::RSA | KeyType::Falcon => 1,
+            KeyType::Secp256k1 => 3,
+            KeyType::Ecdsa => 4,
+            KeyType::Dilithium => 4,
+            KeyType::HybridDilithiumEd25519 => 5,
+            KeyType::SphincsPlus => 50,
+        }
+    }
+
+    /// The length in bytes of the encoded public key carried in the protobuf encoding,
+    /// or `None` if it is variable-length or, for Dilithium and the hybrid type,
+    /// depends on the [`DilithiumLevel`].
+    ///
+    /// [`KeyType::public_key_lens`] lists the lengths of every level.
+    pub const fn public_key_len(&self) -> Option<usize> {
+        match self.public_key_lens() {
+            [len] => Some(*len),
+            _ => None,
+        }
+    }
+
+    /// Every length in bytes the encoded public key of this key type can have, from
+    /// shortest to longest: one per [`DilithiumLevel`] for Dilithium and the hybrid
+    /// type, a single length for the other fixed-length types, and none if the
+    /// encoding is variable-length.
+    pub const fn public_key_lens(&self) -> &'static [usize] {
+        match self {
+            KeyType::Ed25519 => &[32],
+            KeyType::Secp256k1 => &[33],
+            // ML-DSA-44, ML-DSA-65 and ML-DSA-87.
+            KeyType::Dilithium => &[1312, 1952, 2592],
+            // Both components plus their length prefixes.
+            KeyType::HybridDilithiumEd25519 => {
+                &[2 + 1312 + 1 + 32, 2 + 1952 + 1 + 32, 2 + 2592 + 1 + 32]
+            }
+            KeyType::SphincsPlus => &[64],
+            // Falcon-1024.
+            KeyType::Falcon => &[1793],
+            KeyType::RSA | KeyType::Ecdsa => &[],
+        }
+    }
+
+    /// The tag identifying this key type in the `KeyType` enum of the protobuf key
+    /// encoding, e.g. `4` for Dilithium.
+    ///
+    /// Tags `0` to `4` are part of the wire format. The tags of the hybrid, SPHINCS+ and
+    /// Falcon key types (`5`, `6` and `7`) are provisional: no specification allocates
+    /// them yet, and they may change in a future release once one does. Keys and
+    /// signatures framed with them should not be stored or exchanged with other
+    /// implementations.
+    pub const fn to_protobuf_tag(&self) -> i32 {
+        match self {
+            KeyType::RSA => 0,
+            KeyType::Ed25519 => 1,
+            KeyType::Secp256k1 => 2,
+            KeyType::Ecdsa => 3,
+            KeyType::Dilithium => 4,
+            KeyType::HybridDilithiumEd25519 => 5,
+            KeyType::SphincsPlus => 6,
+            KeyType::Falcon => 7,
+        }
+    }
+
+    /// Whether signatures of this key type remain secure against a quantum adversary.
+    ///
+    /// This is `true` for the [`PostQuantum`](SecurityCategory::PostQuantum) and
+    /// [`Hybrid`](SecurityCategory::Hybrid) categories.
+    pub const fn is_post_quantum(&self) -> bool {
+        !matches!(self.security_category(), SecurityCategory::Classical)
+    }
+
+    /// The [`SecurityCategory`] of this key type.
+    pub const fn security_category(&self) -> SecurityCategory {
+        match self {
+            KeyType::Ed25519 | KeyType::RSA | KeyType::Secp256k1 | KeyType::Ecdsa => {
+                SecurityCategory::Classical
+            }
+            KeyType::Dilithium | KeyType::SphincsPlus | KeyType::Falcon => {
+                SecurityCategory::PostQuantum
+            }
+            KeyType::HybridDilithiumEd25519 => SecurityCategory::Hybrid,
+        }
+    }
+
+    /// The key type identified by a protobuf `KeyType` tag, or `None` if the tag is unknown.
+    ///
+    /// This does not depend on the enabled features, so it can be used to route
+    /// encoded keys without decoding them.
+    pub const fn from_protobuf_tag(tag: i32) -> Option<KeyType> {
+        match tag {
+            0 => Some(KeyType::RSA),
+            1 => Some(KeyType::Ed25519),
+            2 => Some(KeyType::Secp256k1),
+            3 => Some(KeyType::Ecdsa),
+            4 => Some(KeyType::Dilithium),
+            5 => Some(KeyType::HybridDilithiumEd25519),
+            6 => Some(KeyType::SphincsPlus),
+            7 => Some(KeyType::Falcon),
+            _ => None,
+        }
+    }
+
+    /// The key types supported by this build, in protobuf tag order, e.g. to advertise
+    /// them in a handshake and negotiate a scheme both peers support.
+    ///
+    /// Dilithium is present whenever the default `dilithium` feature is, the hybrid
+    /// type when both `dilithium` and `ed25519` are, and every other type when its
+    /// feature is enabled. RSA is never supported on `wasm32`.
+    pub fn enabled_variants() -> alloc::vec::Vec<KeyType> {
+        [
+            (
+                KeyType::RSA,
+                cfg!(all(feature = "rsa", not(target_arch = "wasm32"))),
+            ),
+            (KeyType::Ed25519, cfg!(feature = "ed25519")),
+            (KeyType::Secp256k1, cfg!(feature = "secp256k1")),
+            (KeyType::Ecdsa, cfg!(feature = "ecdsa")),
+            (KeyType::Dilithium, cfg!(feature = "dilithium")),
+            (
+                KeyType::HybridDilithiumEd25519,
+                cfg!(all(feature = "dilithium", feature = "ed25519")),
+            ),
+            (KeyType::SphincsPlus, cfg!(feature = "sphincs-plus")),
+            (KeyType::Falcon, cfg!(feature = "falcon")),
+        ]
+        .into_iter()
+        .filter_map(|(key_type, enabled)| enabled.then_some(key_type))
+        .collect()
+    }
+}
+
+/// The number of bytes of the unsigned varint encoding of `n`.
+const fn varint_len(mut n: usize) -> usize {
+    let mut len = 1;
+    while n >= 0x80 {
+        n >>= 7;
+        len += 1;
+    }
+    len
+}
+
+/// Parses the [`Display`](core::fmt::Display) name of a key type, ignoring ASCII case.
+///
+/// Common aliases are accepted too: `ml-dsa` for Dilithium, `slh-dsa` and `sphincs+` for
+/// SPHINCS+, `fn-dsa` for Falcon, `hybrid` for the hybrid Dilithium+Ed25519 type, and the
+/// kebab-case spellings `hybrid-dilithium-ed25519` and `sphincs-plus`.
+impl core::str::FromStr for KeyType {
+    type Err = ParseKeyTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key_type = match s.to_ascii_lowercase().as_str() {
+            "ed25519" => KeyType::Ed25519,
+            "rsa" => KeyType::RSA,
+            "secp256k1" => KeyType::Secp256k1,
+            "ecdsa" => KeyType::Ecdsa,
+            "dilithium" | "ml-dsa" | "mldsa" => KeyType::Dilithium,
+            "hybriddilithiumed25519" | "hybrid-dilithium-ed25519" | "hybrid" => {
+                KeyType::HybridDilithiumEd25519
+            }
+            "sphincsplus" | "sphincs-plus" | "sphincs+" | "slh-dsa" | "slhdsa" => {
+                KeyType::SphincsPlus
+            }
+            "falcon" | "fn-dsa" | "fndsa" => KeyType::Falcon,
+            _ => return Err(ParseKeyTypeError::new(s)),
+        };
+        Ok(key_type)
+    }
+}
+
+/// Whether a [`KeyType`] is secure against quantum adversaries, see
+/// [`KeyType::security_category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityCategory {
+    /// Security rests on problems a quantum computer can solve, e.g. discrete logarithms.
+    Classical,
+    /// Security rests only on problems believed to be hard for quantum computers.
+    PostQuantum,
+    /// Signatures combine a post-quantum and a classical scheme and are only valid if
+    /// both are, so they remain secure as long as either scheme is.
+    Hybrid,
 }
 
-impl std::fmt::Display for KeyType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for KeyType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             KeyType::Ed25519 => f.write_str("Ed25519"),
             KeyType::RSA => f.write_str("RSA"),
             KeyType::Secp256k1 => f.write_str("Secp256k1"),
             KeyType::Ecdsa => f.write_str("Ecdsa"),
             KeyType::Dilithium => f.write_str("Dilithium"),
+            KeyType::HybridDilithiumEd25519 => f.write_str("HybridDilithiumEd25519"),
+            KeyType::SphincsPlus => f.write_str("SphincsPlus"),
+            KeyType::Falcon => f.write_str("Falcon"),
         }
     }
 }
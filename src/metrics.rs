@@ -0,0 +1,231 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Process-wide latency metrics of key generation, signing and verification.
+//!
+//! Only compiled with the `metrics` feature; without it no timer is started anywhere.
+//! `std::time::Instant` is unavailable on `wasm32-unknown-unknown`, so the feature is
+//! not meant for the browser.
+
+use crate::KeyType;
+use std::{
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// The operation a measurement belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CryptoOperation {
+    /// Generating a keypair with one of the `Keypair::generate_*` constructors.
+    Generate,
+    /// [`Keypair::sign`](crate::Keypair::sign).
+    Sign,
+    /// [`PublicKey::verify`](crate::PublicKey::verify), which most other
+    /// verification methods go through.
+    Verify,
+}
+
+impl CryptoOperation {
+    const ALL: [CryptoOperation; 3] = [
+        CryptoOperation::Generate,
+        CryptoOperation::Sign,
+        CryptoOperation::Verify,
+    ];
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The recorded durations of one operation on one key type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperationMetrics {
+    /// How many operations were recorded.
+    pub count: u64,
+    /// The sum of their durations.
+    pub total: Duration,
+    /// The longest duration.
+    pub max: Duration,
+    /// The duration of the most recent operation.
+    pub last: Duration,
+}
+
+impl OperationMetrics {
+    const EMPTY: OperationMetrics = OperationMetrics {
+        count: 0,
+        total: Duration::ZERO,
+        max: Duration::ZERO,
+        last: Duration::ZERO,
+    };
+
+    /// The mean duration, or `None` if nothing was recorded.
+    pub fn mean(&self) -> Option<Duration> {
+        (self.count > 0)
+            .then(|| Duration::from_nanos((self.total.as_nanos() / self.count as u128) as u64))
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+        self.last = elapsed;
+    }
+}
+
+/// Number of protobuf key type tags, used to index the metrics table.
+const KEY_TYPES: usize = 8;
+
+/// Latency metrics of cryptographic operations, per key type and operation.
+///
+/// Every operation in the process is recorded into one global collector;
+/// [`CryptoMetrics::snapshot`] copies it out and [`CryptoMetrics::reset`] clears it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CryptoMetrics {
+    table: [[OperationMetrics; 3]; KEY_TYPES],
+}
+
+static GLOBAL: Mutex<CryptoMetrics> = Mutex::new(CryptoMetrics {
+    table: [[OperationMetrics::EMPTY; 3]; KEY_TYPES],
+});
+
+impl CryptoMetrics {
+    /// A copy of the metrics recorded so far.
+    pub fn snapshot() -> CryptoMetrics {
+        GLOBAL
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Clear the metrics recorded so far, e.g. at the start of a profiling window.
+    pub fn reset() {
+        *GLOBAL.lock().unwrap_or_else(PoisonError::into_inner) = CryptoMetrics {
+            table: [[OperationMetrics::EMPTY; 3]; KEY_TYPES],
+        };
+    }
+
+    /// The metrics of `op` on keys of `key_type`.
+    pub fn get(&self, key_type: KeyType, op: CryptoOperation) -> OperationMetrics {
+        self.table[key_type.to_protobuf_tag() as usize][op.index()]
+    }
+
+    /// Every key type and operation with at least one recorded measurement.
+    pub fn iter(&self) -> impl Iterator<Item = (KeyType, CryptoOperation, OperationMetrics)> + '_ {
+        self.table.iter().enumerate().flat_map(|(tag, row)| {
+            let key_type = KeyType::from_protobuf_tag(tag as i32).expect("tags 0 to 7 are known");
+            CryptoOperation::ALL
+                .into_iter()
+                .map(move |op| (key_type, op, row[op.index()]))
+                .filter(|(_, _, m)| m.count > 0)
+        })
+    }
+}
+
+/// Records the time from its creation until it is dropped into the global collector.
+pub(crate) struct Timer {
+    key_type: KeyType,
+    op: CryptoOperation,
+    start: Instant,
+}
+
+impl Timer {
+    pub(crate) fn start(key_type: KeyType, op: CryptoOperation) -> Timer {
+        Timer {
+            key_type,
+            op,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        GLOBAL.lock().unwrap_or_else(PoisonError::into_inner).table
+            [self.key_type.to_protobuf_tag() as usize][self.op.index()]
+        .record(elapsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Keypair;
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn sign_and_verify_are_recorded() {
+        let before = CryptoMetrics::snapshot();
+
+        let keypair = Keypair::generate_dilithium();
+        let sig = keypair.sign(b"timed").unwrap();
+        assert!(keypair.public().verify(b"timed", &sig));
+
+        let after = CryptoMetrics::snapshot();
+        for op in CryptoOperation::ALL {
+            let (before, after) = (
+                before.get(KeyType::Dilithium, op),
+                after.get(KeyType::Dilithium, op),
+            );
+            assert!(after.count > before.count, "{op:?}");
+            assert!(after.total > before.total, "{op:?}");
+            assert!(after.last > Duration::ZERO, "{op:?}");
+            assert!(after.max >= after.last, "{op:?}");
+        }
+        assert!(
+            after
+                .iter()
+                .any(|(key_type, op, _)| key_type == KeyType::Dilithium
+                    && op == CryptoOperation::Sign)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ed25519")]
+    fn key_types_are_recorded_separately() {
+        let before = CryptoMetrics::snapshot().get(KeyType::Ed25519, CryptoOperation::Sign);
+
+        Keypair::generate_ed25519().sign(b"timed").unwrap();
+
+        let after = CryptoMetrics::snapshot().get(KeyType::Ed25519, CryptoOperation::Sign);
+        assert!(after.count > before.count);
+        assert!(after.mean().is_some());
+    }
+
+    #[test]
+    fn mean_of_nothing_is_none() {
+        let mut metrics = OperationMetrics::default();
+        assert_eq!(metrics.mean(), None);
+
+        metrics.record(Duration::from_millis(2));
+        metrics.record(Duration::from_millis(4));
+        assert_eq!(metrics.mean(), Some(Duration::from_millis(3)));
+        assert_eq!(metrics.max, Duration::from_millis(4));
+        assert_eq!(metrics.last, Duration::from_millis(4));
+
+        // Counts beyond `u32::MAX` must neither truncate nor divide by zero.
+        let metrics = OperationMetrics {
+            count: 1 << 32,
+            total: Duration::from_secs(1 << 32),
+            ..OperationMetrics::default()
+        };
+        assert_eq!(metrics.mean(), Some(Duration::from_secs(1)));
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Threshold checks over signatures of the same message by several keys.
+//!
+//! None of the supported schemes aggregates signatures natively, so a [`MultiSig`]
+//! simply holds every member's public key and signature and verifies each of them.
+//!
+//! The keys travel with the signatures, so anyone can add entries for keys they just
+//! generated. Only the verifier knows which keys belong to the quorum, and
+//! [`MultiSig::verify_threshold`] counts only those.
+
+use crate::{error::DecodingError, PublicKey};
+use alloc::{collections::BTreeSet, format, vec::Vec};
+use unsigned_varint::encode::usize_buffer;
+
+/// The signatures of several keys over the same message, e.g. collected from a quorum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiSig {
+    entries: Vec<(PublicKey, Vec<u8>)>,
+}
+
+impl MultiSig {
+    /// Collect the public key and signature of every member.
+    pub fn collect(entries: Vec<(PublicKey, Vec<u8>)>) -> Self {
+        MultiSig { entries }
+    }
+
+    /// The collected public keys and signatures.
+    pub fn entries(&self) -> &[(PublicKey, Vec<u8>)] {
+        &self.entries
+    }
+
+    /// The number of collected signatures.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no signature was collected.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The distinct keys whose signature over `msg` is valid.
+    ///
+    /// The keys are not checked against any roster, so this must not decide whether a
+    /// quorum was reached; use [`MultiSig::verify_threshold`] for that.
+    pub fn valid_signers(&self, msg: &[u8]) -> Vec<&PublicKey> {
+        let mut signers = BTreeSet::new();
+        for (key, sig) in &self.entries {
+            if !signers.contains(key) && key.verify(msg, sig) {
+                signers.insert(key);
+            }
+        }
+        signers.into_iter().collect()
+    }
+
+    /// Whether at least `k` distinct keys from `allowed` produced a valid signature
+    /// over `msg`.
+    ///
+    /// Entries for keys outside `allowed` are ignored, so signatures from throwaway keys
+    /// never count. A key that appears several times only counts once, so a member
+    /// cannot reach the threshold on its own by submitting its signature repeatedly.
+    /// Verification stops as soon as the threshold is reached.
+    pub fn verify_threshold(&self, allowed: &[PublicKey], msg: &[u8], k: usize) -> bool {
+        let mut signers = BTreeSet::new();
+        for (key, sig) in &self.entries {
+            if signers.len() >= k {
+                break;
+            }
+            if allowed.contains(key) && !signers.contains(key) && key.verify(msg, sig) {
+                signers.insert(key);
+            }
+        }
+        signers.len() >= k
+    }
+
+    /// Encode as the concatenation of every entry, each made of the varint length and
+    /// protobuf encoding of the public key followed by the varint length and bytes of
+    /// the signature.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for (key, sig) in &self.entries {
+            write_field(&mut buf, &key.encode_protobuf());
+            write_field(&mut buf, sig);
+        }
+        buf
+    }
+
+    /// Decode a [`MultiSig`] encoded by [`MultiSig::to_bytes`].
+    ///
+    /// The signatures are not checked; call [`MultiSig::verify_threshold`].
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, DecodingError> {
+        let mut entries = Vec::new();
+        while !bytes.is_empty() {
+            let key;
+            let sig;
+            (key, bytes) = read_field(bytes)?;
+            (sig, bytes) = read_field(bytes)?;
+            entries.push((PublicKey::try_decode_protobuf(key)?, sig.to_vec()));
+        }
+        Ok(MultiSig { entries })
+    }
+}
+
+fn write_field(buf: &mut Vec<u8>, field: &[u8]) {
+    let mut len_buf = usize_buffer();
+    buf.extend_from_slice(unsigned_varint::encode::usize(field.len(), &mut len_buf));
+    buf.extend_from_slice(field);
+}
+
+fn read_field(bytes: &[u8]) -> Result<(&[u8], &[u8]), DecodingError> {
+    let (len, rest) = unsigned_varint::decode::usize(bytes)
+        .map_err(|e| DecodingError::failed_to_parse("multi-signature field length", e))?;
+    if rest.len() < len {
+        return Err(DecodingError::new(format!(
+            "multi-signature field of {len} bytes is truncated to {}",
+            rest.len()
+        )));
+    }
+    Ok(rest.split_at(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "dilithium")]
+    use crate::Keypair;
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn three_of_five() {
+        let msg = b"block 42";
+        let keys = (0..5)
+            .map(|_| Keypair::generate_dilithium())
+            .collect::<Vec<_>>();
+        let entries = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let signed = if i < 3 { &msg[..] } else { b"block 43" };
+                (key.public(), key.sign(signed).unwrap())
+            })
+            .collect();
+
+        let multisig = MultiSig::collect(entries);
+        let roster = keys.iter().map(Keypair::public).collect::<Vec<_>>();
+
+        assert_eq!(multisig.len(), 5);
+        assert_eq!(multisig.valid_signers(msg).len(), 3);
+        assert!(multisig.verify_threshold(&roster, msg, 0));
+        assert!(multisig.verify_threshold(&roster, msg, 3));
+        assert!(!multisig.verify_threshold(&roster, msg, 4));
+        assert!(!multisig.verify_threshold(&roster[1..], msg, 3));
+
+        let decoded = MultiSig::from_bytes(&multisig.to_bytes()).unwrap();
+        assert_eq!(decoded, multisig);
+        assert!(decoded.verify_threshold(&roster, msg, 3));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn non_members_do_not_count() {
+        let msg = b"block 42";
+        let roster = (0..5)
+            .map(|_| Keypair::generate_dilithium().public())
+            .collect::<Vec<_>>();
+        let outsiders = (0..3)
+            .map(|_| Keypair::generate_dilithium())
+            .collect::<Vec<_>>();
+        let entries = outsiders
+            .iter()
+            .map(|key| (key.public(), key.sign(msg).unwrap()))
+            .collect();
+
+        let multisig = MultiSig::from_bytes(&MultiSig::collect(entries).to_bytes()).unwrap();
+
+        assert_eq!(multisig.valid_signers(msg).len(), 3);
+        assert!(!multisig.verify_threshold(&roster, msg, 1));
+        assert!(!multisig.verify_threshold(&[], msg, 1));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn repeated_signer_counts_once() {
+        let msg = b"block 42";
+        let key = Keypair::generate_dilithium();
+        let entry = (key.public(), key.sign(msg).unwrap());
+
+        let roster = [key.public()];
+        let multisig = MultiSig::collect(alloc::vec![entry.clone(), entry.clone(), entry]);
+
+        assert!(multisig.verify_threshold(&roster, msg, 1));
+        assert!(!multisig.verify_threshold(&roster, msg, 2));
+    }
+
+    #[test]
+    fn truncated_encoding_is_rejected() {
+        assert!(MultiSig::from_bytes(&[]).unwrap().is_empty());
+        assert!(MultiSig::from_bytes(&[5, 1, 2]).is_err());
+        assert!(MultiSig::from_bytes(&[0x80]).is_err());
+    }
+}
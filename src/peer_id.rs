@@ -41,6 +41,75 @@ const MAX_INLINE_KEY_LENGTH: usize = 42;
 const MULTIHASH_IDENTITY_CODE: u64 = 0;
 const MULTIHASH_SHA256_CODE: u64 = 0x12;
 
+/// The multihash code of the `PeerId` of a public key whose protobuf encoding is
+/// `key_enc_len` bytes long: small keys are inlined, larger ones hashed with SHA-256.
+pub(crate) fn multihash_code(key_enc_len: usize) -> u64 {
+    if key_enc_len <= MAX_INLINE_KEY_LENGTH {
+        MULTIHASH_IDENTITY_CODE
+    } else {
+        MULTIHASH_SHA256_CODE
+    }
+}
+
+/// Computes the [`PeerId`] of a public key from its protobuf encoding fed in chunks,
+/// e.g. as a large Dilithium key arrives over a stream.
+///
+/// The result is identical to [`PeerId::from_public_key`] for the concatenation of all
+/// chunks. Only the first 42 bytes are kept, for encodings short enough to be inlined.
+#[derive(Clone)]
+pub struct PeerIdHasher {
+    inline: [u8; MAX_INLINE_KEY_LENGTH],
+    len: usize,
+    sha256: sha2::Sha256,
+}
+
+impl PeerIdHasher {
+    /// Start hashing a new public key encoding.
+    pub fn new() -> Self {
+        PeerIdHasher {
+            inline: [0; MAX_INLINE_KEY_LENGTH],
+            len: 0,
+            sha256: sha2::Sha256::new(),
+        }
+    }
+
+    /// Feed the next chunk of the protobuf encoded public key.
+    pub fn update(&mut self, chunk: &[u8]) {
+        if let Some(inline) = self.inline.get_mut(self.len..self.len + chunk.len()) {
+            inline.copy_from_slice(chunk);
+        }
+        self.len += chunk.len();
+        self.sha256.update(chunk);
+    }
+
+    /// Produce the `PeerId` of the encoding fed so far.
+    pub fn finalize(self) -> PeerId {
+        let multihash = match multihash_code(self.len) {
+            MULTIHASH_IDENTITY_CODE => {
+                Multihash::wrap(MULTIHASH_IDENTITY_CODE, &self.inline[..self.len])
+            }
+            code => Multihash::wrap(code, &self.sha256.finalize()),
+        }
+        .expect("64 byte multihash provides sufficient space");
+
+        PeerId { multihash }
+    }
+}
+
+impl Default for PeerIdHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for PeerIdHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PeerIdHasher")
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
 /// Identifier of a peer of the network.
 ///
 /// The data is a CIDv0 compatible multihash of the protobuf encoded public key of the peer
@@ -63,19 +132,42 @@ impl fmt::Display for PeerId {
 }
 
 impl PeerId {
-    /// Builds a `PeerId` from a public key.
-    pub fn from_public_key(key: &crate::keypair::PublicKey) -> PeerId {
+    /// Builds a `PeerId` from a public key, or from anything that provides one such as
+    /// a [`Keypair`](crate::Keypair) or an [`AsyncSigner`](crate::AsyncSigner).
+    pub fn from_public_key<K: crate::PublicKeyProvider + ?Sized>(key: &K) -> PeerId {
+        let key_enc = key.public_key().encode_protobuf();
+
+        let multihash = match multihash_code(key_enc.len()) {
+            MULTIHASH_IDENTITY_CODE => Multihash::wrap(MULTIHASH_IDENTITY_CODE, &key_enc),
+            code => Multihash::wrap(code, &sha2::Sha256::digest(key_enc)),
+        }
+        .expect("64 byte multihash provides sufficient space");
+
+        PeerId { multihash }
+    }
+
+    /// Builds a `PeerId` from a public key with the given multihash code instead of
+    /// the size-based choice of [`PeerId::from_public_key`].
+    ///
+    /// The libp2p spec only allows SHA-256 (`0x12`) and, for keys whose protobuf
+    /// encoding is at most 42 bytes, the identity multihash (`0x00`); other codes return
+    /// `None`.
+    pub(crate) fn from_public_key_with_hash(
+        key: &crate::keypair::PublicKey,
+        code: u64,
+    ) -> Option<PeerId> {
         let key_enc = key.encode_protobuf();
 
-        let multihash = if key_enc.len() <= MAX_INLINE_KEY_LENGTH {
-            Multihash::wrap(MULTIHASH_IDENTITY_CODE, &key_enc)
-                .expect("64 byte multihash provides sufficient space")
-        } else {
-            Multihash::wrap(MULTIHASH_SHA256_CODE, &sha2::Sha256::digest(key_enc))
-                .expect("64 byte multihash provides sufficient space")
-        };
+        let multihash = match code {
+            MULTIHASH_SHA256_CODE => Multihash::wrap(code, &sha2::Sha256::digest(key_enc)),
+            MULTIHASH_IDENTITY_CODE if key_enc.len() <= MAX_INLINE_KEY_LENGTH => {
+                Multihash::wrap(code, &key_enc)
+            }
+            _ => return None,
+        }
+        .expect("64 byte multihash provides sufficient space");
 
-        PeerId { multihash }
+        Some(PeerId { multihash })
     }
 
     /// Parses a `PeerId` from bytes.
@@ -115,6 +207,29 @@ impl PeerId {
         self.multihash.to_bytes()
     }
 
+    /// Write the raw bytes of this `PeerId`, as returned by [`PeerId::to_bytes`], into
+    /// `out`, returning their length.
+    pub(crate) fn write_bytes(&self, out: &mut [u8]) -> Result<usize, crate::EncodingError> {
+        let mut code_buf = unsigned_varint::encode::u64_buffer();
+        let code = unsigned_varint::encode::u64(self.multihash.code(), &mut code_buf);
+        let mut size_buf = unsigned_varint::encode::u8_buffer();
+        let size = unsigned_varint::encode::u8(self.multihash.size(), &mut size_buf);
+        let digest = self.multihash.digest();
+
+        let needed = code.len() + size.len() + digest.len();
+        let available = out.len();
+        let out = out
+            .get_mut(..needed)
+            .ok_or(crate::EncodingError::BufferTooSmall { needed, available })?;
+        let (code_out, rest) = out.split_at_mut(code.len());
+        let (size_out, digest_out) = rest.split_at_mut(size.len());
+        code_out.copy_from_slice(code);
+        size_out.copy_from_slice(size);
+        digest_out.copy_from_slice(digest);
+
+        Ok(needed)
+    }
+
     /// Returns a base-58 encoded string of this `PeerId`.
     pub fn to_base58(self) -> String {
         bs58::encode(self.to_bytes()).into_string()
@@ -230,6 +345,26 @@ pub enum ParseError {
     UnsupportedCode(u64),
     #[error("invalid multihash")]
     InvalidMultihash(#[from] multihash::Error),
+    #[error("multiaddr has no `/p2p` component")]
+    MissingP2pComponent,
+}
+
+/// Parse the [`PeerId`] out of the `/p2p/<peer id>` component of a multiaddr string,
+/// such as the one produced by [`PublicKey::to_p2p_multiaddr_component`](crate::PublicKey::to_p2p_multiaddr_component).
+///
+/// `s` may be the bare component or a full multiaddr ending in one, e.g.
+/// `/ip4/127.0.0.1/tcp/4001/p2p/Qm...`. If there are several, as in a relayed
+/// address, the last one is the peer the address points to. The legacy `/ipfs`
+/// protocol name is accepted as well.
+pub fn peer_id_from_multiaddr_str(s: &str) -> Result<PeerId, ParseError> {
+    let segments = s.trim().split('/').collect::<Vec<_>>();
+    let value = segments
+        .iter()
+        .rposition(|&segment| segment == "p2p" || segment == "ipfs")
+        .and_then(|i| segments.get(i + 1))
+        .ok_or(ParseError::MissingP2pComponent)?;
+
+    value.parse()
 }
 
 impl FromStr for PeerId {
@@ -248,6 +383,66 @@ impl FromStr for PeerId {
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn dilithium_peer_id_through_multiaddr_component() {
+        let public = crate::Keypair::generate_dilithium().public();
+        let peer_id = public.to_peer_id();
+
+        let component = public.to_p2p_multiaddr_component();
+        assert_eq!(component, format!("/p2p/{peer_id}"));
+        assert_eq!(peer_id_from_multiaddr_str(&component).unwrap(), peer_id);
+
+        let addr = format!("/ip4/127.0.0.1/tcp/4001{component}");
+        assert_eq!(peer_id_from_multiaddr_str(&addr).unwrap(), peer_id);
+
+        let relay = crate::Keypair::generate_dilithium().public().to_peer_id();
+        let relayed = format!("/ip4/127.0.0.1/tcp/4001/p2p/{relay}/p2p-circuit{component}");
+        assert_eq!(peer_id_from_multiaddr_str(&relayed).unwrap(), peer_id);
+        assert_eq!(
+            peer_id_from_multiaddr_str(&format!("/ipfs/{peer_id}")).unwrap(),
+            peer_id
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "dilithium", feature = "rand"))]
+    fn streamed_peer_id_matches_one_shot() {
+        let keys = [
+            crate::Keypair::generate_dilithium().public(),
+            #[cfg(feature = "ed25519")]
+            crate::Keypair::generate_ed25519().public(),
+        ];
+
+        let mut rng = rand::thread_rng();
+        for key in keys {
+            let encoding = key.encode_protobuf();
+            for _ in 0..10 {
+                let mut hasher = PeerIdHasher::new();
+                let mut rest = encoding.as_slice();
+                while !rest.is_empty() {
+                    let (chunk, tail) = rest.split_at(rng.gen_range(0..=rest.len().min(100)));
+                    hasher.update(chunk);
+                    rest = tail;
+                }
+                assert_eq!(hasher.finalize(), key.to_peer_id());
+            }
+        }
+    }
+
+    #[test]
+    fn multiaddr_without_p2p_component_is_rejected() {
+        assert!(matches!(
+            peer_id_from_multiaddr_str("/ip4/127.0.0.1/tcp/4001"),
+            Err(ParseError::MissingP2pComponent)
+        ));
+        assert!(matches!(
+            peer_id_from_multiaddr_str("/ip4/127.0.0.1/tcp/4001/p2p"),
+            Err(ParseError::MissingP2pComponent)
+        ));
+        assert!(peer_id_from_multiaddr_str("/p2p/not-a-peer-id").is_err());
+    }
+
     #[test]
     #[cfg(all(feature = "ed25519", feature = "rand"))]
     fn peer_id_into_bytes_then_from_bytes() {
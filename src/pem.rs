@@ -0,0 +1,186 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! PEM armouring of protobuf encoded private keys.
+//!
+//! ```text
+//! -----BEGIN LIBP2P PRIVATE KEY-----
+//! Key-Type: Dilithium
+//!
+//! <base64 of the protobuf encoding, wrapped at 64 columns>
+//! -----END LIBP2P PRIVATE KEY-----
+//! ```
+
+use crate::{error::DecodingError, KeyType};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use zeroize::Zeroizing;
+
+const BEGIN: &str = "-----BEGIN LIBP2P PRIVATE KEY-----";
+const END: &str = "-----END LIBP2P PRIVATE KEY-----";
+const KEY_TYPE_HEADER: &str = "Key-Type";
+const LINE_WIDTH: usize = 64;
+
+/// Wrap a protobuf encoded private key of the given type in a PEM block.
+pub(crate) fn encode(key_type: KeyType, protobuf: &[u8]) -> Zeroizing<String> {
+    let body = Zeroizing::new(STANDARD.encode(protobuf));
+
+    // Sized up front so that growing the string never leaves a stale copy behind.
+    let mut pem = Zeroizing::new(String::with_capacity(
+        body.len() + body.len() / LINE_WIDTH + 128,
+    ));
+    pem.push_str(BEGIN);
+    pem.push('\n');
+    pem.push_str(&format!("{KEY_TYPE_HEADER}: {key_type}\n\n"));
+    for line in body.as_bytes().chunks(LINE_WIDTH) {
+        pem.push_str(std::str::from_utf8(line).expect("base64 is ASCII"));
+        pem.push('\n');
+    }
+    pem.push_str(END);
+    pem.push('\n');
+    pem
+}
+
+/// Parse a PEM block produced by [`encode`], returning the declared key type and the
+/// protobuf encoding it wraps.
+///
+/// Both LF and CRLF line endings are accepted, as is surrounding whitespace.
+pub(crate) fn decode(pem: &str) -> Result<(KeyType, Zeroizing<Vec<u8>>), DecodingError> {
+    let mut lines = pem.lines().map(str::trim).skip_while(|l| l.is_empty());
+
+    if lines.next() != Some(BEGIN) {
+        return Err(DecodingError::new(format!("PEM must start with `{BEGIN}`")));
+    }
+
+    let mut key_type = None;
+    let mut body = Zeroizing::new(String::with_capacity(pem.len()));
+    let mut found_end = false;
+    for line in lines.by_ref() {
+        if line == END {
+            found_end = true;
+            break;
+        }
+        match line.split_once(':') {
+            Some((name, value)) if body.is_empty() => {
+                if name.trim() == KEY_TYPE_HEADER {
+                    key_type = Some(parse_key_type(value.trim())?);
+                }
+            }
+            _ => body.push_str(line),
+        }
+    }
+
+    if !found_end {
+        return Err(DecodingError::new(format!("PEM must end with `{END}`")));
+    }
+    if lines.any(|l| !l.is_empty()) {
+        return Err(DecodingError::new(format!("unexpected data after `{END}`")));
+    }
+    let key_type = key_type.ok_or_else(|| {
+        DecodingError::new(format!("PEM is missing the `{KEY_TYPE_HEADER}` header"))
+    })?;
+
+    let protobuf = STANDARD
+        .decode(body.as_bytes())
+        .map_err(|e| DecodingError::failed_to_parse("PEM base64 body", e))?;
+
+    Ok((key_type, Zeroizing::new(protobuf)))
+}
+
+/// Map a `Key-Type` header value back to the [`KeyType`], rejecting key types that
+/// were not compiled in before the body is decoded.
+fn parse_key_type(value: &str) -> Result<KeyType, DecodingError> {
+    let (key_type, enabled) = match value {
+        "Ed25519" => (KeyType::Ed25519, cfg!(feature = "ed25519")),
+        "RSA" => (KeyType::RSA, cfg!(feature = "rsa")),
+        "Secp256k1" => (KeyType::Secp256k1, cfg!(feature = "secp256k1")),
+        "Ecdsa" => (KeyType::Ecdsa, cfg!(feature = "ecdsa")),
+        "Dilithium" => (KeyType::Dilithium, cfg!(feature = "dilithium")),
+        "HybridDilithiumEd25519" => (
+            KeyType::HybridDilithiumEd25519,
+            cfg!(all(feature = "dilithium", feature = "ed25519")),
+        ),
+        "SphincsPlus" => (KeyType::SphincsPlus, cfg!(feature = "sphincs-plus")),
+        "Falcon" => (KeyType::Falcon, cfg!(feature = "falcon")),
+        other => {
+            return Err(DecodingError::new(format!(
+                "unknown PEM key type `{other}`"
+            )))
+        }
+    };
+
+    if !enabled {
+        return Err(DecodingError::disabled_key_type(key_type));
+    }
+
+    Ok(key_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn encode_then_decode() {
+        let data = (0..=255u8).collect::<Vec<_>>();
+        let pem = encode(KeyType::Dilithium, &data);
+
+        assert!(pem.lines().all(|l| l.len() <= LINE_WIDTH.max(BEGIN.len())));
+
+        let (key_type, decoded) = decode(&pem).unwrap();
+        assert_eq!(key_type, KeyType::Dilithium);
+        assert_eq!(*decoded, data);
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn crlf_and_trailing_whitespace() {
+        let data = vec![7u8; 100];
+        let pem = encode(KeyType::Dilithium, &data)
+            .lines()
+            .map(|l| format!("{l}  \r\n"))
+            .collect::<String>();
+
+        let (_, decoded) = decode(&format!("\r\n{pem}\r\n\r\n")).unwrap();
+        assert_eq!(*decoded, data);
+    }
+
+    fn block(inner: &str) -> String {
+        format!("{BEGIN}\n{inner}\n{END}\n")
+    }
+
+    #[test]
+    fn malformed_pem_is_rejected() {
+        assert!(decode("").is_err());
+        assert!(decode(&format!("{BEGIN}\nKey-Type: Dilithium\n\nAAAA\n")).is_err());
+        assert!(decode(&block("\nAAAA")).is_err());
+        assert!(decode(&block("Key-Type: Foo\n\nAAAA")).is_err());
+        assert!(decode(&block("Key-Type: Dilithium\n\n!!!!")).is_err());
+        assert!(decode(&format!("{}trailing", block("Key-Type: Dilithium\n\nAAAA"))).is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "ed25519"))]
+    fn disabled_key_type_is_rejected_before_decoding() {
+        let err = decode(&block("Key-Type: Ed25519\n\n!!!!")).unwrap_err();
+
+        assert!(err.to_string().contains("ed25519"));
+    }
+}
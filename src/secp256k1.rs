@@ -20,12 +20,12 @@
 
 //! Secp256k1 keys.
 
-use super::error::DecodingError;
+use super::error::{DecodingError, VerificationError};
 use asn1_der::typed::{DerDecodable, Sequence};
 use core::cmp;
 use core::fmt;
 use core::hash;
-use libsecp256k1::{Message, Signature};
+use libsecp256k1::{Message, RecoveryId, Signature};
 use sha2::{Digest as ShaDigestTrait, Sha256};
 use zeroize::Zeroize;
 
@@ -43,6 +43,12 @@ impl Keypair {
         Keypair::from(SecretKey::generate())
     }
 
+    /// Generate a new sec256k1 `Keypair` using the given RNG.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: rand::RngCore + rand::CryptoRng>(rng: &mut R) -> Keypair {
+        Keypair::from(SecretKey::generate_with_rng(rng))
+    }
+
     /// Get the public key of this keypair.
     pub fn public(&self) -> &PublicKey {
         &self.public
@@ -91,7 +97,13 @@ impl SecretKey {
     /// Generate a new random Secp256k1 secret key.
     #[cfg(feature = "rand")]
     pub fn generate() -> SecretKey {
-        SecretKey(libsecp256k1::SecretKey::random(&mut rand::thread_rng()))
+        SecretKey::generate_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generate a new Secp256k1 secret key using the given RNG.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: rand::RngCore + rand::CryptoRng>(rng: &mut R) -> SecretKey {
+        SecretKey(libsecp256k1::SecretKey::random(rng))
     }
 
     /// Create a secret key from a byte slice, zeroing the slice on success.
@@ -130,28 +142,43 @@ impl SecretKey {
     /// ECDSA signature, as defined in [RFC3278].
     ///
     /// [RFC3278]: https://tools.ietf.org/html/rfc3278#section-8.2
+    ///
+    /// `libsecp256k1` always produces low-s signatures, so they are accepted by
+    /// [`PublicKey::verify_strict`].
     pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
-        let generic_array = Sha256::digest(msg);
-
-        // FIXME: Once `generic-array` hits 1.0, we should be able to just use `Into` here.
-        let mut array = [0u8; 32];
-        array.copy_from_slice(generic_array.as_slice());
-
-        let message = Message::parse(&array);
-
-        libsecp256k1::sign(&message, &self.0)
+        libsecp256k1::sign(&hash_message(msg), &self.0)
             .0
             .serialize_der()
             .as_ref()
             .into()
     }
 
+    /// Sign a message with this secret key, producing a 64-byte `r || s` signature and
+    /// the recovery id that lets [`PublicKey::recover`] compute the public key from it.
+    ///
+    /// Like [`SecretKey::sign`], the message is hashed with SHA-256 first.
+    pub fn sign_recoverable(&self, msg: &[u8]) -> ([u8; 64], u8) {
+        let (sig, recovery_id) = libsecp256k1::sign(&hash_message(msg), &self.0);
+        (sig.serialize(), recovery_id.serialize())
+    }
+
     /// Returns the raw bytes of the secret key.
     pub fn to_bytes(&self) -> [u8; 32] {
         self.0.serialize()
     }
 }
 
+/// The SHA-256 digest of `msg`, which is what gets signed.
+fn hash_message(msg: &[u8]) -> Message {
+    let generic_array = Sha256::digest(msg);
+
+    // FIXME: Once `generic-array` hits 1.0, we should be able to just use `Into` here.
+    let mut array = [0u8; 32];
+    array.copy_from_slice(generic_array.as_slice());
+
+    Message::parse(&array)
+}
+
 /// A Secp256k1 public key.
 #[derive(Eq, Clone)]
 pub struct PublicKey(libsecp256k1::PublicKey);
@@ -196,6 +223,40 @@ impl PublicKey {
         self.verify_hash(Sha256::digest(msg).as_ref(), sig)
     }
 
+    /// Verify the Secp256k1 signature like [`PublicKey::verify`], but reject signatures
+    /// whose `s` is in the upper half of the scalar range.
+    ///
+    /// For every valid signature `(r, s)`, `(r, n - s)` is valid too. Requiring low-s,
+    /// as Bitcoin consensus does, leaves a single accepted encoding per signature.
+    pub fn verify_strict(&self, msg: &[u8], sig: &[u8]) -> bool {
+        Signature::parse_der(sig).is_ok_and(|s| !s.s.is_high()) && self.verify(msg, sig)
+    }
+
+    /// Recover the public key that produced a signature returned by
+    /// [`SecretKey::sign_recoverable`].
+    ///
+    /// A signature over another message recovers to another public key rather than
+    /// failing, so the result must be compared against the expected key or `PeerId`.
+    pub fn recover(
+        msg: &[u8],
+        sig: &[u8],
+        recovery_id: u8,
+    ) -> Result<PublicKey, VerificationError> {
+        let sig = Signature::parse_standard_slice(sig)
+            .map_err(|_| VerificationError::MalformedSignature)?;
+        let recovery_id =
+            RecoveryId::parse(recovery_id).map_err(|_| VerificationError::MalformedSignature)?;
+
+        libsecp256k1::recover(&hash_message(msg), &sig, &recovery_id)
+            .map(PublicKey)
+            .map_err(|_| VerificationError::InvalidSignature)
+    }
+
+    /// Whether `sig` parses as a DER-encoded Secp256k1 signature.
+    pub(crate) fn is_well_formed_signature(sig: &[u8]) -> bool {
+        Signature::parse_der(sig).is_ok()
+    }
+
     /// Verify the Secp256k1 DER-encoded signature on a raw 256-bit message using the public key.
     pub fn verify_hash(&self, msg: &[u8], sig: &[u8]) -> bool {
         Message::parse_slice(msg)
@@ -221,6 +282,48 @@ impl PublicKey {
             .map_err(|e| DecodingError::failed_to_parse("secp256k1 public key", e))
             .map(PublicKey)
     }
+
+    /// Encode the public key in DER as a X.509 SubjectPublicKeyInfo structure,
+    /// as defined in [RFC5480], with the point in uncompressed form.
+    ///
+    /// [RFC5480]: https://tools.ietf.org/html/rfc5480#section-2
+    pub fn encode_der(&self) -> Vec<u8> {
+        let point = self.to_bytes_uncompressed();
+
+        let mut der = Vec::with_capacity(2 + Self::SPKI_ALGORITHM.len() + 3 + point.len());
+        der.extend_from_slice(&[0x30, (Self::SPKI_ALGORITHM.len() + 3 + point.len()) as u8]);
+        der.extend_from_slice(&Self::SPKI_ALGORITHM);
+        der.extend_from_slice(&[0x03, (point.len() + 1) as u8, 0x00]);
+        der.extend_from_slice(&point);
+        der
+    }
+
+    /// Decode a public key from a DER-encoded X.509 SubjectPublicKeyInfo
+    /// structure, with the point in compressed or uncompressed form. See also `encode_der`.
+    pub fn try_decode_der(k: &[u8]) -> Result<PublicKey, DecodingError> {
+        let point = Self::strip_spki_header(k).ok_or_else(|| {
+            DecodingError::new("failed to parse secp256k1 X.509: unexpected header".to_string())
+        })?;
+        libsecp256k1::PublicKey::parse_slice(point, None)
+            .map_err(|e| DecodingError::failed_to_parse("secp256k1 public key", e))
+            .map(PublicKey)
+    }
+
+    // AlgorithmIdentifier SEQUENCE of the ecPublicKey OID (1.2.840.10045.2.1)
+    // with the secp256k1 OID (1.3.132.0.10) as parameters.
+    const SPKI_ALGORITHM: [u8; 18] = [
+        0x30, 0x10, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x05, 0x2b, 0x81,
+        0x04, 0x00, 0x0a,
+    ];
+
+    fn strip_spki_header(k: &[u8]) -> Option<&[u8]> {
+        // Both point encodings are short enough for single-byte DER lengths.
+        let short_len = |len: usize| u8::try_from(len.checked_sub(2)?).ok().filter(|l| *l < 0x80);
+
+        let rest = k.strip_prefix(&[0x30, short_len(k.len())?])?;
+        let rest = rest.strip_prefix(&Self::SPKI_ALGORITHM)?;
+        rest.strip_prefix(&[0x03, short_len(rest.len())?, 0x00])
+    }
 }
 
 #[cfg(test)]
@@ -237,4 +340,42 @@ mod tests {
         assert_eq!(sk1.0.serialize(), sk2.0.serialize());
         assert_eq!(sk_bytes, [0; 32]);
     }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn high_s_is_rejected_by_verify_strict() {
+        let pair = Keypair::generate();
+        let pk = pair.public();
+        let msg = "hello world".as_bytes();
+
+        let low = pair.secret().sign(msg);
+        assert!(pk.verify_strict(msg, &low));
+
+        let mut sig = Signature::parse_der(&low).unwrap();
+        sig.s = -sig.s;
+        let high = sig.serialize_der().as_ref().to_vec();
+        assert!(pk.verify(msg, &high));
+        assert!(!pk.verify_strict(msg, &high));
+
+        sig.normalize_s();
+        let normalized = sig.serialize_der().as_ref().to_vec();
+        assert_eq!(normalized, low);
+        assert!(pk.verify_strict(msg, &normalized));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn recoverable_signature_matches_der_signature() {
+        let pair = Keypair::generate();
+        let msg = "hello world".as_bytes();
+
+        let (sig, recovery_id) = pair.secret().sign_recoverable(msg);
+
+        let der = Signature::parse_standard(&sig).unwrap().serialize_der();
+        assert_eq!(der.as_ref(), pair.secret().sign(msg));
+        assert_eq!(
+            &PublicKey::recover(msg, &sig, recovery_id).unwrap(),
+            pair.public()
+        );
+    }
 }
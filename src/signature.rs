@@ -0,0 +1,80 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Signatures tagged with the type of the key that produced them.
+
+use crate::KeyType;
+use alloc::vec::Vec;
+
+/// A signature together with the [`KeyType`] of the key that produced it, see
+/// [`Keypair::sign_typed`](crate::Keypair::sign_typed) and
+/// [`PublicKey::verify_typed`](crate::PublicKey::verify_typed).
+///
+/// The key type is not authenticated: it only lets a verifier reject a signature
+/// that was obviously produced by another kind of key without running the backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    key_type: KeyType,
+    bytes: Vec<u8>,
+}
+
+impl Signature {
+    /// Tag the raw bytes of a signature with the type of the key that produced it.
+    pub fn new(key_type: KeyType, bytes: Vec<u8>) -> Self {
+        Signature { key_type, bytes }
+    }
+
+    /// The type of the key that produced the signature.
+    pub fn key_type(&self) -> KeyType {
+        self.key_type
+    }
+
+    /// The raw signature bytes, as accepted by [`PublicKey::verify`](crate::PublicKey::verify).
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The length in bytes of the raw signature.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether the raw signature is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Convert into the raw signature bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl AsRef<[u8]> for Signature {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl From<Signature> for Vec<u8> {
+    fn from(signature: Signature) -> Self {
+        signature.bytes
+    }
+}
@@ -0,0 +1,310 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Signed envelopes binding a typed payload to the key that signed it.
+
+use crate::error::{DecodingError, SigningError};
+use crate::{proto, AsyncSigner, PublicKey};
+use alloc::{format, vec, vec::Vec};
+use core::{error::Error, fmt};
+use quick_protobuf::{BytesReader, BytesWriter, Writer};
+use unsigned_varint::encode::usize_buffer;
+
+/// Domain string of envelope signatures, the first length-prefixed field of the
+/// signed payload described in RFC 0002.
+///
+/// Upstream libp2p lets each record type pick its domain, e.g. `libp2p-routing-state`
+/// for peer records. Envelopes here always use this one, so none of them verifies as an
+/// upstream record, whatever its payload type.
+const DOMAIN_SEPARATION: &str = "libp2p-pqc-signed-envelope";
+
+/// A signed envelope contains an arbitrary byte string payload, a signature of the payload, and
+/// the public key that can be used to verify the signature.
+///
+/// For more details see libp2p RFC0002: <https://github.com/libp2p/specs/blob/master/RFC/0002-signed-envelopes.md>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedEnvelope {
+    payload_type: Vec<u8>,
+    payload: Vec<u8>,
+    public_key: PublicKey,
+    signature: Vec<u8>,
+}
+
+impl SignedEnvelope {
+    /// Seal a payload into a new [`SignedEnvelope`], signing it with the given keypair
+    /// or any other [`AsyncSigner`].
+    pub fn seal<S: AsyncSigner + ?Sized>(
+        signer: &S,
+        payload_type: Vec<u8>,
+        payload: Vec<u8>,
+    ) -> Result<Self, SigningError> {
+        log::trace!(target: "libp2p-identity", "✉️ Sealing signed envelope");
+        let buffer = signature_payload(&payload_type, &payload);
+        let signature = signer.sign(&buffer)?;
+
+        Ok(Self {
+            payload_type,
+            payload,
+            public_key: signer.public_key().into_owned(),
+            signature,
+        })
+    }
+
+    /// Verify the signature of this [`SignedEnvelope`] and return its payload type and payload.
+    pub fn open(&self) -> Result<(&[u8], &[u8]), EnvelopeError> {
+        log::trace!(target: "libp2p-identity", "✉️ Opening signed envelope");
+        let buffer = signature_payload(&self.payload_type, &self.payload);
+
+        if !self.public_key.verify(&buffer, &self.signature) {
+            return Err(EnvelopeError::InvalidSignature);
+        }
+
+        Ok((&self.payload_type, &self.payload))
+    }
+
+    /// The type of the enclosed payload, without verifying the signature.
+    pub fn payload_type(&self) -> &[u8] {
+        &self.payload_type
+    }
+
+    /// The enclosed payload, without verifying the signature.
+    ///
+    /// Use [`SignedEnvelope::open`] to obtain a payload that has been authenticated.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// The public key the envelope claims to be signed with.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// The signature over the payload type and payload.
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /// Encode this [`SignedEnvelope`] using the protobuf encoding specified in the RFC.
+    pub fn to_protobuf_encoding(&self) -> Vec<u8> {
+        use quick_protobuf::MessageWrite;
+
+        let envelope = proto::Envelope {
+            public_key: Some((&self.public_key).into()),
+            payload_type: self.payload_type.clone(),
+            payload: self.payload.clone(),
+            signature: self.signature.clone(),
+        };
+
+        let mut buf = vec![0; envelope.get_size()];
+        let mut writer = Writer::new(BytesWriter::new(&mut buf));
+        envelope
+            .write_message(&mut writer)
+            .expect("Encoding to succeed");
+
+        buf
+    }
+
+    /// Decode a [`SignedEnvelope`] using the protobuf encoding specified in the RFC.
+    ///
+    /// The signature is not checked; call [`SignedEnvelope::open`] to authenticate the payload.
+    /// Signatures longer than the key type can produce are rejected without being kept.
+    pub fn from_protobuf_encoding(bytes: &[u8]) -> Result<Self, DecodingError> {
+        use quick_protobuf::MessageRead;
+
+        let mut reader = BytesReader::from_bytes(bytes);
+        let envelope = proto::Envelope::from_reader(&mut reader, bytes)
+            .map_err(|e| DecodingError::bad_protobuf("signed envelope", e))?;
+
+        let public_key: PublicKey = envelope
+            .public_key
+            .ok_or_else(|| DecodingError::new("signed envelope is missing a public key".into()))?
+            .try_into()?;
+
+        if let Some(max) = public_key.signature_len() {
+            if envelope.signature.len() > max {
+                return Err(DecodingError::new(format!(
+                    "signed envelope signature of {} bytes exceeds the {max} bytes of a {} signature",
+                    envelope.signature.len(),
+                    public_key.key_type()
+                )));
+            }
+        }
+
+        Ok(Self {
+            payload_type: envelope.payload_type,
+            payload: envelope.payload,
+            public_key,
+            signature: envelope.signature,
+        })
+    }
+}
+
+/// Build the buffer that is signed for an envelope.
+///
+/// Every field is prefixed with its unsigned-varint encoded length so that the
+/// boundaries between the domain, the payload type and the payload are unambiguous.
+fn signature_payload(payload_type: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut domain_sep_length_buffer = usize_buffer();
+    let domain_sep_length =
+        unsigned_varint::encode::usize(DOMAIN_SEPARATION.len(), &mut domain_sep_length_buffer);
+
+    let mut payload_type_length_buffer = usize_buffer();
+    let payload_type_length =
+        unsigned_varint::encode::usize(payload_type.len(), &mut payload_type_length_buffer);
+
+    let mut payload_length_buffer = usize_buffer();
+    let payload_length = unsigned_varint::encode::usize(payload.len(), &mut payload_length_buffer);
+
+    let mut buffer = Vec::with_capacity(
+        domain_sep_length.len()
+            + DOMAIN_SEPARATION.len()
+            + payload_type_length.len()
+            + payload_type.len()
+            + payload_length.len()
+            + payload.len(),
+    );
+
+    buffer.extend_from_slice(domain_sep_length);
+    buffer.extend_from_slice(DOMAIN_SEPARATION.as_bytes());
+    buffer.extend_from_slice(payload_type_length);
+    buffer.extend_from_slice(payload_type);
+    buffer.extend_from_slice(payload_length);
+    buffer.extend_from_slice(payload);
+
+    buffer
+}
+
+/// Error when opening a [`SignedEnvelope`].
+#[derive(Debug)]
+pub enum EnvelopeError {
+    /// The signature does not match the payload type, payload and public key of the envelope.
+    InvalidSignature,
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvelopeError::InvalidSignature => f.write_str("Invalid signature on signed envelope"),
+        }
+    }
+}
+
+impl Error for EnvelopeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "dilithium")]
+    use crate::Keypair;
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn seal_then_open() {
+        let keypair = Keypair::generate_dilithium();
+        let envelope =
+            SignedEnvelope::seal(&keypair, b"/test/payload".to_vec(), b"hello".to_vec()).unwrap();
+
+        let (payload_type, payload) = envelope.open().unwrap();
+
+        assert_eq!(payload_type, b"/test/payload");
+        assert_eq!(payload, b"hello");
+        assert_eq!(envelope.public_key(), &keypair.public());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn protobuf_roundtrip() {
+        let keypair = Keypair::generate_dilithium();
+        let envelope =
+            SignedEnvelope::seal(&keypair, b"/test/payload".to_vec(), b"hello".to_vec()).unwrap();
+
+        let decoded =
+            SignedEnvelope::from_protobuf_encoding(&envelope.to_protobuf_encoding()).unwrap();
+
+        assert_eq!(envelope, decoded);
+        assert!(decoded.open().is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn tampered_payload_type_is_rejected() {
+        let keypair = Keypair::generate_dilithium();
+        let mut envelope =
+            SignedEnvelope::seal(&keypair, b"/test/payload".to_vec(), b"hello".to_vec()).unwrap();
+
+        envelope.payload_type = b"/test/other".to_vec();
+
+        assert!(matches!(
+            envelope.open(),
+            Err(EnvelopeError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn fields_cannot_be_shifted_across_boundaries() {
+        let keypair = Keypair::generate_dilithium();
+        let mut envelope =
+            SignedEnvelope::seal(&keypair, b"/test/".to_vec(), b"payload".to_vec()).unwrap();
+
+        envelope.payload_type = b"/test/payload".to_vec();
+        envelope.payload = Vec::new();
+
+        assert!(envelope.open().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "dilithium")]
+    fn oversized_signature_is_rejected() {
+        let keypair = Keypair::generate_dilithium();
+        let mut envelope =
+            SignedEnvelope::seal(&keypair, b"/test/payload".to_vec(), b"hello".to_vec()).unwrap();
+
+        envelope.signature.push(0);
+
+        assert!(SignedEnvelope::from_protobuf_encoding(&envelope.to_protobuf_encoding()).is_err());
+    }
+
+    #[test]
+    fn envelope_error_message() {
+        assert_eq!(
+            EnvelopeError::InvalidSignature.to_string(),
+            "Invalid signature on signed envelope"
+        );
+    }
+
+    #[test]
+    fn missing_public_key_is_rejected() {
+        use quick_protobuf::MessageWrite;
+
+        let envelope = proto::Envelope {
+            public_key: None,
+            payload_type: b"/test/payload".to_vec(),
+            payload: b"hello".to_vec(),
+            signature: vec![1, 2, 3],
+        };
+        let mut buf = vec![0; envelope.get_size()];
+        envelope
+            .write_message(&mut Writer::new(BytesWriter::new(&mut buf)))
+            .unwrap();
+
+        assert!(SignedEnvelope::from_protobuf_encoding(&buf).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Traits that decouple an identity from holding its secret key in memory.
+//!
+//! A [`Keypair`] implements [`AsyncSigner`] directly. Keys that live in an HSM or a
+//! remote KMS implement it by forwarding the message to the device, and can then be
+//! used wherever the crate accepts a signer, such as
+//! [`SignedEnvelope::seal`](crate::SignedEnvelope::seal).
+
+use crate::{error::SigningError, Keypair, PublicKey};
+use alloc::{borrow::Cow, vec::Vec};
+
+/// Anything that knows the public key of an identity.
+///
+/// Implemented for [`PublicKey`] and [`Keypair`], and required by [`AsyncSigner`].
+/// Helpers that only need the public key, such as
+/// [`PeerId::from_public_key`](crate::PeerId::from_public_key) and
+/// [`VerificationCache::verify_cached`](crate::VerificationCache::verify_cached), accept
+/// any implementation.
+pub trait PublicKeyProvider {
+    /// The public key of the identity.
+    ///
+    /// Implementations that hold the key should borrow it rather than clone it.
+    fn public_key(&self) -> Cow<'_, PublicKey>;
+
+    /// Verify a signature for a message against the provided public key.
+    #[cfg_attr(
+        not(any(
+            feature = "ecdsa",
+            feature = "secp256k1",
+            feature = "ed25519",
+            feature = "rsa",
+            feature = "dilithium",
+            feature = "sphincs-plus",
+            feature = "falcon"
+        )),
+        allow(unreachable_code, unused_variables)
+    )]
+    fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
+        self.public_key().verify(msg, sig)
+    }
+
+    /// The [`PeerId`](crate::PeerId) of the provided public key.
+    #[cfg(feature = "peerid")]
+    fn peer_id(&self) -> crate::PeerId {
+        crate::PeerId::from_public_key(self)
+    }
+}
+
+/// Signs messages with a secret key the caller may not have access to.
+///
+/// Signatures must be in the format [`PublicKey::verify`] expects for the key type
+/// of [`PublicKeyProvider::public_key`]. Signing blocks; implementations backed by a
+/// remote service are expected to wait for the response.
+pub trait AsyncSigner: PublicKeyProvider {
+    /// Sign a message.
+    fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, SigningError>;
+}
+
+impl AsyncSigner for Keypair {
+    fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, SigningError> {
+        Keypair::sign(self, msg)
+    }
+}
+
+impl PublicKeyProvider for Keypair {
+    fn public_key(&self) -> Cow<'_, PublicKey> {
+        Cow::Owned(self.public())
+    }
+}
+
+impl PublicKeyProvider for PublicKey {
+    fn public_key(&self) -> Cow<'_, PublicKey> {
+        Cow::Borrowed(self)
+    }
+}
+
+#[cfg(all(test, feature = "dilithium"))]
+mod tests {
+    use super::*;
+
+    /// Stands in for an HSM: the secret key is only reachable through `sign`, and the
+    /// public key is read once when the device is opened.
+    struct MockSigner {
+        public: PublicKey,
+        device: Keypair,
+    }
+
+    impl MockSigner {
+        fn open(device: Keypair) -> Self {
+            MockSigner {
+                public: device.public(),
+                device,
+            }
+        }
+    }
+
+    impl PublicKeyProvider for MockSigner {
+        fn public_key(&self) -> Cow<'_, PublicKey> {
+            Cow::Borrowed(&self.public)
+        }
+    }
+
+    impl AsyncSigner for MockSigner {
+        fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, SigningError> {
+            self.device.sign(msg)
+        }
+    }
+
+    #[test]
+    fn mock_signer_signs_for_its_public_key() {
+        let signer = MockSigner::open(Keypair::generate_dilithium());
+        let public = signer.public_key().into_owned();
+        let msg = b"signed outside of the process";
+
+        let sig = signer.sign(msg).unwrap();
+
+        assert!(public.verify(msg, &sig));
+        assert!(signer.verify(msg, &sig));
+        assert!(!signer.verify(b"another message", &sig));
+        assert_eq!(signer.peer_id(), public.to_peer_id());
+    }
+
+    #[test]
+    fn helpers_accept_any_provider() {
+        let signer = MockSigner::open(Keypair::generate_dilithium());
+        let public = signer.public.clone();
+        let msg = b"verified through a provider";
+        let sig = signer.sign(msg).unwrap();
+
+        assert_eq!(crate::PeerId::from_public_key(&signer), public.to_peer_id());
+        assert_eq!(
+            crate::PeerId::from_public_key(&signer.device),
+            public.to_peer_id()
+        );
+
+        #[cfg(feature = "verification-cache")]
+        {
+            let mut cache = crate::VerificationCache::new(4);
+            assert!(cache.verify_cached(&signer, msg, &sig));
+            assert!(cache.verify_cached(&public, msg, &sig));
+            assert_eq!(cache.len(), 1);
+        }
+        #[cfg(not(feature = "verification-cache"))]
+        let _ = sig;
+    }
+
+    #[test]
+    fn keypair_is_a_signer() {
+        fn sign_with(signer: &dyn AsyncSigner, msg: &[u8]) -> Vec<u8> {
+            signer.sign(msg).unwrap()
+        }
+        let keypair = Keypair::generate_dilithium();
+        let msg = b"message";
+
+        let sig = sign_with(&keypair, msg);
+
+        assert_eq!(*keypair.public_key(), keypair.public());
+        assert!(PublicKeyProvider::verify(&keypair, msg, &sig));
+    }
+}
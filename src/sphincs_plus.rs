@@ -0,0 +1,275 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! SPHINCS+ (SLH-DSA) keys.
+//!
+//! Keys use the SLH-DSA-SHA2-256f parameter set of FIPS 205, the fast-signing
+//! variant at NIST security category 5, the same category as the default
+//! Dilithium level. Signatures are deterministic.
+
+use super::error::{DecodingError, SigningError};
+use core::fmt;
+use fips205::slh_dsa_sha2_256f as slh_dsa;
+use fips205::traits::{SerDes, Signer as _, Verifier as _};
+use zeroize::Zeroizing;
+
+/// Length in bytes of an encoded SPHINCS+ public key.
+pub const PUBLIC_KEY_LEN: usize = slh_dsa::PK_LEN;
+
+/// Length in bytes of an encoded SPHINCS+ secret key, which embeds the public key.
+pub const SECRET_KEY_LEN: usize = slh_dsa::SK_LEN;
+
+/// Length in bytes of a SPHINCS+ signature.
+pub const SIGNATURE_LEN: usize = slh_dsa::SIG_LEN;
+
+/// A SPHINCS+ keypair.
+#[derive(Clone)]
+pub struct Keypair {
+    secret: SecretKey,
+    public: PublicKey,
+}
+
+impl Keypair {
+    /// Generate a new random SPHINCS+ keypair.
+    #[cfg(feature = "rand")]
+    pub fn generate() -> Keypair {
+        Keypair::generate_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generate a new SPHINCS+ keypair using the given RNG.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rng` fails to produce randomness.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: rand::RngCore + rand::CryptoRng>(rng: &mut R) -> Keypair {
+        let (_, secret) = slh_dsa::try_keygen_with_rng(rng).expect("the RNG does not fail");
+        Keypair::from(SecretKey(secret))
+    }
+
+    /// Encode the keypair as its secret key, which embeds the public key.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.secret.to_bytes()
+    }
+
+    /// Try to parse a keypair from the format produced by [`Keypair::to_bytes`].
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Keypair, DecodingError> {
+        SecretKey::try_from_bytes(bytes).map(Keypair::from)
+    }
+
+    /// Sign a message using the private key of this keypair.
+    pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
+        self.secret.sign(&[], msg)
+    }
+
+    /// Sign a message bound to the context string `ctx`, using the SLH-DSA context field.
+    ///
+    /// The signature only verifies with [`PublicKey::verify_with_context`] under the
+    /// same context. An empty context produces the same signature as [`Keypair::sign`].
+    ///
+    /// Contexts longer than 255 bytes are rejected with [`SigningError::ContextTooLong`].
+    pub fn sign_with_context(&self, ctx: &[u8], msg: &[u8]) -> Result<Vec<u8>, SigningError> {
+        if ctx.len() > 255 {
+            return Err(SigningError::ContextTooLong(ctx.len()));
+        }
+        Ok(self.secret.sign(ctx, msg))
+    }
+
+    /// Get the public key of this keypair.
+    pub fn public(&self) -> &PublicKey {
+        &self.public
+    }
+
+    /// Get the secret key of this keypair.
+    pub fn secret(&self) -> &SecretKey {
+        &self.secret
+    }
+}
+
+impl fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keypair")
+            .field("public", &self.public)
+            .finish()
+    }
+}
+
+/// Demote a SPHINCS+ keypair to a secret key.
+impl From<Keypair> for SecretKey {
+    fn from(kp: Keypair) -> SecretKey {
+        kp.secret
+    }
+}
+
+/// Promote a SPHINCS+ secret key into a keypair.
+impl From<SecretKey> for Keypair {
+    fn from(secret: SecretKey) -> Keypair {
+        let public = PublicKey(secret.0.get_public_key().into_bytes());
+        Keypair { secret, public }
+    }
+}
+
+/// A SPHINCS+ public key.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PublicKey([u8; PUBLIC_KEY_LEN]);
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PublicKey: ")?;
+        for byte in &self.0 {
+            write!(f, "{byte:x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PublicKey {
+    /// Verify the SPHINCS+ signature on a message using the public key.
+    ///
+    /// Signatures of any length other than [`SIGNATURE_LEN`] are rejected up front.
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
+        self.verify_with_context(&[], msg, sig)
+    }
+
+    /// Verify a signature made with [`Keypair::sign_with_context`] under the context `ctx`.
+    ///
+    /// Contexts longer than 255 bytes never verify.
+    pub fn verify_with_context(&self, ctx: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+        let Ok(sig) = <&[u8; SIGNATURE_LEN]>::try_from(sig) else {
+            return false;
+        };
+        slh_dsa::PublicKey::try_from_bytes(&self.0)
+            .map(|pk| pk.verify(msg, sig, ctx))
+            .unwrap_or(false)
+    }
+
+    /// Convert the public key to its raw byte representation.
+    pub fn to_bytes(&self) -> [u8; PUBLIC_KEY_LEN] {
+        self.0
+    }
+
+    /// Try to parse a public key from the raw bytes produced by [`PublicKey::to_bytes`].
+    pub fn try_from_bytes(k: &[u8]) -> Result<PublicKey, DecodingError> {
+        <[u8; PUBLIC_KEY_LEN]>::try_from(k)
+            .map(PublicKey)
+            .map_err(|e| DecodingError::failed_to_parse("SPHINCS+ public key", e))
+    }
+}
+
+/// A SPHINCS+ secret key.
+///
+/// The key material is wiped when the secret key is dropped.
+#[derive(Clone)]
+pub struct SecretKey(slh_dsa::PrivateKey);
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretKey")
+    }
+}
+
+impl zeroize::ZeroizeOnDrop for SecretKey {}
+
+impl SecretKey {
+    fn sign(&self, ctx: &[u8], msg: &[u8]) -> Vec<u8> {
+        // Deterministic (non-hedged) signing never draws from the RNG.
+        self.0
+            .try_sign_with_rng(&mut rand::rngs::OsRng, msg, ctx, false)
+            .expect("the context length is checked by the caller")
+            .to_vec()
+    }
+
+    /// Try to parse a SPHINCS+ secret key from a byte slice containing the actual key.
+    ///
+    /// The embedded public key is checked against the one recomputed from the secret seeds.
+    pub fn try_from_bytes(sk: &[u8]) -> Result<SecretKey, DecodingError> {
+        let bytes = Zeroizing::new(
+            <[u8; SECRET_KEY_LEN]>::try_from(sk)
+                .map_err(|e| DecodingError::failed_to_parse("SPHINCS+ secret key", e))?,
+        );
+
+        slh_dsa::PrivateKey::try_from_bytes(&bytes)
+            .map(SecretKey)
+            .map_err(|e| DecodingError::new(format!("failed to parse SPHINCS+ secret key: {e}")))
+    }
+
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        Zeroizing::new(self.0.clone().into_bytes()).to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sphincs_plus_keypair_encode_decode() {
+        let kp1 = Keypair::generate();
+        let kp2 = Keypair::try_from_bytes(&kp1.to_bytes()).unwrap();
+
+        assert_eq!(kp1.public(), kp2.public());
+        assert_eq!(kp1.secret().to_bytes(), kp2.secret().to_bytes());
+        assert_eq!(kp1.to_bytes().len(), SECRET_KEY_LEN);
+        assert_eq!(kp1.public().to_bytes().len(), PUBLIC_KEY_LEN);
+    }
+
+    #[test]
+    fn sphincs_plus_signature() {
+        let kp = Keypair::generate();
+        let pk = kp.public();
+
+        let msg = "hello world".as_bytes();
+        let sig = kp.sign(msg);
+        assert_eq!(sig.len(), SIGNATURE_LEN);
+        assert!(pk.verify(msg, &sig));
+
+        let mut invalid_sig = sig.clone();
+        invalid_sig[3..6].copy_from_slice(&[10, 23, 42]);
+        assert!(!pk.verify(msg, &invalid_sig));
+
+        let invalid_msg = "h3ll0 w0rld".as_bytes();
+        assert!(!pk.verify(invalid_msg, &sig));
+
+        assert!(!pk.verify(msg, &sig[..SIGNATURE_LEN - 1]));
+        assert!(!pk.verify(msg, &[sig.as_slice(), &[0]].concat()));
+    }
+
+    #[test]
+    fn sphincs_plus_corrupted_secret_is_rejected() {
+        let mut bytes = Keypair::generate().to_bytes();
+        // Flip a bit in the secret seed, which no longer matches the embedded root.
+        bytes[0] ^= 1;
+
+        assert!(SecretKey::try_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn sphincs_plus_invalid_lengths_are_rejected() {
+        assert!(PublicKey::try_from_bytes(&[0u8; 32]).is_err());
+        assert!(Keypair::try_from_bytes(&[0u8; 64]).is_err());
+        assert!(SecretKey::try_from_bytes(&[0u8; SECRET_KEY_LEN + 1]).is_err());
+    }
+
+    #[test]
+    fn sphincs_plus_secret_key_zeroizes_on_drop() {
+        fn assert_zeroize_on_drop<T: zeroize::ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<SecretKey>();
+    }
+}
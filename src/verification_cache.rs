@@ -0,0 +1,191 @@
+// Copyright 2026 Quantus Network.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! A cache of successful signature verifications.
+
+use std::collections::{BTreeMap, HashMap};
+
+use sha2::{Digest as _, Sha256};
+
+use crate::{PublicKey, PublicKeyProvider};
+
+const DOMAIN_SEPARATION: &[u8] = b"libp2p-pqc-verification-cache";
+
+/// Remembers successful [`PublicKey::verify`] calls, so that re-verifying the same
+/// signature, e.g. on gossip rebroadcast, does not rerun the signature scheme.
+///
+/// Only the SHA-256 hash of each verified `(public key, message, signature)` triple
+/// is stored. Failed verifications are never cached, so a lookup can only succeed for
+/// the exact inputs of an earlier successful verification. When full, the least
+/// recently used entry is evicted.
+#[derive(Debug, Clone)]
+pub struct VerificationCache {
+    capacity: usize,
+    /// Hash of each cached triple to the tick it was last used at.
+    entries: HashMap<[u8; 32], u64>,
+    /// The inverse of `entries`, ordered from least to most recently used.
+    recency: BTreeMap<u64, [u8; 32]>,
+    tick: u64,
+}
+
+impl VerificationCache {
+    /// Create a cache remembering up to `capacity` successful verifications.
+    ///
+    /// A capacity of zero disables caching.
+    pub fn new(capacity: usize) -> Self {
+        VerificationCache {
+            capacity,
+            entries: HashMap::with_capacity(capacity),
+            recency: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Verify `sig` on `msg` with `key`, answering from the cache if this exact
+    /// triple verified before.
+    ///
+    /// `key` is a [`PublicKey`] or anything else that provides one.
+    pub fn verify_cached<K: PublicKeyProvider + ?Sized>(
+        &mut self,
+        key: &K,
+        msg: &[u8],
+        sig: &[u8],
+    ) -> bool {
+        let key = key.public_key();
+        let hash = triple_hash(&key, msg, sig);
+        self.tick += 1;
+
+        if let Some(last_used) = self.entries.get_mut(&hash) {
+            self.recency.remove(last_used);
+            *last_used = self.tick;
+            self.recency.insert(self.tick, hash);
+            return true;
+        }
+
+        if !key.verify(msg, sig) {
+            return false;
+        }
+        if self.capacity == 0 {
+            return true;
+        }
+        if self.entries.len() == self.capacity {
+            if let Some((_, evicted)) = self.recency.pop_first() {
+                self.entries.remove(&evicted);
+            }
+        }
+        self.entries.insert(hash, self.tick);
+        self.recency.insert(self.tick, hash);
+        true
+    }
+
+    /// The maximum number of verifications remembered.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of verifications currently remembered.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no verification is currently remembered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forget all remembered verifications.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+/// Hash the length-prefixed protobuf encoding of `key`, `msg` and `sig`.
+fn triple_hash(key: &PublicKey, msg: &[u8], sig: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN_SEPARATION);
+    for part in [&key.encode_protobuf()[..], msg, sig] {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(all(test, feature = "dilithium"))]
+mod tests {
+    use super::*;
+    use crate::Keypair;
+
+    #[test]
+    fn tampered_signature_is_never_served_from_cache() {
+        let keypair = Keypair::generate_dilithium();
+        let public = keypair.public();
+        let msg = b"hello";
+        let sig = keypair.sign(msg).unwrap();
+        let mut cache = VerificationCache::new(4);
+
+        assert!(cache.verify_cached(&public, msg, &sig));
+        assert!(cache.verify_cached(&public, msg, &sig));
+        assert_eq!(cache.len(), 1);
+
+        let mut tampered = sig.clone();
+        tampered[0] ^= 1;
+        assert!(!cache.verify_cached(&public, msg, &tampered));
+        assert!(!cache.verify_cached(&public, b"hellp", &sig));
+        let other = Keypair::generate_dilithium().public();
+        assert!(!cache.verify_cached(&other, msg, &sig));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn least_recently_used_entry_is_evicted() {
+        let keypair = Keypair::generate_dilithium();
+        let public = keypair.public();
+        let msgs = [b"a", b"b", b"c"];
+        let sigs = msgs.map(|msg| keypair.sign(msg).unwrap());
+        let mut cache = VerificationCache::new(2);
+
+        assert!(cache.verify_cached(&public, msgs[0], &sigs[0]));
+        assert!(cache.verify_cached(&public, msgs[1], &sigs[1]));
+        // Touch `a`, so `b` is the least recently used entry.
+        assert!(cache.verify_cached(&public, msgs[0], &sigs[0]));
+        assert!(cache.verify_cached(&public, msgs[2], &sigs[2]));
+        assert_eq!(cache.len(), 2);
+
+        let hashes = |i: usize| triple_hash(&public, msgs[i], &sigs[i]);
+        assert!(cache.entries.contains_key(&hashes(0)));
+        assert!(!cache.entries.contains_key(&hashes(1)));
+        assert!(cache.entries.contains_key(&hashes(2)));
+
+        // The evicted entry still verifies, it is just not served from the cache.
+        assert!(cache.verify_cached(&public, msgs[1], &sigs[1]));
+        assert!(!cache.entries.contains_key(&hashes(0)));
+    }
+
+    #[test]
+    fn zero_capacity_disables_caching() {
+        let keypair = Keypair::generate_dilithium();
+        let sig = keypair.sign(b"hello").unwrap();
+        let mut cache = VerificationCache::new(0);
+
+        assert!(cache.verify_cached(&keypair.public(), b"hello", &sig));
+        assert!(cache.is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "dilithium")]
+
 use libp2p_identity::{KeyType, Keypair};
 
 #[test]
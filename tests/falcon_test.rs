@@ -0,0 +1,108 @@
+#![cfg(feature = "falcon")]
+
+use libp2p_identity::{falcon, KeyType, Keypair, PublicKey};
+
+#[test]
+fn test_falcon_keypair_generation() {
+    let keypair = Keypair::generate_falcon();
+
+    assert_eq!(keypair.public().key_type(), KeyType::Falcon);
+    assert_eq!(keypair.key_type(), KeyType::Falcon);
+}
+
+#[test]
+fn test_falcon_sign_and_verify() {
+    let keypair = Keypair::generate_falcon();
+    let public_key = keypair.public();
+
+    let message = b"Hello, Post-Quantum World!";
+    let signature = keypair.sign(message).expect("Signing should succeed");
+
+    assert_eq!(KeyType::Falcon.signature_len(), None);
+    assert!(signature.len() <= falcon::MAX_SIGNATURE_LEN);
+    assert!(
+        public_key.verify(message, &signature),
+        "Signature should be valid"
+    );
+    assert!(
+        !public_key.verify(b"Wrong message", &signature),
+        "Signature should be invalid for wrong message"
+    );
+}
+
+#[test]
+fn test_falcon_variable_length_signatures() {
+    let keypair = Keypair::generate_falcon();
+    let public_key = keypair.public();
+    let message = b"Falcon signatures are not fixed-size";
+    let signature = keypair.sign(message).unwrap();
+
+    assert!(public_key.verify_detailed(message, &signature).is_ok());
+    assert!(!public_key.verify(message, &[]));
+    assert!(!public_key.verify(message, &signature[..signature.len() - 1]));
+
+    let mut oversized = signature.clone();
+    oversized.resize(falcon::MAX_SIGNATURE_LEN + 1, 0);
+    assert!(!public_key.verify(message, &oversized));
+}
+
+#[test]
+fn test_falcon_protobuf_roundtrip() {
+    let original_keypair = Keypair::generate_falcon();
+
+    let encoded = original_keypair
+        .to_protobuf_encoding()
+        .expect("Encoding should succeed");
+    let decoded_keypair =
+        Keypair::from_protobuf_encoding(&encoded).expect("Decoding should succeed");
+
+    assert_eq!(original_keypair.public(), decoded_keypair.public());
+
+    // Signing is randomised, so only check that the decoded keypair still signs for the
+    // original public key.
+    let message = b"Test message for roundtrip";
+    let signature = decoded_keypair
+        .sign(message)
+        .expect("Decoded signing should succeed");
+    assert!(original_keypair.public().verify(message, &signature));
+}
+
+#[test]
+fn test_falcon_public_key_encoding() {
+    let public_key = Keypair::generate_falcon().public();
+
+    let encoded = public_key.encode_protobuf();
+    let decoded =
+        PublicKey::try_decode_protobuf(&encoded).expect("Public key decoding should succeed");
+
+    assert_eq!(public_key, decoded);
+    assert_eq!(decoded.key_type(), KeyType::Falcon);
+}
+
+#[test]
+fn test_falcon_peer_id_generation() {
+    let public_key = Keypair::generate_falcon().public();
+
+    let peer_id = public_key.to_peer_id();
+    assert_eq!(peer_id, public_key.to_peer_id());
+    assert!(public_key.matches_peer_id(&peer_id));
+
+    let other = Keypair::generate_falcon().public().to_peer_id();
+    assert_ne!(peer_id, other);
+}
+
+#[test]
+#[cfg(feature = "dilithium")]
+fn test_falcon_signatures_are_smaller_than_dilithium() {
+    let message = b"Same message, two post-quantum schemes";
+
+    let falcon = Keypair::generate_falcon().sign(message).unwrap();
+    let dilithium = Keypair::generate_dilithium().sign(message).unwrap();
+
+    assert!(
+        falcon.len() < dilithium.len(),
+        "Falcon signature ({} bytes) should be smaller than Dilithium ({} bytes)",
+        falcon.len(),
+        dilithium.len()
+    );
+}
@@ -0,0 +1,48 @@
+// Exercises the verification path of `no_std` builds, in which the library is
+// compiled as `#![no_std]`:
+//
+//     cargo test --no-default-features --features ed25519 --test no_std
+//     cargo test --no-default-features --features dilithium-verify --test no_std
+#![cfg(not(feature = "std"))]
+
+#[test]
+#[cfg(feature = "ed25519")]
+fn verify_without_std() {
+    use libp2p_identity::{KeyType, Keypair, PublicKey};
+
+    let keypair = Keypair::ed25519_from_bytes([7u8; 32]).unwrap();
+    let encoded = keypair.to_protobuf_encoding().unwrap();
+    let keypair = Keypair::from_protobuf_encoding(&encoded).unwrap();
+
+    let public_key = PublicKey::try_decode_protobuf(&keypair.public().encode_protobuf()).unwrap();
+    assert_eq!(public_key.key_type(), KeyType::Ed25519);
+
+    let signature = keypair.sign(b"no_std").unwrap();
+    assert!(public_key.verify(b"no_std", &signature));
+    assert!(!public_key.verify(b"std", &signature));
+}
+
+#[test]
+#[cfg(feature = "dilithium-verify")]
+fn verify_dilithium_without_std() {
+    use libp2p_identity::dilithium::PublicKey;
+    use libp2p_identity::DilithiumLevel;
+
+    // Signing is not available without `std`; the key was seeded with `[7; 32]` and
+    // signed `no_std` in a full build.
+    let public_key = PublicKey::try_from_bytes(include_bytes!("../src/test/ml-dsa-44.pk")).unwrap();
+    let signature = include_bytes!("../src/test/ml-dsa-44.sig");
+    assert_eq!(public_key.level(), DilithiumLevel::Level2);
+
+    assert!(public_key.verify(b"no_std", signature));
+    assert!(!public_key.verify(b"std", signature));
+    assert!(public_key
+        .clone()
+        .into_prepared()
+        .verify(b"no_std", signature));
+
+    let mut verifier = public_key.verifier();
+    verifier.update(b"no_");
+    verifier.update(b"std");
+    assert!(verifier.finalize(signature));
+}
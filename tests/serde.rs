@@ -48,3 +48,54 @@ pub fn deserialize_peer_id_msgpack() {
 
     assert_eq!(peer_id, rmp_serde::from_read(&mut &buf[..]).unwrap());
 }
+
+#[cfg(feature = "dilithium")]
+mod dilithium {
+    use libp2p_identity::{Keypair, PublicKey};
+
+    #[test]
+    pub fn keypair_json_roundtrip() {
+        let keypair = Keypair::generate_dilithium();
+
+        let json = serde_json::to_string(&keypair).unwrap();
+        let decoded: Keypair = serde_json::from_str(&json).unwrap();
+
+        assert!(json.starts_with('"'));
+        assert_eq!(decoded.public(), keypair.public());
+        assert_eq!(decoded.secret(), keypair.secret());
+    }
+
+    #[test]
+    pub fn keypair_bincode_roundtrip() {
+        let keypair = Keypair::generate_dilithium();
+
+        let bytes = bincode::serialize(&keypair).unwrap();
+        let decoded: Keypair = bincode::deserialize(&bytes).unwrap();
+
+        assert_eq!(decoded.public(), keypair.public());
+        assert_eq!(decoded.secret(), keypair.secret());
+    }
+
+    #[test]
+    pub fn public_key_json_and_bincode_roundtrip() {
+        let public = Keypair::generate_dilithium().public();
+
+        let json = serde_json::to_string(&public).unwrap();
+        assert_eq!(public, serde_json::from_str::<PublicKey>(&json).unwrap());
+
+        let bytes = bincode::serialize(&public).unwrap();
+        assert_eq!(public, bincode::deserialize::<PublicKey>(&bytes).unwrap());
+    }
+
+    #[test]
+    pub fn mismatched_key_type_is_rejected() {
+        let mut encoded = Keypair::generate_dilithium().public().encode_protobuf();
+        // The first field is the key type: `08 04` is Dilithium, `08 01` is Ed25519.
+        assert_eq!(&encoded[..2], &[0x08, 0x04]);
+        encoded[1] = 0x01;
+
+        // bincode writes a `Vec<u8>` the same way as a byte string: a length prefix then the bytes.
+        let bytes = bincode::serialize(&encoded).unwrap();
+        assert!(bincode::deserialize::<PublicKey>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "sphincs-plus")]
+
+use libp2p_identity::{KeyType, Keypair, PublicKey};
+
+#[test]
+fn test_sphincs_plus_keypair_generation() {
+    let keypair = Keypair::generate_sphincs_plus();
+
+    assert_eq!(keypair.public().key_type(), KeyType::SphincsPlus);
+    assert_eq!(keypair.key_type(), KeyType::SphincsPlus);
+}
+
+#[test]
+fn test_sphincs_plus_sign_and_verify() {
+    let keypair = Keypair::generate_sphincs_plus();
+    let public_key = keypair.public();
+
+    let message = b"Hello, Post-Quantum World!";
+    let signature = keypair.sign(message).expect("Signing should succeed");
+
+    assert_eq!(Some(signature.len()), KeyType::SphincsPlus.signature_len());
+    assert!(
+        public_key.verify(message, &signature),
+        "Signature should be valid"
+    );
+    assert!(
+        !public_key.verify(b"Wrong message", &signature),
+        "Signature should be invalid for wrong message"
+    );
+}
+
+#[test]
+fn test_sphincs_plus_protobuf_roundtrip() {
+    let original_keypair = Keypair::generate_sphincs_plus();
+
+    let encoded = original_keypair
+        .to_protobuf_encoding()
+        .expect("Encoding should succeed");
+    let decoded_keypair =
+        Keypair::from_protobuf_encoding(&encoded).expect("Decoding should succeed");
+
+    assert_eq!(original_keypair.public(), decoded_keypair.public());
+
+    // Signing is deterministic, so the decoded keypair produces the same signature.
+    let message = b"Test message for roundtrip";
+    let signature = decoded_keypair
+        .sign(message)
+        .expect("Decoded signing should succeed");
+    assert_eq!(signature, original_keypair.sign(message).unwrap());
+    assert!(original_keypair.public().verify(message, &signature));
+}
+
+#[test]
+fn test_sphincs_plus_public_key_encoding() {
+    let public_key = Keypair::generate_sphincs_plus().public();
+
+    let encoded = public_key.encode_protobuf();
+    let decoded =
+        PublicKey::try_decode_protobuf(&encoded).expect("Public key decoding should succeed");
+
+    assert_eq!(public_key, decoded);
+    assert_eq!(decoded.key_type(), KeyType::SphincsPlus);
+}
+
+#[test]
+fn test_sphincs_plus_oversized_public_key_is_rejected() {
+    let mut encoded = Keypair::generate_sphincs_plus().public().encode_protobuf();
+    // Grow the length-delimited key data field by one byte.
+    let len_at = encoded.len() - 65;
+    encoded[len_at] += 1;
+    encoded.push(0);
+
+    assert!(PublicKey::try_decode_protobuf(&encoded).is_err());
+}
+
+#[test]
+fn test_sphincs_plus_peer_id_generation() {
+    let public_key = Keypair::generate_sphincs_plus().public();
+
+    let peer_id = public_key.to_peer_id();
+    assert_eq!(peer_id, public_key.to_peer_id());
+    assert!(public_key.matches_peer_id(&peer_id));
+
+    let other = Keypair::generate_sphincs_plus().public().to_peer_id();
+    assert_ne!(peer_id, other);
+}
@@ -0,0 +1,26 @@
+// Compile check of key generation and verification for the browser, where entropy
+// comes from `crypto.getRandomValues` through `getrandom`'s `js` backend:
+//
+//     cargo test --target wasm32-unknown-unknown --features wasm --test wasm --no-run
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use libp2p_identity::{KeyType, Keypair, PublicKey};
+
+#[test]
+fn generate_sign_and_verify() {
+    let keypair = Keypair::generate_dilithium();
+    let public_key = PublicKey::try_decode_protobuf(&keypair.public().encode_protobuf()).unwrap();
+    assert_eq!(public_key.key_type(), KeyType::Dilithium);
+
+    let signature = keypair.sign(b"wasm").unwrap();
+    assert!(public_key.verify(b"wasm", &signature));
+    assert!(!public_key.verify(b"native", &signature));
+
+    let _peer_id = public_key.to_peer_id();
+}
+
+#[test]
+fn generate_with_rng() {
+    let keypair = Keypair::generate_dilithium_with_rng(&mut rand::rngs::OsRng);
+    assert_eq!(keypair.key_type(), KeyType::Dilithium);
+}